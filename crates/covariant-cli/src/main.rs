@@ -1,7 +1,8 @@
 use std::path::{Path, PathBuf};
 use std::process;
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use covariant_thread::ThreadMode;
 
 /// COVARIANT — A functional programming language for 3D CAD design.
 #[derive(Parser)]
@@ -17,6 +18,9 @@ enum Command {
     Run {
         /// Path to the .cov source file.
         file: PathBuf,
+        /// How threaded holes are rendered.
+        #[arg(long, value_enum, default_value_t = Threads::None)]
+        threads: Threads,
     },
     /// Parse and check a .cov file without evaluating.
     Check {
@@ -28,15 +32,80 @@ enum Command {
         /// Path to the .cov source file.
         file: PathBuf,
     },
+    /// Evaluate a .cov file and report mass properties of the resulting solid(s).
+    Info {
+        /// Path to the .cov source file.
+        file: PathBuf,
+        /// Material density in g/cm³.
+        #[arg(long, default_value_t = 1.0)]
+        density: f64,
+        /// Tessellation tolerance in mm.
+        #[arg(long, default_value_t = 0.01)]
+        tolerance: f64,
+        /// How threaded holes are rendered.
+        #[arg(long, value_enum, default_value_t = Threads::None)]
+        threads: Threads,
+    },
+    /// Evaluate a .cov file and export the resulting solid(s).
+    ///
+    /// The format follows the output extension: `.step`/`.stp`, `.3mf`,
+    /// `.stl`, `.obj`, `.ply` or `.glb`.
+    Export {
+        /// Path to the .cov source file.
+        file: PathBuf,
+        /// Output file.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Product name written to STEP files, the model title of 3MF files,
+        /// or the body name of mesh files (defaults to the output file stem).
+        #[arg(long)]
+        name: Option<String>,
+        /// How threaded holes are rendered.
+        #[arg(long, value_enum, default_value_t = Threads::None)]
+        threads: Threads,
+    },
+}
+
+/// Thread rendering for `threaded_hole`.
+#[derive(Clone, Copy, ValueEnum)]
+enum Threads {
+    /// Plain holes at the tap drill diameter.
+    None,
+    /// Plain holes, with each thread recorded as a cosmetic feature.
+    Cosmetic,
+    /// Helical threads with the basic thread profile.
+    Full,
+}
+
+impl From<Threads> for ThreadMode {
+    fn from(threads: Threads) -> Self {
+        match threads {
+            Threads::None => ThreadMode::None,
+            Threads::Cosmetic => ThreadMode::Cosmetic,
+            Threads::Full => ThreadMode::Full,
+        }
+    }
 }
 
 fn main() {
     let cli = Cli::parse();
 
     let result = match cli.command {
-        Command::Run { ref file } => run(file),
+        Command::Run { ref file, threads } => run(file, threads.into()),
         Command::Check { ref file } => check(file),
         Command::Debug { ref file } => debug(file),
+        Command::Info {
+            ref file,
+            density,
+            tolerance,
+            threads,
+        } => info(file, density, tolerance, threads.into()),
+        Command::Export {
+            ref file,
+            ref output,
+            ref name,
+            threads,
+        } => export(file, output, name.clone(), threads.into()),
     };
 
     if let Err(msg) = result {
@@ -46,12 +115,17 @@ fn main() {
 }
 
 /// Read source, parse, lower, and evaluate.
-fn run(path: &Path) -> Result<(), String> {
+fn run(path: &Path, thread_mode: ThreadMode) -> Result<(), String> {
     let source = read_source(path)?;
     let dag = parse_and_lower(&source, path)?;
 
     let kernel = covariant_geom::TruckKernel;
-    covariant_eval::eval(&dag, &kernel).map_err(|e| format_eval_error(&e, &source, path))?;
+    let options = covariant_eval::EvalOptions {
+        thread_mode,
+        ..Default::default()
+    };
+    covariant_eval::eval_with(&dag, &kernel, &options)
+        .map_err(|e| format_eval_error(&e, &source, path))?;
 
     Ok(())
 }
@@ -89,6 +163,181 @@ fn debug(path: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// Read source, parse, lower, evaluate, and print mass properties of the result.
+///
+/// The program must evaluate to a solid or a list of solids.
+fn info(
+    path: &Path,
+    density: f64,
+    tolerance: f64,
+    thread_mode: ThreadMode,
+) -> Result<(), String> {
+    use covariant_geom::GeomKernel;
+
+    let kernel = covariant_geom::TruckKernel;
+    let solids = eval_solids(path, &kernel, thread_mode, "info")?;
+
+    // g/cm³ → g/mm³, so mass is in grams and inertia in g·mm².
+    let density_mm = density / 1000.0;
+    let mut total_mass = 0.0;
+    for (i, solid) in solids.iter().enumerate() {
+        let props = kernel
+            .mass_properties(solid, density_mm, tolerance)
+            .map_err(|e| format!("error: mass properties failed: {e}"))?;
+        if solids.len() > 1 {
+            println!("Solid {}:", i + 1);
+        }
+        print_mass_properties(&props);
+        total_mass += props.mass;
+    }
+    if solids.len() > 1 {
+        println!("Total mass: {total_mass:.3} g");
+    }
+    Ok(())
+}
+
+/// Read source, parse, lower, evaluate, and export the result.
+///
+/// The program must evaluate to a solid or a list of solids.
+fn export(
+    path: &Path,
+    output: &Path,
+    name: Option<String>,
+    thread_mode: ThreadMode,
+) -> Result<(), String> {
+    let extension = output
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let mesh_format = covariant_export::MeshFormat::from_path(output);
+    if !matches!(extension.as_str(), "step" | "stp" | "3mf") && mesh_format.is_none() {
+        return Err(format!(
+            "error: unsupported export format '{}' \
+             (expected .step, .stp, .3mf, .stl, .obj, .ply or .glb)",
+            output.display()
+        ));
+    }
+
+    let kernel = covariant_geom::TruckKernel;
+    let options = covariant_export::ExportOptions::default();
+    let solids = covariant_export::build_for_export(thread_mode, |mode| {
+        eval_solids(path, &kernel, mode, "export")
+    })?;
+    let stem = name.clone().unwrap_or_else(|| {
+        output
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "part".to_string())
+    });
+    let result = match extension.as_str() {
+        "step" | "stp" => {
+            let opts = covariant_export::StepOptions {
+                product_name: name,
+                ..Default::default()
+            };
+            covariant_export::export_step(&kernel, &solids, output, &opts)
+        }
+        "3mf" => {
+            let mut metadata = vec![(
+                "source".to_string(),
+                path.file_name()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+            )];
+            if let Some(title) = name {
+                metadata.push(("Title".to_string(), title));
+            }
+            let bodies = model_bodies(solids, &stem);
+            covariant_export::export_3mf(&kernel, &bodies, &metadata, output, &options)
+        }
+        _ => {
+            let opts = covariant_export::ExportOptions {
+                format: mesh_format.expect("format checked above"),
+                ..options
+            };
+            let bodies = model_bodies(solids, &stem);
+            covariant_export::export_mesh(&kernel, &bodies, output, &opts)
+        }
+    };
+    result.map_err(|e| format!("error: export failed: {e}"))?;
+    eprintln!("wrote {}", output.display());
+    Ok(())
+}
+
+/// Name solids for export: a lone solid takes `stem`, several are
+/// numbered `<stem>-1`, `<stem>-2`, ...
+fn model_bodies(
+    solids: Vec<covariant_geom::Solid>,
+    stem: &str,
+) -> Vec<covariant_export::ModelBody> {
+    let count = solids.len();
+    solids
+        .into_iter()
+        .enumerate()
+        .map(|(i, solid)| {
+            let name = if count == 1 {
+                stem.to_string()
+            } else {
+                format!("{stem}-{}", i + 1)
+            };
+            covariant_export::ModelBody::new(name, solid)
+        })
+        .collect()
+}
+
+/// Evaluate a file that must produce a solid or a non-empty list of solids.
+fn eval_solids(
+    path: &Path,
+    kernel: &covariant_geom::TruckKernel,
+    thread_mode: ThreadMode,
+    command: &str,
+) -> Result<Vec<covariant_geom::Solid>, String> {
+    use covariant_eval::Value;
+
+    let source = read_source(path)?;
+    let dag = parse_and_lower(&source, path)?;
+
+    let options = covariant_eval::EvalOptions {
+        thread_mode,
+        ..Default::default()
+    };
+    let output = covariant_eval::eval_with(&dag, kernel, &options)
+        .map_err(|e| format_eval_error(&e, &source, path))?;
+    match output.value {
+        Value::Solid(solid) => Ok(vec![solid]),
+        Value::List(items) if !items.is_empty() => items
+            .into_iter()
+            .map(|item| match item {
+                Value::Solid(solid) => Ok(solid),
+                other => Err(other.type_name().to_string()),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|ty| format!("error: {command} expects a list of Solid, found {ty}")),
+        other => Err(format!(
+            "error: {command} expects the program to produce a Solid, got {}",
+            other.type_name()
+        )),
+    }
+}
+
+fn print_mass_properties(props: &covariant_geom::MassProperties) {
+    let c = props.centroid;
+    println!("  Volume:   {:.3} mm³", props.volume);
+    println!("  Area:     {:.3} mm²", props.area);
+    println!("  Centroid: ({:.4}, {:.4}, {:.4}) mm", c.x, c.y, c.z);
+    println!("  Density:  {:.4} g/cm³", props.density * 1000.0);
+    println!("  Mass:     {:.3} g", props.mass);
+    for (label, tensor) in [
+        ("Inertia about centroid (g·mm²):", &props.inertia_centroid),
+        ("Inertia about origin (g·mm²):", &props.inertia_origin),
+    ] {
+        println!("  {label}");
+        for row in tensor {
+            println!("    [{:>14.4} {:>14.4} {:>14.4}]", row[0], row[1], row[2]);
+        }
+    }
+}
+
 fn read_source(path: &Path) -> Result<String, String> {
     std::fs::read_to_string(path)
         .map_err(|e| format!("error: cannot read '{}': {e}", path.display()))
@@ -17,7 +17,7 @@ pub struct DebugStep {
     pub node_id: NodeId,
     /// Source span of the producing expression.
     pub span: Span,
-    /// Optional label set by `trace()`.
+    /// Optional label set by `trace()` or a pattern builtin.
     pub label: Option<String>,
     /// The solid produced at this step.
     pub solid: Solid,
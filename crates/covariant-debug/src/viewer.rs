@@ -321,6 +321,5 @@ mod tests {
     #[test]
     fn viewer_module_compiles() {
         // Smoke test: this function existing means the module compiles.
-        assert!(true);
     }
 }
@@ -1,6 +1,6 @@
 //! Integration tests for the debug step collection system.
 
-use covariant_debug::{eval_debug, DebugSession};
+use covariant_debug::eval_debug;
 use covariant_geom::{GeomKernel, TruckKernel};
 
 /// Parse and lower source code into an IR DAG.
@@ -116,12 +116,19 @@ fn eval_debug_mounting_plate_example() {
     )
     .expect("eval_debug on mounting_plate should succeed");
 
-    // The mounting plate example has several geometry operations:
-    // plate(box), hole(threaded_hole), 4x move, union_many, difference, export_stl creates mesh
-    // At minimum: box + threaded_hole + 4 move + union_many + difference = 8 steps
+    // box, threaded_hole, move, grid_pattern, difference. The four hole
+    // copies and their union happen inside grid_pattern, which records a
+    // single labeled step instead of the old 4 × move + union_many.
+    assert_eq!(
+        session.step_count(),
+        5,
+        "expected 5 geometry steps from mounting_plate"
+    );
     assert!(
-        session.step_count() >= 7,
-        "expected at least 7 geometry steps from mounting_plate, got {}",
-        session.step_count()
+        session
+            .steps
+            .iter()
+            .any(|s| s.label.as_deref() == Some("grid_pattern: 2 × 2 at 60mm × 30mm")),
+        "expected a labeled grid_pattern step"
     );
 }
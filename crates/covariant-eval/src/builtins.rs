@@ -6,29 +6,48 @@
 use std::path::Path;
 use std::sync::Arc;
 
-use covariant_geom::{Point3, Vector3};
+use covariant_geom::thread::THREAD_TOLERANCE;
+use covariant_geom::{EdgeSelector, Matrix4, Point2, Point3, SweepFrame, Vector3};
 use covariant_thread::{
-    ThreadKind, ThreadSize, ThreadSpec, ThreadStandard, get_dimensions, hole_diameter,
+    ChamferParams, CylinderParams, HelixParams, ThreadCallout, ThreadGeometry, ThreadKind,
+    ThreadMode, ThreadSize, ThreadSpec, ThreadStandard, generate_thread_geometry,
 };
 
 use crate::env::Env;
 use crate::error::{EvalError, EvalErrorKind, EvalResult};
 use crate::eval::EvalCtx;
-use crate::value::{BuiltinFnPtr, Value};
+use crate::feature::Feature;
+use crate::types::Ty;
+use crate::value::{BuiltinFnPtr, BuiltinParam, Value};
 
 // ── Helpers ──────────────────────────────────────────────────────────────
 
-/// Register a built-in function in the environment.
-fn register(env: &mut Env, name: &str, func: BuiltinFnPtr) {
+/// Register a built-in function with its parameter signature in the environment.
+///
+/// The evaluator resolves named arguments and defaults against `params`
+/// and type-checks each argument before `func` is called, so `func` always
+/// receives exactly one value per parameter, in declaration order.
+fn register(env: &mut Env, name: &str, params: Vec<BuiltinParam>, func: BuiltinFnPtr) {
     env.define(
         name,
         Value::BuiltinFn {
             name: name.to_string(),
+            params: params.into(),
             func,
         },
     );
 }
 
+/// A required built-in parameter.
+fn req(name: &str, ty: Ty) -> BuiltinParam {
+    BuiltinParam::required(name, ty)
+}
+
+/// An optional built-in parameter with a default value.
+fn opt(name: &str, ty: Ty, default: Value) -> BuiltinParam {
+    BuiltinParam::optional(name, ty, default)
+}
+
 /// Extract a numeric f64 from a value (Int, Float, or Length), with an error message.
 fn expect_f64(val: &Value, arg_name: &str) -> EvalResult<f64> {
     val.as_f64().ok_or_else(|| {
@@ -78,6 +97,153 @@ fn expect_vec3(val: &Value, arg_name: &str) -> EvalResult<[f64; 3]> {
     }
 }
 
+/// Extract a Plane's origin and unit normal from a value.
+fn expect_plane(val: &Value, arg_name: &str) -> EvalResult<(Point3, Vector3)> {
+    match val {
+        Value::Plane { origin, normal } => Ok((
+            Point3::new(origin[0], origin[1], origin[2]),
+            Vector3::new(normal[0], normal[1], normal[2]),
+        )),
+        _ => Err(EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("expected Plane for '{arg_name}', got {}", val.type_name()),
+            None,
+        )),
+    }
+}
+
+/// Extract a non-zero Vec3 as a unit vector.
+fn expect_direction(val: &Value, arg_name: &str) -> EvalResult<Vector3> {
+    let v = expect_vec3(val, arg_name)?;
+    let v = Vector3::new(v[0], v[1], v[2]);
+    let length = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+    if length < 1e-12 {
+        return Err(EvalError::new(
+            EvalErrorKind::GeomError,
+            format!("'{arg_name}' must be non-zero"),
+            None,
+        ));
+    }
+    Ok(v / length)
+}
+
+/// Extract a 4×4 matrix from a list of four rows of four numbers.
+fn expect_matrix4(val: &Value, arg_name: &str) -> EvalResult<Matrix4> {
+    let bad = || {
+        EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("expected 4×4 matrix (4 rows of 4 numbers) for '{arg_name}'"),
+            None,
+        )
+    };
+    let rows = expect_list(val, arg_name)?;
+    if rows.len() != 4 {
+        return Err(bad());
+    }
+    // cgmath stores columns: `columns[j][i]` is row i, column j.
+    let mut columns = [[0.0; 4]; 4];
+    for (i, row) in rows.iter().enumerate() {
+        let Value::List(entries) = row else {
+            return Err(bad());
+        };
+        if entries.len() != 4 {
+            return Err(bad());
+        }
+        for (j, entry) in entries.iter().enumerate() {
+            columns[j][i] = entry.as_f64().ok_or_else(bad)?;
+        }
+    }
+    Ok(Matrix4::from(columns))
+}
+
+/// A Plane value through `origin`; the normal is normalized.
+fn plane_value(origin: [f64; 3], normal: [f64; 3]) -> EvalResult<Value> {
+    let length = normal.iter().map(|c| c * c).sum::<f64>().sqrt();
+    if length < 1e-12 {
+        return Err(EvalError::new(
+            EvalErrorKind::GeomError,
+            "plane normal must be non-zero".to_string(),
+            None,
+        ));
+    }
+    Ok(Value::Plane {
+        origin,
+        normal: normal.map(|c| c / length),
+    })
+}
+
+/// Extract a Vec2 from a value.
+fn expect_vec2(val: &Value, arg_name: &str) -> EvalResult<[f64; 2]> {
+    match val {
+        Value::Vec2(v) => Ok(*v),
+        _ => Err(EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("expected Vec2 for '{arg_name}', got {}", val.type_name()),
+            None,
+        )),
+    }
+}
+
+/// Extract a Solid, reporting that a Surface would also have been accepted.
+fn expect_solid_or_surface(val: &Value, arg_name: &str) -> EvalResult<covariant_geom::Solid> {
+    expect_solid(val, arg_name).map_err(|_| {
+        EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("expected Solid or Surface for '{arg_name}', got {}", val.type_name()),
+            None,
+        )
+    })
+}
+
+/// Extract a Face (Surface) from a value.
+fn expect_face(val: &Value, arg_name: &str) -> EvalResult<covariant_geom::Face> {
+    match val {
+        Value::Face(f) => Ok(f.clone()),
+        _ => Err(EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("expected Surface for '{arg_name}', got {}", val.type_name()),
+            None,
+        )),
+    }
+}
+
+/// Extract a Curve from a value.
+fn expect_curve(val: &Value, arg_name: &str) -> EvalResult<covariant_geom::Wire> {
+    match val {
+        Value::Curve(c) => Ok(c.clone()),
+        _ => Err(EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("expected Curve for '{arg_name}', got {}", val.type_name()),
+            None,
+        )),
+    }
+}
+
+/// Extract an angle in radians; plain numbers are taken as radians.
+fn expect_angle(val: &Value, arg_name: &str) -> EvalResult<f64> {
+    match val {
+        Value::Angle(a) => Ok(*a),
+        other => expect_f64(other, arg_name),
+    }
+}
+
+/// Extract a List from a value.
+fn expect_list<'v>(val: &'v Value, arg_name: &str) -> EvalResult<&'v [Value]> {
+    match val {
+        Value::List(items) => Ok(items),
+        _ => Err(EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("expected List for '{arg_name}', got {}", val.type_name()),
+            None,
+        )),
+    }
+}
+
+/// Convert a geometry kernel error into an evaluation error.
+fn geom_error(op: &str, err: covariant_geom::GeomError) -> EvalError {
+    EvalError::new(EvalErrorKind::GeomError, format!("{op} failed: {err}"), None)
+}
+
 /// Extract a String from a value.
 fn expect_string(val: &Value, arg_name: &str) -> EvalResult<String> {
     match val {
@@ -90,6 +256,143 @@ fn expect_string(val: &Value, arg_name: &str) -> EvalResult<String> {
     }
 }
 
+/// Extract a list of strings from a value.
+fn expect_strings(val: &Value, arg_name: &str) -> EvalResult<Vec<String>> {
+    expect_list(val, arg_name)?
+        .iter()
+        .map(|v| expect_string(v, arg_name))
+        .collect()
+}
+
+/// Extract an export quality preset: `"draft"`, `"standard"` or `"fine"`.
+fn expect_quality(val: &Value) -> EvalResult<covariant_export::Quality> {
+    match expect_string(val, "quality")?.as_str() {
+        "draft" => Ok(covariant_export::Quality::Draft),
+        "standard" => Ok(covariant_export::Quality::Standard),
+        "fine" => Ok(covariant_export::Quality::Fine),
+        other => Err(EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("unknown quality '{other}' (expected \"draft\", \"standard\" or \"fine\")"),
+            None,
+        )),
+    }
+}
+
+/// Build named export bodies from a Solid or List[Solid] and per-body
+/// names, colors and materials, where an empty string leaves the entry
+/// unset. Unnamed bodies are called after the file stem, numbered when
+/// there are several.
+fn expect_model_bodies(
+    path: &str,
+    bodies: &Value,
+    names: &Value,
+    colors: &Value,
+    materials: &Value,
+) -> EvalResult<Vec<covariant_export::ModelBody>> {
+    let solids = match bodies {
+        Value::List(items) => items
+            .iter()
+            .map(|v| expect_solid(v, "bodies"))
+            .collect::<EvalResult<Vec<_>>>()?,
+        other => vec![expect_solid(other, "bodies")?],
+    };
+    let names = expect_strings(names, "names")?;
+    let colors = expect_strings(colors, "colors")?;
+    let materials = expect_strings(materials, "materials")?;
+
+    let stem = Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "part".to_string());
+    let count = solids.len();
+    let mut out = Vec::with_capacity(count);
+    for (i, solid) in solids.into_iter().enumerate() {
+        let name = match names.get(i) {
+            Some(name) if !name.is_empty() => name.clone(),
+            _ if count == 1 => stem.clone(),
+            _ => format!("{stem}-{}", i + 1),
+        };
+        let mut body = covariant_export::ModelBody::new(name, solid);
+        if let Some(text) = colors.get(i).filter(|c| !c.is_empty()) {
+            body.color = Some(covariant_export::Color::parse(text).ok_or_else(|| {
+                EvalError::new(
+                    EvalErrorKind::TypeError,
+                    format!("invalid color '{text}' (expected #RRGGBB or #RRGGBBAA)"),
+                    None,
+                )
+            })?);
+        }
+        body.material = materials.get(i).filter(|m| !m.is_empty()).cloned();
+        out.push(body);
+    }
+    Ok(out)
+}
+
+/// Render a scalar design value as 3MF metadata text, in source syntax.
+fn metadata_text(val: &Value, field: &str) -> EvalResult<String> {
+    Ok(match val {
+        Value::Int(n) => n.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Length(l) => format!("{l}mm"),
+        Value::Angle(a) => format!("{}deg", a.to_degrees()),
+        Value::Bool(b) => b.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Vec2([x, y]) => format!("vec2({x}, {y})"),
+        Value::Vec3([x, y, z]) => format!("vec3({x}, {y}, {z})"),
+        Value::EnumVariant { variant, .. } => variant.clone(),
+        other => {
+            return Err(EvalError::new(
+                EvalErrorKind::TypeError,
+                format!(
+                    "metadata field '{field}' must be a number, string or vector, got {}",
+                    other.type_name()
+                ),
+                None,
+            ));
+        }
+    })
+}
+
+/// An `EdgeSelector` data value: a selection kind and its direction.
+fn edge_selector_value(kind: &str, direction: [f64; 3]) -> Value {
+    Value::Data {
+        type_name: "EdgeSelector".to_string(),
+        fields: vec![
+            ("kind".to_string(), Value::String(kind.to_string())),
+            ("direction".to_string(), Value::Vec3(direction)),
+        ],
+    }
+}
+
+/// Extract an EdgeSelector from a value built by `all_edges`,
+/// `edges_parallel` or `edges_on_face`.
+fn expect_edge_selector(val: &Value, arg_name: &str) -> EvalResult<EdgeSelector> {
+    let bad = || {
+        EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("expected EdgeSelector for '{arg_name}', got {}", val.type_name()),
+            None,
+        )
+    };
+    let Value::Data { type_name, fields } = val else {
+        return Err(bad());
+    };
+    if type_name != "EdgeSelector" {
+        return Err(bad());
+    }
+    let field = |name: &str| fields.iter().find(|(n, _)| n == name).map(|(_, v)| v);
+    let direction = match field("direction") {
+        Some(Value::Vec3(d)) => Vector3::new(d[0], d[1], d[2]),
+        _ => return Err(bad()),
+    };
+    match field("kind") {
+        Some(Value::String(kind)) if kind == "all" => Ok(EdgeSelector::All),
+        Some(Value::String(kind)) if kind == "parallel" => Ok(EdgeSelector::ParallelTo(direction)),
+        Some(Value::String(kind)) if kind == "face" => Ok(EdgeSelector::OnFace(direction)),
+        _ => Err(bad()),
+    }
+}
+
 /// Extract a ThreadStandard from an EnumVariant value.
 fn expect_thread_standard(val: &Value) -> EvalResult<ThreadStandard> {
     match val {
@@ -97,6 +400,8 @@ fn expect_thread_standard(val: &Value) -> EvalResult<ThreadStandard> {
             match variant.as_str() {
                 "IsoMetric" => Ok(ThreadStandard::IsoMetric),
                 "Uts" => Ok(ThreadStandard::Uts),
+                "Bsw" => Ok(ThreadStandard::Bsw),
+                "Bsp" => Ok(ThreadStandard::Bsp),
                 _ => Err(EvalError::new(
                     EvalErrorKind::TypeError,
                     format!("unknown ThreadStandard variant: {variant}"),
@@ -116,16 +421,13 @@ fn expect_thread_standard(val: &Value) -> EvalResult<ThreadStandard> {
 fn expect_thread_size(val: &Value) -> EvalResult<ThreadSize> {
     match val {
         Value::EnumVariant { type_name, variant } if type_name == "ThreadSize" => {
-            for size in ThreadSize::ALL {
-                if format!("{size:?}") == *variant {
-                    return Ok(*size);
-                }
-            }
-            Err(EvalError::new(
-                EvalErrorKind::TypeError,
-                format!("unknown ThreadSize variant: {variant}"),
-                None,
-            ))
+            variant.parse().map_err(|_| {
+                EvalError::new(
+                    EvalErrorKind::TypeError,
+                    format!("unknown ThreadSize variant: {variant}"),
+                    None,
+                )
+            })
         }
         _ => Err(EvalError::new(
             EvalErrorKind::TypeError,
@@ -161,24 +463,22 @@ fn expect_thread_kind(val: &Value) -> EvalResult<ThreadKind> {
     }
 }
 
-fn check_arity(name: &str, args: &[Value], expected: usize) -> EvalResult<()> {
-    if args.len() != expected {
-        return Err(EvalError::new(
-            EvalErrorKind::ArityMismatch,
-            format!("{name} expects {expected} argument(s), got {}", args.len()),
-            None,
-        ));
-    }
-    Ok(())
-}
-
 // ── Registration ─────────────────────────────────────────────────────────
 
 /// Register all built-in functions and enum constants into the environment.
 pub fn register_builtins(env: &mut Env) {
     register_geometric_primitives(env);
+    register_sketch_primitives(env);
+    register_curve_primitives(env);
+    register_sweep_ops(env);
     register_boolean_ops(env);
+    register_edge_treatments(env);
+    register_offsets(env);
+    register_hulls(env);
+    register_sections(env);
+    register_analysis(env);
     register_transforms(env);
+    register_patterns(env);
     register_thread_fn(env);
     register_utility(env);
     register_enum_constants(env);
@@ -189,8 +489,8 @@ fn register_geometric_primitives(env: &mut Env) {
     register(
         env,
         "box",
+        vec![req("size", Ty::Vec3)],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("box", args, 1)?;
             let v = expect_vec3(&args[0], "size")?;
             Ok(Value::Solid(ctx.kernel.box_solid(v[0], v[1], v[2])))
         }),
@@ -200,8 +500,8 @@ fn register_geometric_primitives(env: &mut Env) {
     register(
         env,
         "cylinder",
+        vec![req("radius", Ty::Length), req("height", Ty::Length)],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("cylinder", args, 2)?;
             let r = expect_length(&args[0], "radius")?;
             let h = expect_length(&args[1], "height")?;
             Ok(Value::Solid(ctx.kernel.cylinder(r, h)))
@@ -212,8 +512,8 @@ fn register_geometric_primitives(env: &mut Env) {
     register(
         env,
         "sphere",
+        vec![req("radius", Ty::Length)],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("sphere", args, 1)?;
             let r = expect_length(&args[0], "radius")?;
             Ok(Value::Solid(ctx.kernel.sphere(r)))
         }),
@@ -223,206 +523,1332 @@ fn register_geometric_primitives(env: &mut Env) {
     register(
         env,
         "vec3",
+        vec![req("x", Ty::Length), req("y", Ty::Length), req("z", Ty::Length)],
         Arc::new(|args: &[Value], _ctx: &mut EvalCtx<'_>| {
-            check_arity("vec3", args, 3)?;
             let x = expect_f64(&args[0], "x")?;
             let y = expect_f64(&args[1], "y")?;
             let z = expect_f64(&args[2], "z")?;
             Ok(Value::Vec3([x, y, z]))
         }),
     );
+
+    // vec2(x, y) -> Vec2
+    register(
+        env,
+        "vec2",
+        vec![req("x", Ty::Length), req("y", Ty::Length)],
+        Arc::new(|args: &[Value], _ctx: &mut EvalCtx<'_>| {
+            let x = expect_f64(&args[0], "x")?;
+            let y = expect_f64(&args[1], "y")?;
+            Ok(Value::Vec2([x, y]))
+        }),
+    );
+
+    // plane(origin: Vec3, normal: Vec3) -> Plane
+    register(
+        env,
+        "plane",
+        vec![req("origin", Ty::Vec3), req("normal", Ty::Vec3)],
+        Arc::new(|args: &[Value], _ctx: &mut EvalCtx<'_>| {
+            let origin = expect_vec3(&args[0], "origin")?;
+            let normal = expect_vec3(&args[1], "normal")?;
+            plane_value(origin, normal)
+        }),
+    );
+
+    // offset_plane(plane: Plane, distance: Length) -> Plane
+    register(
+        env,
+        "offset_plane",
+        vec![req("plane", Ty::Plane), req("distance", Ty::Length)],
+        Arc::new(|args: &[Value], _ctx: &mut EvalCtx<'_>| {
+            let (origin, normal) = expect_plane(&args[0], "plane")?;
+            let distance = expect_length(&args[1], "distance")?;
+            plane_value((origin + normal * distance).into(), normal.into())
+        }),
+    );
+
+    // Principal planes through the origin.
+    for (name, normal) in [
+        ("XY", [0.0, 0.0, 1.0]),
+        ("YZ", [1.0, 0.0, 0.0]),
+        ("XZ", [0.0, 1.0, 0.0]),
+    ] {
+        env.define(
+            name,
+            Value::Plane {
+                origin: [0.0, 0.0, 0.0],
+                normal,
+            },
+        );
+    }
 }
 
-fn register_boolean_ops(env: &mut Env) {
-    // union(a: Solid, b: Solid) -> Solid
+fn register_sketch_primitives(env: &mut Env) {
+    // circle2d(radius: Length, center: Vec2 = vec2(0, 0)) -> Surface
     register(
         env,
-        "union",
+        "circle2d",
+        vec![
+            req("radius", Ty::Length),
+            opt("center", Ty::Vec2, Value::Vec2([0.0, 0.0])),
+        ],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("union", args, 2)?;
-            let a = expect_solid(&args[0], "a")?;
-            let b = expect_solid(&args[1], "b")?;
-            ctx.kernel.union(&a, &b).map(Value::Solid).map_err(|e| {
-                EvalError::new(EvalErrorKind::GeomError, format!("union failed: {e}"), None)
-            })
+            let r = expect_length(&args[0], "radius")?;
+            let c = expect_vec2(&args[1], "center")?;
+            ctx.kernel
+                .circle_profile(Point2::new(c[0], c[1]), r)
+                .map(Value::Face)
+                .map_err(|e| geom_error("circle2d", e))
         }),
     );
 
-    // difference(a: Solid, b: Solid) -> Solid
+    // rectangle2d(size: Vec2, origin: Vec2 = vec2(0, 0)) -> Surface
     register(
         env,
-        "difference",
+        "rectangle2d",
+        vec![
+            req("size", Ty::Vec2),
+            opt("origin", Ty::Vec2, Value::Vec2([0.0, 0.0])),
+        ],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("difference", args, 2)?;
-            let a = expect_solid(&args[0], "a")?;
-            let b = expect_solid(&args[1], "b")?;
+            let size = expect_vec2(&args[0], "size")?;
+            let o = expect_vec2(&args[1], "origin")?;
             ctx.kernel
-                .difference(&a, &b)
-                .map(Value::Solid)
-                .map_err(|e| {
-                    EvalError::new(
-                        EvalErrorKind::GeomError,
-                        format!("difference failed: {e}"),
-                        None,
-                    )
-                })
+                .rectangle_profile(Point2::new(o[0], o[1]), size[0], size[1])
+                .map(Value::Face)
+                .map_err(|e| geom_error("rectangle2d", e))
         }),
     );
 
-    // intersect(a: Solid, b: Solid) -> Solid
+    // polygon2d(points: List[Vec2]) -> Surface
     register(
         env,
-        "intersect",
+        "polygon2d",
+        vec![req("points", Ty::List(Box::new(Ty::Vec2)))],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("intersect", args, 2)?;
-            let a = expect_solid(&args[0], "a")?;
-            let b = expect_solid(&args[1], "b")?;
-            ctx.kernel
-                .intersection(&a, &b)
-                .map(Value::Solid)
-                .map_err(|e| {
-                    EvalError::new(
-                        EvalErrorKind::GeomError,
-                        format!("intersect failed: {e}"),
-                        None,
-                    )
+            let points = expect_list(&args[0], "points")?
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    expect_vec2(v, &format!("points[{i}]")).map(|p| Point2::new(p[0], p[1]))
                 })
+                .collect::<EvalResult<Vec<_>>>()?;
+            ctx.kernel
+                .polygon_profile(&points)
+                .map(Value::Face)
+                .map_err(|e| geom_error("polygon2d", e))
         }),
     );
 
-    // union_many(solids: List[Solid]) -> Solid
+    // with_holes(outer: Surface, holes: List[Surface]) -> Surface
     register(
         env,
-        "union_many",
+        "with_holes",
+        vec![
+            req("outer", Ty::Surface),
+            req("holes", Ty::List(Box::new(Ty::Surface))),
+        ],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("union_many", args, 1)?;
-            let list = match &args[0] {
-                Value::List(items) => items,
-                _ => {
-                    return Err(EvalError::new(
-                        EvalErrorKind::TypeError,
-                        format!(
-                            "union_many expects List[Solid], got {}",
-                            args[0].type_name()
-                        ),
-                        None,
-                    ))
-                }
-            };
-            let solids: Vec<covariant_geom::Solid> = list
+            let outer = expect_face(&args[0], "outer")?;
+            let holes = expect_list(&args[1], "holes")?
                 .iter()
                 .enumerate()
-                .map(|(i, v)| expect_solid(v, &format!("solids[{i}]")))
+                .map(|(i, v)| expect_face(v, &format!("holes[{i}]")))
                 .collect::<EvalResult<Vec<_>>>()?;
             ctx.kernel
-                .union_many(&solids)
-                .map(Value::Solid)
-                .map_err(|e| {
-                    EvalError::new(
-                        EvalErrorKind::GeomError,
-                        format!("union_many failed: {e}"),
-                        None,
-                    )
-                })
+                .profile_with_holes(&outer, &holes)
+                .map(Value::Face)
+                .map_err(|e| geom_error("with_holes", e))
         }),
     );
 }
 
-fn register_transforms(env: &mut Env) {
-    // move(solid: Solid, v: Vec3) -> Solid
+fn register_curve_primitives(env: &mut Env) {
+    // line(p0: Vec3, p1: Vec3) -> Curve
     register(
         env,
-        "move",
+        "line",
+        vec![req("p0", Ty::Vec3), req("p1", Ty::Vec3)],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("move", args, 2)?;
-            let solid = expect_solid(&args[0], "solid")?;
-            let v = expect_vec3(&args[1], "v")?;
-            Ok(Value::Solid(
-                ctx.kernel.translate(&solid, Vector3::new(v[0], v[1], v[2])),
-            ))
+            let p0 = expect_vec3(&args[0], "p0")?;
+            let p1 = expect_vec3(&args[1], "p1")?;
+            ctx.kernel
+                .line_curve(
+                    Point3::new(p0[0], p0[1], p0[2]),
+                    Point3::new(p1[0], p1[1], p1[2]),
+                )
+                .map(Value::Curve)
+                .map_err(|e| geom_error("line", e))
         }),
     );
 
-    // rotate(solid: Solid, axis: Vec3, angle: Angle) -> Solid
+    // circle(center: Vec3, radius: Length, normal: Vec3 = vec3(0, 0, 1)) -> Curve
     register(
         env,
-        "rotate",
+        "circle",
+        vec![
+            req("center", Ty::Vec3),
+            req("radius", Ty::Length),
+            opt("normal", Ty::Vec3, Value::Vec3([0.0, 0.0, 1.0])),
+        ],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("rotate", args, 3)?;
-            let solid = expect_solid(&args[0], "solid")?;
-            let axis = expect_vec3(&args[1], "axis")?;
-            let angle = match &args[2] {
-                Value::Angle(a) => *a,
-                other => expect_f64(other, "angle")?,
-            };
-            Ok(Value::Solid(ctx.kernel.rotate(
-                &solid,
-                Point3::new(0.0, 0.0, 0.0),
-                Vector3::new(axis[0], axis[1], axis[2]),
-                angle,
-            )))
+            let c = expect_vec3(&args[0], "center")?;
+            let r = expect_length(&args[1], "radius")?;
+            let n = expect_vec3(&args[2], "normal")?;
+            ctx.kernel
+                .circle_curve(
+                    Point3::new(c[0], c[1], c[2]),
+                    r,
+                    Vector3::new(n[0], n[1], n[2]),
+                )
+                .map(Value::Curve)
+                .map_err(|e| geom_error("circle", e))
         }),
     );
 
-    // scale(solid: Solid, factor: Float) -> Solid
+    // arc(center: Vec3, radius: Length, start_angle: Angle, end_angle: Angle) -> Curve
     register(
         env,
-        "scale",
+        "arc",
+        vec![
+            req("center", Ty::Vec3),
+            req("radius", Ty::Length),
+            req("start_angle", Ty::Angle),
+            req("end_angle", Ty::Angle),
+        ],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("scale", args, 2)?;
-            let solid = expect_solid(&args[0], "solid")?;
-            let factor = expect_f64(&args[1], "factor")?;
-            Ok(Value::Solid(
-                ctx.kernel
-                    .scale(&solid, Point3::new(0.0, 0.0, 0.0), factor),
-            ))
+            let c = expect_vec3(&args[0], "center")?;
+            let r = expect_length(&args[1], "radius")?;
+            let a0 = expect_angle(&args[2], "start_angle")?;
+            let a1 = expect_angle(&args[3], "end_angle")?;
+            ctx.kernel
+                .arc_curve(Point3::new(c[0], c[1], c[2]), r, a0, a1)
+                .map(Value::Curve)
+                .map_err(|e| geom_error("arc", e))
         }),
     );
-}
 
-fn register_thread_fn(env: &mut Env) {
-    // threaded_hole(standard, size, kind, depth, chamfer) -> Solid
+    // helix(radius: Length, pitch: Length, height: Length,
+    //       left_handed: Bool = false) -> Curve
     register(
         env,
-        "threaded_hole",
+        "helix",
+        vec![
+            req("radius", Ty::Length),
+            req("pitch", Ty::Length),
+            req("height", Ty::Length),
+            opt("left_handed", Ty::Bool, Value::Bool(false)),
+        ],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("threaded_hole", args, 5)?;
-            let _standard = expect_thread_standard(&args[0])?;
-            let size = expect_thread_size(&args[1])?;
-            let kind = expect_thread_kind(&args[2])?;
-            let depth = expect_length(&args[3], "depth")?;
-            let _chamfer = expect_length(&args[4], "chamfer")?;
-
-            let spec = ThreadSpec::new(size, kind, depth, _chamfer);
-            let dims = get_dimensions(&spec).ok_or_else(|| {
-                EvalError::new(
-                    EvalErrorKind::Custom,
-                    format!("no thread dimensions for {size}"),
-                    None,
-                )
-            })?;
-            let hole_d = hole_diameter(&dims, kind);
-            let radius = hole_d / 2.0;
-            // Extend the hole slightly past both ends to avoid co-planar
-            // faces during boolean subtraction (standard CAD practice).
-            let ext = 0.1; // mm
-            let cyl = ctx.kernel.cylinder(radius, depth + 2.0 * ext);
-            let shifted = ctx
-                .kernel
-                .translate(&cyl, covariant_geom::Vector3::new(0.0, 0.0, -ext));
-            Ok(Value::Solid(shifted))
+            let radius = expect_length(&args[0], "radius")?;
+            let pitch = expect_length(&args[1], "pitch")?;
+            let height = expect_length(&args[2], "height")?;
+            let left_handed = matches!(args[3], Value::Bool(true));
+            ctx.kernel
+                .helix_curve(radius, pitch, height, left_handed)
+                .map(Value::Curve)
+                .map_err(|e| geom_error("helix", e))
         }),
     );
-}
 
-fn register_utility(env: &mut Env) {
-    // trace(label: String, value) -> value
+    // curve(f: Fn(t) -> Vec3, t_min, t_max, samples: Int = 64) -> Curve
+    //
+    // `t` is passed as an Angle when both bounds are Angles, otherwise as
+    // a Float.
     register(
         env,
-        "trace",
+        "curve",
+        vec![
+            req(
+                "f",
+                Ty::Fn {
+                    params: vec![Ty::Any],
+                    ret: Box::new(Ty::Vec3),
+                },
+            ),
+            req("t_min", Ty::Angle),
+            req("t_max", Ty::Angle),
+            opt("samples", Ty::Int, Value::Int(64)),
+        ],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("trace", args, 2)?;
-            let label = expect_string(&args[0], "label")?;
+            let func = args[0].clone();
+            let t_min = expect_angle(&args[1], "t_min")?;
+            let t_max = expect_angle(&args[2], "t_max")?;
+            let as_angle = matches!((&args[1], &args[2]), (Value::Angle(_), Value::Angle(_)));
+            let samples = match args[3] {
+                Value::Int(n) if n >= 2 => n as usize,
+                _ => {
+                    return Err(EvalError::new(
+                        EvalErrorKind::TypeError,
+                        "'samples' must be at least 2",
+                        None,
+                    ));
+                }
+            };
+            let points = (0..samples)
+                .map(|i| {
+                    let t = t_min + (t_max - t_min) * i as f64 / (samples - 1) as f64;
+                    let t = if as_angle { Value::Angle(t) } else { Value::Float(t) };
+                    let p = ctx.call_value(&func, &[t], None)?;
+                    expect_vec3(&p, "f(t)").map(|p| Point3::new(p[0], p[1], p[2]))
+                })
+                .collect::<EvalResult<Vec<_>>>()?;
+            ctx.kernel
+                .interpolated_curve(&points)
+                .map(Value::Curve)
+                .map_err(|e| geom_error("curve", e))
+        }),
+    );
+}
+
+fn register_sweep_ops(env: &mut Env) {
+    // extrude(profile: Surface, by: Length | Vec3) -> Solid
+    register(
+        env,
+        "extrude",
+        vec![req("profile", Ty::Surface), req("by", Ty::Any)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let profile = expect_face(&args[0], "profile")?;
+            let result = match &args[1] {
+                Value::Vec3(d) => ctx
+                    .kernel
+                    .sweep(&profile, Vector3::new(d[0], d[1], d[2])),
+                other => {
+                    let height = expect_length(other, "by").map_err(|_| {
+                        EvalError::new(
+                            EvalErrorKind::TypeError,
+                            format!(
+                                "expected Length or Vec3 for 'by', got {}",
+                                other.type_name()
+                            ),
+                            None,
+                        )
+                    })?;
+                    ctx.kernel.extrude(&profile, height)
+                }
+            };
+            result.map(Value::Solid).map_err(|e| geom_error("extrude", e))
+        }),
+    );
+
+    // sweep(profile: Surface, along: Curve, up: Vec3 | Unit = ()) -> Solid
+    //
+    // Without `up` the profile follows the path's Frenet frame.
+    register(
+        env,
+        "sweep",
+        vec![
+            req("profile", Ty::Surface),
+            req("along", Ty::Curve),
+            opt("up", Ty::Any, Value::Unit),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let profile = expect_face(&args[0], "profile")?;
+            let path = expect_curve(&args[1], "along")?;
+            let frame = match &args[2] {
+                Value::Unit => SweepFrame::Frenet,
+                other => {
+                    let up = expect_vec3(other, "up")?;
+                    SweepFrame::FixedUp(Vector3::new(up[0], up[1], up[2]))
+                }
+            };
+            ctx.kernel
+                .sweep_along(&profile, &path, frame)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("sweep", e))
+        }),
+    );
+
+    // loft(sections: List[Surface], ruled: Bool = false) -> Solid
+    register(
+        env,
+        "loft",
+        vec![
+            req("sections", Ty::List(Box::new(Ty::Surface))),
+            opt("ruled", Ty::Bool, Value::Bool(false)),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let sections = expect_list(&args[0], "sections")?
+                .iter()
+                .enumerate()
+                .map(|(i, v)| expect_face(v, &format!("sections[{i}]")))
+                .collect::<EvalResult<Vec<_>>>()?;
+            let ruled = matches!(args[1], Value::Bool(true));
+            ctx.kernel
+                .loft(&sections, ruled)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("loft", e))
+        }),
+    );
+
+    // revolve(profile: Surface, axis: Vec3, angle: Angle = 360deg,
+    //         origin: Vec3 = vec3(0, 0, 0)) -> Solid
+    register(
+        env,
+        "revolve",
+        vec![
+            req("profile", Ty::Surface),
+            req("axis", Ty::Vec3),
+            opt("angle", Ty::Angle, Value::Angle(std::f64::consts::TAU)),
+            opt("origin", Ty::Vec3, Value::Vec3([0.0, 0.0, 0.0])),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let profile = expect_face(&args[0], "profile")?;
+            let axis = expect_vec3(&args[1], "axis")?;
+            let angle = expect_angle(&args[2], "angle")?;
+            let origin = expect_vec3(&args[3], "origin")?;
+            ctx.kernel
+                .revolve(
+                    &profile,
+                    Point3::new(origin[0], origin[1], origin[2]),
+                    Vector3::new(axis[0], axis[1], axis[2]),
+                    angle,
+                )
+                .map(Value::Solid)
+                .map_err(|e| geom_error("revolve", e))
+        }),
+    );
+}
+
+fn register_boolean_ops(env: &mut Env) {
+    // union(a: Solid, b: Solid) -> Solid
+    register(
+        env,
+        "union",
+        vec![req("a", Ty::Solid), req("b", Ty::Solid)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let a = expect_solid(&args[0], "a")?;
+            let b = expect_solid(&args[1], "b")?;
+            ctx.kernel.union(&a, &b).map(Value::Solid).map_err(|e| {
+                EvalError::new(EvalErrorKind::GeomError, format!("union failed: {e}"), None)
+            })
+        }),
+    );
+
+    // difference(a: Solid, b: Solid) -> Solid
+    register(
+        env,
+        "difference",
+        vec![req("a", Ty::Solid), req("b", Ty::Solid)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let a = expect_solid(&args[0], "a")?;
+            let b = expect_solid(&args[1], "b")?;
+            ctx.kernel
+                .difference(&a, &b)
+                .map(Value::Solid)
+                .map_err(|e| {
+                    EvalError::new(
+                        EvalErrorKind::GeomError,
+                        format!("difference failed: {e}"),
+                        None,
+                    )
+                })
+        }),
+    );
+
+    // intersect(a: Solid, b: Solid) -> Solid
+    register(
+        env,
+        "intersect",
+        vec![req("a", Ty::Solid), req("b", Ty::Solid)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let a = expect_solid(&args[0], "a")?;
+            let b = expect_solid(&args[1], "b")?;
+            ctx.kernel
+                .intersection(&a, &b)
+                .map(Value::Solid)
+                .map_err(|e| {
+                    EvalError::new(
+                        EvalErrorKind::GeomError,
+                        format!("intersect failed: {e}"),
+                        None,
+                    )
+                })
+        }),
+    );
+
+    // union_many(solids: List[Solid]) -> Solid
+    register(
+        env,
+        "union_many",
+        vec![req("solids", Ty::List(Box::new(Ty::Solid)))],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let list = match &args[0] {
+                Value::List(items) => items,
+                _ => {
+                    return Err(EvalError::new(
+                        EvalErrorKind::TypeError,
+                        format!(
+                            "union_many expects List[Solid], got {}",
+                            args[0].type_name()
+                        ),
+                        None,
+                    ))
+                }
+            };
+            let solids: Vec<covariant_geom::Solid> = list
+                .iter()
+                .enumerate()
+                .map(|(i, v)| expect_solid(v, &format!("solids[{i}]")))
+                .collect::<EvalResult<Vec<_>>>()?;
+            ctx.kernel
+                .union_many(&solids)
+                .map(Value::Solid)
+                .map_err(|e| {
+                    EvalError::new(
+                        EvalErrorKind::GeomError,
+                        format!("union_many failed: {e}"),
+                        None,
+                    )
+                })
+        }),
+    );
+
+    // difference_many(base: Solid, cutters: List[Solid]) -> Solid
+    register(
+        env,
+        "difference_many",
+        vec![
+            req("base", Ty::Solid),
+            req("cutters", Ty::List(Box::new(Ty::Solid))),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let base = expect_solid(&args[0], "base")?;
+            let cutters = expect_list(&args[1], "cutters")?
+                .iter()
+                .enumerate()
+                .map(|(i, v)| expect_solid(v, &format!("cutters[{i}]")))
+                .collect::<EvalResult<Vec<_>>>()?;
+            ctx.kernel
+                .difference_many(&base, &cutters)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("difference_many", e))
+        }),
+    );
+}
+
+fn register_edge_treatments(env: &mut Env) {
+    // all_edges() -> EdgeSelector
+    register(
+        env,
+        "all_edges",
+        vec![],
+        Arc::new(|_args: &[Value], _ctx: &mut EvalCtx<'_>| {
+            Ok(edge_selector_value("all", [0.0, 0.0, 0.0]))
+        }),
+    );
+
+    // edges_parallel(axis: Vec3) -> EdgeSelector
+    register(
+        env,
+        "edges_parallel",
+        vec![req("axis", Ty::Vec3)],
+        Arc::new(|args: &[Value], _ctx: &mut EvalCtx<'_>| {
+            let axis = expect_vec3(&args[0], "axis")?;
+            Ok(edge_selector_value("parallel", axis))
+        }),
+    );
+
+    // edges_on_face(normal: Vec3) -> EdgeSelector
+    register(
+        env,
+        "edges_on_face",
+        vec![req("normal", Ty::Vec3)],
+        Arc::new(|args: &[Value], _ctx: &mut EvalCtx<'_>| {
+            let normal = expect_vec3(&args[0], "normal")?;
+            Ok(edge_selector_value("face", normal))
+        }),
+    );
+
+    // fillet(solid: Solid, radius: Length, edges: EdgeSelector = all_edges()) -> Solid
+    register(
+        env,
+        "fillet",
+        vec![
+            req("solid", Ty::Solid),
+            req("radius", Ty::Length),
+            opt(
+                "edges",
+                Ty::Data("EdgeSelector".to_string()),
+                edge_selector_value("all", [0.0, 0.0, 0.0]),
+            ),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let radius = expect_length(&args[1], "radius")?;
+            let edges = expect_edge_selector(&args[2], "edges")?;
+            ctx.kernel
+                .fillet(&solid, radius, &edges)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("fillet", e))
+        }),
+    );
+
+    // chamfer(solid: Solid, distance: Length, edges: EdgeSelector = all_edges()) -> Solid
+    register(
+        env,
+        "chamfer",
+        vec![
+            req("solid", Ty::Solid),
+            req("distance", Ty::Length),
+            opt(
+                "edges",
+                Ty::Data("EdgeSelector".to_string()),
+                edge_selector_value("all", [0.0, 0.0, 0.0]),
+            ),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let distance = expect_length(&args[1], "distance")?;
+            let edges = expect_edge_selector(&args[2], "edges")?;
+            ctx.kernel
+                .chamfer(&solid, distance, &edges)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("chamfer", e))
+        }),
+    );
+}
+
+fn register_offsets(env: &mut Env) {
+    // shell(solid: Solid, thickness: Length, open_faces: List[Vec3] = []) -> Solid
+    register(
+        env,
+        "shell",
+        vec![
+            req("solid", Ty::Solid),
+            req("thickness", Ty::Length),
+            opt("open_faces", Ty::List(Box::new(Ty::Vec3)), Value::List(vec![])),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let thickness = expect_length(&args[1], "thickness")?;
+            let open_faces = expect_list(&args[2], "open_faces")?
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    expect_vec3(v, &format!("open_faces[{i}]"))
+                        .map(|d| Vector3::new(d[0], d[1], d[2]))
+                })
+                .collect::<EvalResult<Vec<_>>>()?;
+            ctx.kernel
+                .shell(&solid, thickness, &open_faces)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("shell", e))
+        }),
+    );
+
+    // offset(solid: Solid, distance: Length) -> Solid
+    register(
+        env,
+        "offset",
+        vec![req("solid", Ty::Solid), req("distance", Ty::Length)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let distance = expect_length(&args[1], "distance")?;
+            ctx.kernel
+                .offset(&solid, distance)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("offset", e))
+        }),
+    );
+}
+
+fn register_hulls(env: &mut Env) {
+    // hull(solids: List[Solid]) -> Solid
+    register(
+        env,
+        "hull",
+        vec![req("solids", Ty::List(Box::new(Ty::Solid)))],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solids = expect_list(&args[0], "solids")?
+                .iter()
+                .enumerate()
+                .map(|(i, v)| expect_solid(v, &format!("solids[{i}]")))
+                .collect::<EvalResult<Vec<_>>>()?;
+            ctx.kernel
+                .hull(&solids)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("hull", e))
+        }),
+    );
+
+    // minkowski(a: Solid, b: Solid) -> Solid
+    register(
+        env,
+        "minkowski",
+        vec![req("a", Ty::Solid), req("b", Ty::Solid)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let a = expect_solid(&args[0], "a")?;
+            let b = expect_solid(&args[1], "b")?;
+            ctx.kernel
+                .minkowski(&a, &b)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("minkowski", e))
+        }),
+    );
+}
+
+fn register_sections(env: &mut Env) {
+    // section(solid: Solid, plane: Plane) -> List[Surface]
+    register(
+        env,
+        "section",
+        vec![req("solid", Ty::Solid), req("plane", Ty::Plane)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let (origin, normal) = expect_plane(&args[1], "plane")?;
+            ctx.kernel
+                .section(&solid, origin, normal)
+                .map(|faces| Value::List(faces.into_iter().map(Value::Face).collect()))
+                .map_err(|e| geom_error("section", e))
+        }),
+    );
+
+    // project(solid: Solid, plane: Plane) -> List[Surface]
+    register(
+        env,
+        "project",
+        vec![req("solid", Ty::Solid), req("plane", Ty::Plane)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let (origin, normal) = expect_plane(&args[1], "plane")?;
+            ctx.kernel
+                .project(&solid, origin, normal)
+                .map(|faces| Value::List(faces.into_iter().map(Value::Face).collect()))
+                .map_err(|e| geom_error("project", e))
+        }),
+    );
+}
+
+/// Tessellation tolerance used for mass properties unless one is given.
+const ANALYSIS_TOLERANCE: f64 = 0.01;
+
+fn register_analysis(env: &mut Env) {
+    // volume(solid: Solid, tolerance: Length = 0.01mm) -> Float (mm³)
+    register(
+        env,
+        "volume",
+        vec![
+            req("solid", Ty::Solid),
+            opt("tolerance", Ty::Length, Value::Length(ANALYSIS_TOLERANCE)),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let tolerance = expect_length(&args[1], "tolerance")?;
+            ctx.kernel
+                .mass_properties(&solid, 1.0, tolerance)
+                .map(|props| Value::Float(props.volume))
+                .map_err(|e| geom_error("volume", e))
+        }),
+    );
+
+    // centroid(solid: Solid, tolerance: Length = 0.01mm) -> Vec3
+    register(
+        env,
+        "centroid",
+        vec![
+            req("solid", Ty::Solid),
+            opt("tolerance", Ty::Length, Value::Length(ANALYSIS_TOLERANCE)),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let tolerance = expect_length(&args[1], "tolerance")?;
+            ctx.kernel
+                .mass_properties(&solid, 1.0, tolerance)
+                .map(|props| Value::Vec3([props.centroid.x, props.centroid.y, props.centroid.z]))
+                .map_err(|e| geom_error("centroid", e))
+        }),
+    );
+
+    // bbox(solid: Solid) -> BoundingBox { min, max, size, center: Vec3 }
+    register(
+        env,
+        "bbox",
+        vec![req("solid", Ty::Solid)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let bounds = ctx
+                .kernel
+                .bounding_box(&solid)
+                .map_err(|e| geom_error("bbox", e))?;
+            let (size, center) = (bounds.size(), bounds.center());
+            Ok(Value::Data {
+                type_name: "BoundingBox".to_string(),
+                fields: vec![
+                    ("min".to_string(), Value::Vec3(bounds.min.into())),
+                    ("max".to_string(), Value::Vec3(bounds.max.into())),
+                    ("size".to_string(), Value::Vec3(size.into())),
+                    ("center".to_string(), Value::Vec3(center.into())),
+                ],
+            })
+        }),
+    );
+
+    // closest_distance(a: Solid, b: Solid) -> Length
+    register(
+        env,
+        "closest_distance",
+        vec![req("a", Ty::Solid), req("b", Ty::Solid)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let a = expect_solid(&args[0], "a")?;
+            let b = expect_solid(&args[1], "b")?;
+            ctx.kernel
+                .closest_distance(&a, &b)
+                .map(Value::Length)
+                .map_err(|e| geom_error("closest_distance", e))
+        }),
+    );
+
+    // contains_point(solid: Solid, point: Vec3) -> Bool
+    register(
+        env,
+        "contains_point",
+        vec![req("solid", Ty::Solid), req("point", Ty::Vec3)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let p = expect_vec3(&args[1], "point")?;
+            ctx.kernel
+                .contains_point(&solid, Point3::new(p[0], p[1], p[2]))
+                .map(Value::Bool)
+                .map_err(|e| geom_error("contains_point", e))
+        }),
+    );
+}
+
+fn register_transforms(env: &mut Env) {
+    // move(solid: Solid | Surface, v: Vec3) -> Solid | Surface
+    register(
+        env,
+        "move",
+        vec![req("solid", Ty::Any), req("v", Ty::Vec3)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let v = expect_vec3(&args[1], "v")?;
+            let v = Vector3::new(v[0], v[1], v[2]);
+            match &args[0] {
+                Value::Face(profile) => Ok(Value::Face(ctx.kernel.translate_profile(profile, v))),
+                other => {
+                    let solid = expect_solid_or_surface(other, "solid")?;
+                    Ok(Value::Solid(ctx.kernel.translate(&solid, v)))
+                }
+            }
+        }),
+    );
+
+    // rotate(solid: Solid | Surface, axis: Vec3, angle: Angle,
+    //        origin: Vec3 = vec3(0, 0, 0)) -> Solid | Surface
+    register(
+        env,
+        "rotate",
+        vec![
+            req("solid", Ty::Any),
+            req("axis", Ty::Vec3),
+            req("angle", Ty::Angle),
+            opt("origin", Ty::Vec3, Value::Vec3([0.0, 0.0, 0.0])),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let axis = expect_vec3(&args[1], "axis")?;
+            let axis = Vector3::new(axis[0], axis[1], axis[2]);
+            let angle = expect_angle(&args[2], "angle")?;
+            let origin = expect_vec3(&args[3], "origin")?;
+            let origin = Point3::new(origin[0], origin[1], origin[2]);
+            match &args[0] {
+                Value::Face(profile) => Ok(Value::Face(
+                    ctx.kernel.rotate_profile(profile, origin, axis, angle),
+                )),
+                other => {
+                    let solid = expect_solid_or_surface(other, "solid")?;
+                    Ok(Value::Solid(ctx.kernel.rotate(&solid, origin, axis, angle)))
+                }
+            }
+        }),
+    );
+
+    // scale(solid: Solid, factor: Float | Vec3, center: Vec3 = vec3(0, 0, 0)) -> Solid
+    //
+    // A Vec3 factor scales each axis independently.
+    register(
+        env,
+        "scale",
+        vec![
+            req("solid", Ty::Solid),
+            req("factor", Ty::Any),
+            opt("center", Ty::Vec3, Value::Vec3([0.0, 0.0, 0.0])),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let factors = match &args[1] {
+                Value::Vec3(f) => Vector3::new(f[0], f[1], f[2]),
+                other => {
+                    let k = expect_f64(other, "factor").map_err(|_| {
+                        EvalError::new(
+                            EvalErrorKind::TypeError,
+                            format!(
+                                "expected Float or Vec3 for 'factor', got {}",
+                                other.type_name()
+                            ),
+                            None,
+                        )
+                    })?;
+                    Vector3::new(k, k, k)
+                }
+            };
+            let center = expect_vec3(&args[2], "center")?;
+            ctx.kernel
+                .scale(&solid, Point3::new(center[0], center[1], center[2]), factors)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("scale", e))
+        }),
+    );
+
+    // transform(solid: Solid, matrix: List[List[Float | Length]]) -> Solid
+    //
+    // `matrix` is 4×4, row-major, acting on column vectors; the last column
+    // holds the translation (mm) and the last row must be [0, 0, 0, 1].
+    register(
+        env,
+        "transform",
+        vec![
+            req("solid", Ty::Solid),
+            req("matrix", Ty::List(Box::new(Ty::List(Box::new(Ty::Any))))),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let matrix = expect_matrix4(&args[1], "matrix")?;
+            ctx.kernel
+                .transform(&solid, matrix)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("transform", e))
+        }),
+    );
+
+    // mirror(solid: Solid, plane: Plane) -> Solid
+    register(
+        env,
+        "mirror",
+        vec![req("solid", Ty::Solid), req("plane", Ty::Plane)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let (origin, normal) = expect_plane(&args[1], "plane")?;
+            Ok(Value::Solid(ctx.kernel.mirror(&solid, origin, normal)))
+        }),
+    );
+}
+
+/// Extract an instance count (an Int of at least 1).
+fn expect_count(val: &Value, arg_name: &str) -> EvalResult<usize> {
+    match val {
+        Value::Int(n) if *n >= 1 => Ok(*n as usize),
+        Value::Int(n) => Err(EvalError::new(
+            EvalErrorKind::Custom,
+            format!("'{arg_name}' must be at least 1, got {n}"),
+            None,
+        )),
+        _ => Err(EvalError::new(
+            EvalErrorKind::TypeError,
+            format!("'{arg_name}' must be an Int, got {}", val.type_name()),
+            None,
+        )),
+    }
+}
+
+/// Record a pattern as one feature and return its instances, fused into a
+/// single solid unless `fuse` is false.
+fn finish_pattern(
+    ctx: &mut EvalCtx<'_>,
+    op: &str,
+    feature: Feature,
+    instances: Vec<covariant_geom::Solid>,
+    fuse: bool,
+) -> EvalResult<Value> {
+    let result = if fuse {
+        let fused = ctx
+            .kernel
+            .union_many(&instances)
+            .map_err(|e| geom_error(op, e))?;
+        // Label the fused solid's debug step with the pattern, not N moves.
+        if ctx.debug_steps.is_some() && ctx.pending_label.is_none() {
+            ctx.pending_label = Some(feature.to_string());
+        }
+        Value::Solid(fused)
+    } else {
+        Value::List(instances.into_iter().map(Value::Solid).collect())
+    };
+    ctx.features.push(feature);
+    Ok(result)
+}
+
+fn register_patterns(env: &mut Env) {
+    // linear_pattern(solid: Solid, direction: Vec3, count: Int, spacing: Length,
+    //                fuse: Bool = true) -> Solid | List[Solid]
+    register(
+        env,
+        "linear_pattern",
+        vec![
+            req("solid", Ty::Solid),
+            req("direction", Ty::Vec3),
+            req("count", Ty::Int),
+            req("spacing", Ty::Length),
+            opt("fuse", Ty::Bool, Value::Bool(true)),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let direction = expect_direction(&args[1], "direction")?;
+            let count = expect_count(&args[2], "count")?;
+            let spacing = expect_length(&args[3], "spacing")?;
+            let fuse = matches!(args[4], Value::Bool(true));
+            let instances = (0..count)
+                .map(|i| ctx.kernel.translate(&solid, direction * (spacing * i as f64)))
+                .collect();
+            let feature = Feature::LinearPattern {
+                count,
+                direction: direction.into(),
+                spacing,
+            };
+            finish_pattern(ctx, "linear_pattern", feature, instances, fuse)
+        }),
+    );
+
+    // polar_pattern(solid: Solid, axis: Vec3, count: Int, angle: Angle = 360deg,
+    //               origin: Vec3 = vec3(0, 0, 0), fuse: Bool = true) -> Solid | List[Solid]
+    //
+    // A full turn spaces the copies evenly; a partial `angle` puts the first
+    // and last copies at its two ends.
+    register(
+        env,
+        "polar_pattern",
+        vec![
+            req("solid", Ty::Solid),
+            req("axis", Ty::Vec3),
+            req("count", Ty::Int),
+            opt("angle", Ty::Angle, Value::Angle(std::f64::consts::TAU)),
+            opt("origin", Ty::Vec3, Value::Vec3([0.0, 0.0, 0.0])),
+            opt("fuse", Ty::Bool, Value::Bool(true)),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let axis = expect_direction(&args[1], "axis")?;
+            let count = expect_count(&args[2], "count")?;
+            let angle = expect_angle(&args[3], "angle")?;
+            let origin = expect_vec3(&args[4], "origin")?;
+            let fuse = matches!(args[5], Value::Bool(true));
+            let step = if angle.abs() >= std::f64::consts::TAU - 1e-9 {
+                angle / count as f64
+            } else if count > 1 {
+                angle / (count - 1) as f64
+            } else {
+                0.0
+            };
+            let center = Point3::new(origin[0], origin[1], origin[2]);
+            let instances = (0..count)
+                .map(|i| ctx.kernel.rotate(&solid, center, axis, step * i as f64))
+                .collect();
+            let feature = Feature::PolarPattern {
+                count,
+                axis: axis.into(),
+                origin,
+                step,
+            };
+            finish_pattern(ctx, "polar_pattern", feature, instances, fuse)
+        }),
+    );
+
+    // grid_pattern(solid: Solid, nx: Int, ny: Int, dx: Length, dy: Length,
+    //              fuse: Bool = true) -> Solid | List[Solid]
+    register(
+        env,
+        "grid_pattern",
+        vec![
+            req("solid", Ty::Solid),
+            req("nx", Ty::Int),
+            req("ny", Ty::Int),
+            req("dx", Ty::Length),
+            req("dy", Ty::Length),
+            opt("fuse", Ty::Bool, Value::Bool(true)),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let solid = expect_solid(&args[0], "solid")?;
+            let nx = expect_count(&args[1], "nx")?;
+            let ny = expect_count(&args[2], "ny")?;
+            let dx = expect_length(&args[3], "dx")?;
+            let dy = expect_length(&args[4], "dy")?;
+            let fuse = matches!(args[5], Value::Bool(true));
+            let instances = (0..ny)
+                .flat_map(|j| (0..nx).map(move |i| Vector3::new(dx * i as f64, dy * j as f64, 0.0)))
+                .map(|offset| ctx.kernel.translate(&solid, offset))
+                .collect();
+            let feature = Feature::GridPattern { nx, ny, dx, dy };
+            finish_pattern(ctx, "grid_pattern", feature, instances, fuse)
+        }),
+    );
+}
+
+fn register_thread_fn(env: &mut Env) {
+    // threaded_hole(standard, size, kind, depth, chamfer = 0mm) -> Solid
+    register(
+        env,
+        "threaded_hole",
+        vec![
+            req("standard", Ty::Enum("ThreadStandard".to_string())),
+            req("size", Ty::Enum("ThreadSize".to_string())),
+            req("kind", Ty::Enum("ThreadKind".to_string())),
+            req("depth", Ty::Length),
+            opt("chamfer", Ty::Length, Value::Length(0.0)),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let standard = expect_thread_standard(&args[0])?;
+            let size = expect_thread_size(&args[1])?;
+            let kind = expect_thread_kind(&args[2])?;
+            let depth = expect_length(&args[3], "depth")?;
+            let chamfer = expect_length(&args[4], "chamfer")?;
+
+            if size.standard() != standard {
+                return Err(EvalError::new(
+                    EvalErrorKind::Custom,
+                    format!(
+                        "size {size} belongs to the {} standard, not {standard}",
+                        size.standard()
+                    ),
+                    None,
+                ));
+            }
+            thread_solid(ctx, &ThreadSpec::new(size, kind, depth, chamfer)).map(Value::Solid)
+        }),
+    );
+
+    // thread(callout, depth = callout length, chamfer = 0mm) -> Solid
+    register(
+        env,
+        "thread",
+        vec![
+            req("callout", Ty::String),
+            opt("depth", Ty::Optional(Box::new(Ty::Length)), Value::Unit),
+            opt("chamfer", Ty::Length, Value::Length(0.0)),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let text = expect_string(&args[0], "callout")?;
+            let callout: ThreadCallout = text
+                .parse()
+                .map_err(|msg| EvalError::new(EvalErrorKind::Custom, msg, None))?;
+            let depth = match &args[1] {
+                Value::Unit => callout.length.ok_or_else(|| {
+                    EvalError::new(
+                        EvalErrorKind::Custom,
+                        format!("'{text}' gives no length; pass a depth"),
+                        None,
+                    )
+                })?,
+                other => expect_length(other, "depth")?,
+            };
+            let chamfer = expect_length(&args[2], "chamfer")?;
+            thread_solid(ctx, &callout.spec(depth, chamfer)).map(Value::Solid)
+        }),
+    );
+}
+
+/// Solid for a thread spec in the session's thread mode: the cutter for a
+/// hole along +Z from its entrance at `z = 0`, or the rod of an external
+/// thread.
+fn thread_solid(ctx: &mut EvalCtx<'_>, spec: &ThreadSpec) -> EvalResult<covariant_geom::Solid> {
+    let ThreadSpec {
+        size,
+        kind,
+        depth,
+        chamfer,
+        ..
+    } = *spec;
+    if depth <= 0.0 || chamfer < 0.0 || chamfer >= depth {
+        return Err(EvalError::new(
+            EvalErrorKind::Custom,
+            format!(
+                "need depth > 0 and 0 <= chamfer < depth \
+                 (got depth {depth}mm, chamfer {chamfer}mm)"
+            ),
+            None,
+        ));
+    }
+
+    // Clearance and insert holes carry no thread of their own.
+    let mode = match kind {
+        ThreadKind::Internal | ThreadKind::External => ctx.thread_mode,
+        _ => ThreadMode::None,
+    };
+    let geometry = generate_thread_geometry(spec, mode).ok_or_else(|| {
+        EvalError::new(
+            EvalErrorKind::Custom,
+            format!("no thread dimensions for {size}"),
+            None,
+        )
+    })?;
+    match geometry {
+        ThreadGeometry::Simple { cylinder, chamfer } => {
+            plain_thread_solid(ctx, kind, cylinder, chamfer)
+        }
+        ThreadGeometry::Cosmetic {
+            cylinder,
+            chamfer,
+            annotation,
+        } => {
+            ctx.features.push(Feature::CosmeticThread {
+                size: size.to_string(),
+                diameter: annotation.diameter,
+                pitch: annotation.pitch,
+                depth: annotation.depth,
+            });
+            plain_thread_solid(ctx, kind, cylinder, chamfer)
+        }
+        ThreadGeometry::Full { chamfer, helix, .. } => full_thread_solid(ctx, kind, helix, chamfer),
+    }
+}
+
+/// How far hole cutters reach past both ends, so that subtracting them
+/// leaves no coplanar faces (standard CAD practice).
+const HOLE_OVERSHOOT: f64 = 0.1; // mm
+
+/// Fewest sides of the polygons that facet chamfers.
+const MIN_FACETS: usize = 16;
+
+/// Plain hole cutter along +Z from its entrance at `z = 0`, or a plain rod
+/// for external threads, with the chamfer at the entrance.
+fn plain_thread_solid(
+    ctx: &EvalCtx<'_>,
+    kind: ThreadKind,
+    cylinder: CylinderParams,
+    chamfer: Option<ChamferParams>,
+) -> EvalResult<covariant_geom::Solid> {
+    let r = cylinder.diameter / 2.0;
+    let d = cylinder.depth;
+    let e = HOLE_OVERSHOOT;
+    match (kind, chamfer) {
+        (ThreadKind::External, None) => Ok(ctx.kernel.cylinder(r, d)),
+        (ThreadKind::External, Some(ch)) => {
+            let c = external_chamfer(r, ch)?;
+            faceted(ctx, &[(r - c, 0.0), (r, c), (r, d)])
+        }
+        (_, None) => Ok(ctx.kernel.translate(
+            &ctx.kernel.cylinder(r, d + 2.0 * e),
+            Vector3::new(0.0, 0.0, -e),
+        )),
+        (_, Some(ch)) => faceted(
+            ctx,
+            &[(ch.outer_diameter / 2.0 + e, -e), (r, ch.depth), (r, d + e)],
+        ),
+    }
+}
+
+/// Helical thread along +Z: the cutter for a tapped hole, countersunk at
+/// its entrance, or a threaded rod with its start bevelled.
+fn full_thread_solid(
+    ctx: &EvalCtx<'_>,
+    kind: ThreadKind,
+    helix: HelixParams,
+    chamfer: Option<ChamferParams>,
+) -> EvalResult<covariant_geom::Solid> {
+    let e = HOLE_OVERSHOOT;
+    if kind == ThreadKind::External {
+        let rod = ctx
+            .kernel
+            .thread(&helix)
+            .map_err(|err| geom_error("thread", err))?;
+        let Some(ch) = chamfer else {
+            return Ok(rod);
+        };
+        let r = helix.major_diameter / 2.0;
+        let c = external_chamfer(r, ch)?;
+        let d = helix.depth;
+        let bevel = faceted(ctx, &[(r - c - e, -e), (r + e, c + e), (r + e, d + e)])?;
+        return ctx
+            .kernel
+            .intersection(&rod, &bevel)
+            .map_err(|err| geom_error("thread", err));
+    }
+
+    let thread = ctx
+        .kernel
+        .thread(&HelixParams {
+            depth: helix.depth + 2.0 * e,
+            ..helix
+        })
+        .map_err(|err| geom_error("thread", err))?;
+    let thread = ctx.kernel.translate(&thread, Vector3::new(0.0, 0.0, -e));
+    let Some(ch) = chamfer else {
+        return Ok(thread);
+    };
+    // The countersink starts below the thread's own end cap so the two
+    // share no face.
+    let cone = faceted(
+        ctx,
+        &[
+            (ch.outer_diameter / 2.0 + 2.0 * e, -2.0 * e),
+            (ch.inner_diameter / 2.0, ch.depth),
+        ],
+    )?;
+    ctx.kernel
+        .union(&thread, &cone)
+        .map_err(|err| geom_error("thread", err))
+}
+
+/// Ruled loft through regular polygons about the Z axis, given as
+/// `(radius, z)` pairs; each polygon encloses the circle of its radius.
+///
+/// Chamfers are faceted like the threads themselves (to within
+/// [`THREAD_TOLERANCE`]): the kernel's booleans fail on the cones of
+/// revolved profiles.
+fn faceted(ctx: &EvalCtx<'_>, sections: &[(f64, f64)]) -> EvalResult<covariant_geom::Solid> {
+    let largest = sections.iter().map(|&(r, _)| r).fold(0.0, f64::max);
+    let sides = ((std::f64::consts::PI / (1.0 - THREAD_TOLERANCE / largest).acos()).ceil()
+        as usize)
+        .max(MIN_FACETS);
+    let half_step = std::f64::consts::PI / sides as f64;
+    let sections = sections
+        .iter()
+        .map(|&(r, z)| {
+            let r = r / half_step.cos();
+            let points: Vec<Point2> = (0..sides)
+                .map(|i| {
+                    let a = 2.0 * half_step * i as f64;
+                    Point2::new(r * a.cos(), r * a.sin())
+                })
+                .collect();
+            let polygon = ctx
+                .kernel
+                .polygon_profile(&points)
+                .map_err(|e| geom_error("thread", e))?;
+            Ok(ctx
+                .kernel
+                .translate_profile(&polygon, Vector3::new(0.0, 0.0, z)))
+        })
+        .collect::<EvalResult<Vec<_>>>()?;
+    ctx.kernel
+        .loft(&sections, true)
+        .map_err(|e| geom_error("thread", e))
+}
+
+/// Bevel depth at the start of an external thread, which must leave some
+/// of the rod's end face.
+fn external_chamfer(radius: f64, chamfer: ChamferParams) -> EvalResult<f64> {
+    if chamfer.depth >= radius {
+        return Err(EvalError::new(
+            EvalErrorKind::Custom,
+            format!(
+                "chamfer {}mm is too large for a {}mm rod",
+                chamfer.depth,
+                2.0 * radius
+            ),
+            None,
+        ));
+    }
+    Ok(chamfer.depth)
+}
+
+fn register_utility(env: &mut Env) {
+    // trace(label: String, value) -> value
+    register(
+        env,
+        "trace",
+        vec![req("label", Ty::String), req("value", Ty::Any)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let label = expect_string(&args[0], "label")?;
             eprintln!("[trace] {label}: {:?}", args[1]);
             // In debug mode, set the label for the next geometry-producing step.
             if ctx.debug_steps.is_some() {
@@ -436,8 +1862,8 @@ fn register_utility(env: &mut Env) {
     register(
         env,
         "export_stl",
+        vec![req("path", Ty::String), req("solid", Ty::Solid)],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("export_stl", args, 2)?;
             let path = expect_string(&args[0], "path")?;
             let solid = expect_solid(&args[1], "solid")?;
             let opts = covariant_export::ExportOptions::default();
@@ -454,12 +1880,242 @@ fn register_utility(env: &mut Env) {
         }),
     );
 
+    // export_step(path: String, solid: Solid, name: String = "") -> Unit
+    register(
+        env,
+        "export_step",
+        vec![
+            req("path", Ty::String),
+            req("solid", Ty::Solid),
+            opt("name", Ty::String, Value::String(String::new())),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let path = expect_string(&args[0], "path")?;
+            let solid = expect_solid(&args[1], "solid")?;
+            let name = expect_string(&args[2], "name")?;
+            let opts = covariant_export::StepOptions {
+                product_name: (!name.is_empty()).then_some(name),
+                ..Default::default()
+            };
+            covariant_export::export_step(ctx.kernel, &[solid], Path::new(&path), &opts).map_err(
+                |e| {
+                    EvalError::new(
+                        EvalErrorKind::GeomError,
+                        format!("export_step failed: {e}"),
+                        None,
+                    )
+                },
+            )?;
+            Ok(Value::Unit)
+        }),
+    );
+
+    // export_3mf(path: String, bodies: Solid | List[Solid], names: List[String] = [],
+    //            colors: List[String] = [], materials: List[String] = [],
+    //            metadata: Data | Unit = (), quality: String = "standard") -> Unit
+    //
+    // Each solid becomes one named object. Colors are `#RRGGBB` or
+    // `#RRGGBBAA`; an empty string leaves that body uncoloured, as an empty
+    // material name leaves it without a material. The fields of a `data`
+    // value become model metadata.
+    register(
+        env,
+        "export_3mf",
+        vec![
+            req("path", Ty::String),
+            req("bodies", Ty::Any),
+            opt("names", Ty::List(Box::new(Ty::String)), Value::List(vec![])),
+            opt(
+                "colors",
+                Ty::List(Box::new(Ty::String)),
+                Value::List(vec![]),
+            ),
+            opt(
+                "materials",
+                Ty::List(Box::new(Ty::String)),
+                Value::List(vec![]),
+            ),
+            opt("metadata", Ty::Any, Value::Unit),
+            opt("quality", Ty::String, Value::String("standard".to_string())),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let path = expect_string(&args[0], "path")?;
+            let bodies = expect_model_bodies(&path, &args[1], &args[2], &args[3], &args[4])?;
+            let metadata = match &args[5] {
+                Value::Unit => Vec::new(),
+                Value::Data { fields, .. } => fields
+                    .iter()
+                    .map(|(name, value)| Ok((name.clone(), metadata_text(value, name)?)))
+                    .collect::<EvalResult<Vec<_>>>()?,
+                other => {
+                    return Err(EvalError::new(
+                        EvalErrorKind::TypeError,
+                        format!(
+                            "expected data value for 'metadata', got {}",
+                            other.type_name()
+                        ),
+                        None,
+                    ));
+                }
+            };
+            let quality = expect_quality(&args[6])?;
+            let opts = covariant_export::ExportOptions {
+                quality,
+                ..Default::default()
+            };
+            covariant_export::export_3mf(ctx.kernel, &bodies, &metadata, Path::new(&path), &opts)
+                .map_err(|e| {
+                EvalError::new(
+                    EvalErrorKind::GeomError,
+                    format!("export_3mf failed: {e}"),
+                    None,
+                )
+            })?;
+            Ok(Value::Unit)
+        }),
+    );
+
+    // export_mesh(path: String, bodies: Solid | List[Solid], names: List[String] = [],
+    //             colors: List[String] = [], quality: String = "standard") -> Unit
+    //
+    // The format follows the extension: `.stl`, `.obj`, `.ply` or `.glb`.
+    register(
+        env,
+        "export_mesh",
+        vec![
+            req("path", Ty::String),
+            req("bodies", Ty::Any),
+            opt("names", Ty::List(Box::new(Ty::String)), Value::List(vec![])),
+            opt(
+                "colors",
+                Ty::List(Box::new(Ty::String)),
+                Value::List(vec![]),
+            ),
+            opt("quality", Ty::String, Value::String("standard".to_string())),
+        ],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let path = expect_string(&args[0], "path")?;
+            let format =
+                covariant_export::MeshFormat::from_path(Path::new(&path)).ok_or_else(|| {
+                    EvalError::new(
+                        EvalErrorKind::TypeError,
+                        format!(
+                            "unsupported mesh format '{path}' (expected .stl, .obj, .ply or .glb)"
+                        ),
+                        None,
+                    )
+                })?;
+            let no_materials = Value::List(vec![]);
+            let bodies = expect_model_bodies(&path, &args[1], &args[2], &args[3], &no_materials)?;
+            let opts = covariant_export::ExportOptions {
+                quality: expect_quality(&args[4])?,
+                format,
+            };
+            covariant_export::export_mesh(ctx.kernel, &bodies, Path::new(&path), &opts).map_err(
+                |e| {
+                    EvalError::new(
+                        EvalErrorKind::GeomError,
+                        format!("export_mesh failed: {e}"),
+                        None,
+                    )
+                },
+            )?;
+            Ok(Value::Unit)
+        }),
+    );
+
+    // import_stl(path: String) -> Mesh
+    register(
+        env,
+        "import_stl",
+        vec![req("path", Ty::String)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let path = expect_string(&args[0], "path")?;
+            ctx.kernel
+                .import_stl(Path::new(&path))
+                .map(Value::Mesh)
+                .map_err(|e| geom_error("import_stl", e))
+        }),
+    );
+
+    // import_obj(path: String) -> Mesh
+    register(
+        env,
+        "import_obj",
+        vec![req("path", Ty::String)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let path = expect_string(&args[0], "path")?;
+            ctx.kernel
+                .import_obj(Path::new(&path))
+                .map(Value::Mesh)
+                .map_err(|e| geom_error("import_obj", e))
+        }),
+    );
+
+    // mesh_to_solid(mesh: Mesh) -> Solid
+    register(
+        env,
+        "mesh_to_solid",
+        vec![req("mesh", Ty::Mesh)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let Value::Mesh(mesh) = &args[0] else {
+                return Err(EvalError::new(
+                    EvalErrorKind::TypeError,
+                    format!("expected Mesh for 'mesh', got {}", args[0].type_name()),
+                    None,
+                ));
+            };
+            ctx.kernel
+                .mesh_to_solid(mesh)
+                .map(Value::Solid)
+                .map_err(|e| geom_error("mesh_to_solid", e))
+        }),
+    );
+
+    // save_solid(path: String, solid: Solid) -> Unit
+    register(
+        env,
+        "save_solid",
+        vec![req("path", Ty::String), req("solid", Ty::Solid)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let path = expect_string(&args[0], "path")?;
+            let solid = expect_solid(&args[1], "solid")?;
+            ctx.kernel
+                .save_solid(&solid, Path::new(&path))
+                .map_err(|e| geom_error("save_solid", e))?;
+            Ok(Value::Unit)
+        }),
+    );
+
+    // load_solid(path: String) -> Solid
+    register(
+        env,
+        "load_solid",
+        vec![req("path", Ty::String)],
+        Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
+            let path = expect_string(&args[0], "path")?;
+            ctx.kernel
+                .load_solid(Path::new(&path))
+                .map(Value::Solid)
+                .map_err(|e| geom_error("load_solid", e))
+        }),
+    );
+
     // map(fn, list) -> list
     register(
         env,
         "map",
+        vec![
+            req(
+                "f",
+                Ty::Fn {
+                    params: vec![Ty::Any],
+                    ret: Box::new(Ty::Any),
+                },
+            ),
+            req("list", Ty::List(Box::new(Ty::Any))),
+        ],
         Arc::new(|args: &[Value], ctx: &mut EvalCtx<'_>| {
-            check_arity("map", args, 2)?;
             let func = args[0].clone();
             let list = match &args[1] {
                 Value::List(items) => items.clone(),
@@ -496,15 +2152,33 @@ fn register_enum_constants(env: &mut Env) {
             variant: "Uts".to_string(),
         },
     );
+    env.define(
+        "BSW",
+        Value::EnumVariant {
+            type_name: "ThreadStandard".to_string(),
+            variant: "Bsw".to_string(),
+        },
+    );
+    env.define(
+        "BSP",
+        Value::EnumVariant {
+            type_name: "ThreadStandard".to_string(),
+            variant: "Bsp".to_string(),
+        },
+    );
 
-    // Thread sizes — ISO Metric
-    for size in ThreadSize::ALL {
-        let name = format!("{size:?}");
+    // Thread sizes — metric ones spelled as written with `_` for `.`
+    // (M1_6, M8x1, M10x1_25), inch ones named after the variants
+    for size in ThreadSize::all() {
+        let name = match size {
+            ThreadSize::Metric { .. } => size.to_string().replace('.', "_"),
+            _ => format!("{size:?}"),
+        };
         env.define(
             &name,
             Value::EnumVariant {
                 type_name: "ThreadSize".to_string(),
-                variant: name.clone(),
+                variant: size.to_string(),
             },
         );
     }
@@ -547,23 +2221,91 @@ mod tests {
         assert!(env.lookup("cylinder").is_some());
         assert!(env.lookup("sphere").is_some());
         assert!(env.lookup("vec3").is_some());
+        assert!(env.lookup("vec2").is_some());
+        // Sketch primitives
+        assert!(env.lookup("circle2d").is_some());
+        assert!(env.lookup("rectangle2d").is_some());
+        assert!(env.lookup("polygon2d").is_some());
+        assert!(env.lookup("with_holes").is_some());
+        // Curves
+        assert!(env.lookup("line").is_some());
+        assert!(env.lookup("circle").is_some());
+        assert!(env.lookup("arc").is_some());
+        assert!(env.lookup("helix").is_some());
+        assert!(env.lookup("curve").is_some());
+        // Sweeps
+        assert!(env.lookup("extrude").is_some());
+        assert!(env.lookup("sweep").is_some());
+        assert!(env.lookup("loft").is_some());
+        assert!(env.lookup("revolve").is_some());
         // Booleans
         assert!(env.lookup("union").is_some());
         assert!(env.lookup("difference").is_some());
         assert!(env.lookup("intersect").is_some());
         assert!(env.lookup("union_many").is_some());
+        assert!(env.lookup("difference_many").is_some());
+        // Edge treatments
+        assert!(env.lookup("all_edges").is_some());
+        assert!(env.lookup("edges_parallel").is_some());
+        assert!(env.lookup("edges_on_face").is_some());
+        assert!(env.lookup("fillet").is_some());
+        assert!(env.lookup("chamfer").is_some());
+        // Offsets
+        assert!(env.lookup("shell").is_some());
+        assert!(env.lookup("offset").is_some());
+        // Hulls
+        assert!(env.lookup("hull").is_some());
+        assert!(env.lookup("minkowski").is_some());
+        // Sections
+        assert!(env.lookup("section").is_some());
+        assert!(env.lookup("project").is_some());
+        // Analysis
+        assert!(env.lookup("volume").is_some());
+        assert!(env.lookup("centroid").is_some());
+        assert!(env.lookup("bbox").is_some());
+        assert!(env.lookup("closest_distance").is_some());
+        assert!(env.lookup("contains_point").is_some());
         // Transforms
         assert!(env.lookup("move").is_some());
         assert!(env.lookup("rotate").is_some());
         assert!(env.lookup("scale").is_some());
+        assert!(env.lookup("mirror").is_some());
+        assert!(env.lookup("transform").is_some());
+        // Patterns
+        assert!(env.lookup("linear_pattern").is_some());
+        assert!(env.lookup("polar_pattern").is_some());
+        assert!(env.lookup("grid_pattern").is_some());
+        assert!(env.lookup("plane").is_some());
+        assert!(env.lookup("offset_plane").is_some());
         // Thread
         assert!(env.lookup("threaded_hole").is_some());
+        assert!(env.lookup("thread").is_some());
         // Utility
         assert!(env.lookup("trace").is_some());
         assert!(env.lookup("export_stl").is_some());
+        assert!(env.lookup("export_step").is_some());
+        assert!(env.lookup("export_3mf").is_some());
+        assert!(env.lookup("export_mesh").is_some());
+        assert!(env.lookup("import_stl").is_some());
+        assert!(env.lookup("import_obj").is_some());
+        assert!(env.lookup("mesh_to_solid").is_some());
+        assert!(env.lookup("save_solid").is_some());
+        assert!(env.lookup("load_solid").is_some());
         assert!(env.lookup("map").is_some());
     }
 
+    #[test]
+    fn principal_planes_registered() {
+        let mut env = Env::new();
+        register_builtins(&mut env);
+        assert!(matches!(
+            env.lookup("XY"),
+            Some(Value::Plane { origin, normal }) if *origin == [0.0; 3] && *normal == [0.0, 0.0, 1.0]
+        ));
+        assert!(matches!(env.lookup("YZ"), Some(Value::Plane { normal, .. }) if normal[0] == 1.0));
+        assert!(matches!(env.lookup("XZ"), Some(Value::Plane { normal, .. }) if normal[1] == 1.0));
+    }
+
     #[test]
     fn enum_constants_registered() {
         let mut env = Env::new();
@@ -579,10 +2321,25 @@ mod tests {
             Some(Value::EnumVariant { type_name, variant })
             if type_name == "ThreadStandard" && variant == "Uts"
         ));
+        assert!(matches!(
+            env.lookup("BSW"),
+            Some(Value::EnumVariant { type_name, variant })
+            if type_name == "ThreadStandard" && variant == "Bsw"
+        ));
+        assert!(matches!(
+            env.lookup("BSP"),
+            Some(Value::EnumVariant { type_name, variant })
+            if type_name == "ThreadStandard" && variant == "Bsp"
+        ));
         // Thread sizes
         assert!(env.lookup("M3").is_some());
         assert!(env.lookup("M5").is_some());
         assert!(env.lookup("M10").is_some());
+        assert!(env.lookup("M3_5").is_some());
+        assert!(env.lookup("M64").is_some());
+        assert!(env.lookup("M10x1_25").is_some());
+        assert!(env.lookup("Bsw1_4").is_some());
+        assert!(env.lookup("G1_1_2").is_some());
         // Thread kinds
         assert!(matches!(
             env.lookup("TAP"),
@@ -5,12 +5,14 @@ use covariant_ir::node::{IrArg, IrNode};
 use covariant_ir::{Dag, NodeId};
 use covariant_syntax::ast::{BinOpKind, Pattern, UnaryOpKind};
 use covariant_syntax::Span;
+use covariant_thread::ThreadMode;
 
 use crate::builtins::register_builtins;
 use crate::env::Env;
 use crate::error::{EvalError, EvalErrorKind, EvalResult};
+use crate::feature::Feature;
 use crate::units::{angle_to_rad, length_to_mm};
-use crate::value::{FnParam, Value};
+use crate::value::{BuiltinParam, FnParam, Value};
 
 /// A raw debug step record: (node_id, span, label, solid).
 pub type RawDebugStep = (NodeId, Span, Option<String>, covariant_geom::Solid);
@@ -26,21 +28,75 @@ pub struct EvalCtx<'a> {
     pub debug_steps: Option<Vec<RawDebugStep>>,
     /// Label set by `trace()` for the next geometry-producing step.
     pub pending_label: Option<String>,
+    /// Engineering features (patterns, ...) in evaluation order.
+    pub features: Vec<Feature>,
+    /// How `threaded_hole` and `thread` render threads; `ThreadMode::None`
+    /// by default.
+    pub thread_mode: ThreadMode,
 }
 
-/// Evaluate an IR DAG, returning the value of the last root node.
-pub fn eval(dag: &Dag, kernel: &dyn GeomKernel) -> EvalResult<Value> {
+/// Options for [`eval_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct EvalOptions {
+    /// How `threaded_hole` and `thread` render threads: plain holes
+    /// (`None`), plain holes logged as cosmetic thread features
+    /// (`Cosmetic`), or helical thread geometry (`Full`).
+    pub thread_mode: ThreadMode,
+    /// Collect geometry-producing steps for debug visualization.
+    pub debug_steps: bool,
+}
+
+impl Default for EvalOptions {
+    fn default() -> Self {
+        Self {
+            thread_mode: ThreadMode::None,
+            debug_steps: false,
+        }
+    }
+}
+
+/// Everything an evaluation produces.
+#[derive(Debug)]
+pub struct EvalOutput {
+    /// The value of the last root node.
+    pub value: Value,
+    /// Engineering features (patterns, threads, ...) in evaluation order.
+    pub features: Vec<Feature>,
+    /// Raw debug steps `(node_id, span, label, solid)`, one per
+    /// geometry-producing call; empty unless `debug_steps` was requested.
+    pub debug_steps: Vec<RawDebugStep>,
+}
+
+/// Evaluate an IR DAG with the given options.
+pub fn eval_with(
+    dag: &Dag,
+    kernel: &dyn GeomKernel,
+    options: &EvalOptions,
+) -> EvalResult<EvalOutput> {
     let mut env = Env::new();
     register_builtins(&mut env);
 
     let mut ctx = EvalCtx::new(dag, env, kernel);
+    ctx.thread_mode = options.thread_mode;
+    if options.debug_steps {
+        ctx.debug_steps = Some(Vec::new());
+    }
 
     let roots = dag.roots();
-    let mut last = Value::Unit;
+    let mut value = Value::Unit;
     for &root in roots {
-        last = ctx.eval_node(root)?;
+        value = ctx.eval_node(root)?;
     }
-    Ok(last)
+    Ok(EvalOutput {
+        value,
+        features: ctx.features,
+        debug_steps: ctx.debug_steps.unwrap_or_default(),
+    })
+}
+
+/// Evaluate an IR DAG, returning the value of the last root node.
+pub fn eval(dag: &Dag, kernel: &dyn GeomKernel) -> EvalResult<Value> {
+    eval_with(dag, kernel, &EvalOptions::default()).map(|out| out.value)
 }
 
 /// Evaluate an IR DAG with debug step collection enabled.
@@ -51,20 +107,11 @@ pub fn eval_debug(
     dag: &Dag,
     kernel: &dyn GeomKernel,
 ) -> EvalResult<(Value, Vec<RawDebugStep>)> {
-    let mut env = Env::new();
-    register_builtins(&mut env);
-
-    let mut ctx = EvalCtx::new(dag, env, kernel);
-    ctx.debug_steps = Some(Vec::new());
-
-    let roots = dag.roots();
-    let mut last = Value::Unit;
-    for &root in roots {
-        last = ctx.eval_node(root)?;
-    }
-
-    let steps = ctx.debug_steps.take().unwrap_or_default();
-    Ok((last, steps))
+    let options = EvalOptions {
+        debug_steps: true,
+        ..EvalOptions::default()
+    };
+    eval_with(dag, kernel, &options).map(|out| (out.value, out.debug_steps))
 }
 
 impl<'a> EvalCtx<'a> {
@@ -77,6 +124,8 @@ impl<'a> EvalCtx<'a> {
             data_types: std::collections::HashMap::new(),
             debug_steps: None,
             pending_label: None,
+            features: Vec::new(),
+            thread_mode: ThreadMode::None,
         }
     }
 
@@ -146,6 +195,15 @@ impl<'a> EvalCtx<'a> {
                                 Some(field.span),
                             )
                         }),
+                    Value::Plane { origin, normal } => match field.node.as_str() {
+                        "origin" => Ok(Value::Vec3(origin)),
+                        "normal" => Ok(Value::Vec3(normal)),
+                        _ => Err(EvalError::new(
+                            EvalErrorKind::FieldNotFound,
+                            format!("field '{}' not found on Plane", field.node),
+                            Some(field.span),
+                        )),
+                    },
                     _ => Err(EvalError::new(
                         EvalErrorKind::TypeError,
                         format!(
@@ -488,16 +546,16 @@ impl<'a> EvalCtx<'a> {
         span: Span,
     ) -> EvalResult<Value> {
         match func_val {
-            Value::BuiltinFn { name, func } => {
-                let args = self.eval_args_positional(ir_args)?;
-                func(&args, self).map_err(|mut e| {
+            Value::BuiltinFn { name, params, func } => self
+                .resolve_builtin_args(&params, ir_args, span)
+                .and_then(|args| func(&args, self))
+                .map_err(|mut e| {
                     if e.span.is_none() {
                         e.span = Some(span);
                     }
                     e.message = format!("{name}: {}", e.message);
                     e
-                })
-            }
+                }),
             Value::Function {
                 params,
                 body,
@@ -532,7 +590,32 @@ impl<'a> EvalCtx<'a> {
         span: Option<Span>,
     ) -> EvalResult<Value> {
         match func {
-            Value::BuiltinFn { func: f, .. } => f(args, self),
+            Value::BuiltinFn {
+                name,
+                params,
+                func: f,
+            } => {
+                if args.len() > params.len() {
+                    return Err(EvalError::new(
+                        EvalErrorKind::ArityMismatch,
+                        format!(
+                            "{name}: too many arguments: expected {}, got {}",
+                            params.len(),
+                            args.len(),
+                        ),
+                        span,
+                    ));
+                }
+                let mut slots: Vec<Option<Value>> = args.iter().cloned().map(Some).collect();
+                slots.resize(params.len(), None);
+                let args = self
+                    .complete_builtin_args(params, slots, span)
+                    .map_err(|mut e| {
+                        e.message = format!("{name}: {}", e.message);
+                        e
+                    })?;
+                f(&args, self)
+            }
             Value::Function {
                 params,
                 body,
@@ -556,13 +639,6 @@ impl<'a> EvalCtx<'a> {
         }
     }
 
-    /// Evaluate all arguments positionally (for builtins).
-    fn eval_args_positional(&mut self, args: &[IrArg]) -> EvalResult<Vec<Value>> {
-        args.iter()
-            .map(|arg| self.eval_node(arg.value))
-            .collect()
-    }
-
     /// Resolve arguments to match function parameters, handling named args + defaults.
     fn resolve_args(
         &mut self,
@@ -570,16 +646,95 @@ impl<'a> EvalCtx<'a> {
         ir_args: &[IrArg],
         span: Span,
     ) -> EvalResult<Vec<Value>> {
-        let mut result = vec![None; params.len()];
+        let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+        let mut result = self.bind_args(&names, ir_args, span)?;
+
+        // Fill in defaults for missing args.
+        for (i, param) in params.iter().enumerate() {
+            if result[i].is_none() {
+                if let Some(default_node) = param.default {
+                    let val = self.eval_node(default_node)?;
+                    result[i] = Some(val);
+                } else {
+                    return Err(EvalError::new(
+                        EvalErrorKind::ArityMismatch,
+                        format!("missing argument '{}'", param.name),
+                        Some(span),
+                    ));
+                }
+            }
+        }
+
+        Ok(result.into_iter().map(|v| v.unwrap()).collect())
+    }
+
+    /// Resolve arguments against a built-in signature, handling named args,
+    /// defaults, and parameter type checks.
+    fn resolve_builtin_args(
+        &mut self,
+        params: &[BuiltinParam],
+        ir_args: &[IrArg],
+        span: Span,
+    ) -> EvalResult<Vec<Value>> {
+        let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+        let slots = self.bind_args(&names, ir_args, span)?;
+        self.complete_builtin_args(params, slots, Some(span))
+    }
+
+    /// Fill built-in defaults for missing arguments and check every argument
+    /// against its declared type.
+    fn complete_builtin_args(
+        &self,
+        params: &[BuiltinParam],
+        slots: Vec<Option<Value>>,
+        span: Option<Span>,
+    ) -> EvalResult<Vec<Value>> {
+        params
+            .iter()
+            .zip(slots)
+            .map(|(param, slot)| {
+                let val = slot.or_else(|| param.default.clone()).ok_or_else(|| {
+                    EvalError::new(
+                        EvalErrorKind::ArityMismatch,
+                        format!("missing argument '{}'", param.name),
+                        span,
+                    )
+                })?;
+                if !param.ty.accepts(&val) {
+                    return Err(EvalError::new(
+                        EvalErrorKind::TypeError,
+                        format!(
+                            "expected {} for '{}', got {}",
+                            param.ty,
+                            param.name,
+                            val.type_name()
+                        ),
+                        span,
+                    ));
+                }
+                Ok(val)
+            })
+            .collect()
+    }
+
+    /// Evaluate call arguments into parameter slots by position or by name.
+    ///
+    /// Slots for parameters that were not given are left as `None`.
+    fn bind_args(
+        &mut self,
+        names: &[&str],
+        ir_args: &[IrArg],
+        span: Span,
+    ) -> EvalResult<Vec<Option<Value>>> {
+        let mut result = vec![None; names.len()];
 
-        // First pass: positional args.
         let mut positional_idx = 0;
         for arg in ir_args {
             if let Some(ref name_spanned) = arg.name {
                 // Named argument — find the param.
-                let param_idx = params
+                let param_idx = names
                     .iter()
-                    .position(|p| p.name == name_spanned.node)
+                    .position(|p| *p == name_spanned.node)
                     .ok_or_else(|| {
                         EvalError::new(
                             EvalErrorKind::ArityMismatch,
@@ -587,44 +742,45 @@ impl<'a> EvalCtx<'a> {
                             Some(name_spanned.span),
                         )
                     })?;
+                if result[param_idx].is_some() {
+                    return Err(EvalError::new(
+                        EvalErrorKind::ArityMismatch,
+                        format!("argument '{}' given more than once", name_spanned.node),
+                        Some(name_spanned.span),
+                    ));
+                }
                 let val = self.eval_node(arg.value)?;
                 result[param_idx] = Some(val);
             } else {
                 // Positional argument.
-                if positional_idx >= params.len() {
+                if positional_idx >= names.len() {
                     return Err(EvalError::new(
                         EvalErrorKind::ArityMismatch,
                         format!(
                             "too many arguments: expected {}, got at least {}",
-                            params.len(),
+                            names.len(),
                             positional_idx + 1,
                         ),
                         Some(span),
                     ));
                 }
-                let val = self.eval_node(arg.value)?;
-                result[positional_idx] = Some(val);
-                positional_idx += 1;
-            }
-        }
-
-        // Second pass: fill in defaults for missing args.
-        for (i, param) in params.iter().enumerate() {
-            if result[i].is_none() {
-                if let Some(default_node) = param.default {
-                    let val = self.eval_node(default_node)?;
-                    result[i] = Some(val);
-                } else {
+                if result[positional_idx].is_some() {
                     return Err(EvalError::new(
                         EvalErrorKind::ArityMismatch,
-                        format!("missing argument '{}'", param.name),
-                        Some(span),
+                        format!(
+                            "argument '{}' given more than once",
+                            names[positional_idx]
+                        ),
+                        Some(arg.span),
                     ));
                 }
+                let val = self.eval_node(arg.value)?;
+                result[positional_idx] = Some(val);
+                positional_idx += 1;
             }
         }
 
-        Ok(result.into_iter().map(|v| v.unwrap()).collect())
+        Ok(result)
     }
 
     /// Try to match a pattern against a value, returning bindings on success.
@@ -683,7 +839,18 @@ impl<'a> EvalCtx<'a> {
             (Value::Angle(x), Value::Angle(y)) => x == y,
             (Value::Bool(x), Value::Bool(y)) => x == y,
             (Value::String(x), Value::String(y)) => x == y,
+            (Value::Vec2(x), Value::Vec2(y)) => x == y,
             (Value::Vec3(x), Value::Vec3(y)) => x == y,
+            (
+                Value::Plane {
+                    origin: o1,
+                    normal: n1,
+                },
+                Value::Plane {
+                    origin: o2,
+                    normal: n2,
+                },
+            ) => o1 == o2 && n1 == n2,
             (Value::Unit, Value::Unit) => true,
             (
                 Value::EnumVariant {
@@ -777,8 +944,8 @@ mod tests {
 
     #[test]
     fn eval_float_lit() {
-        let val = eval_single(IrNode::FloatLit(3.14)).unwrap();
-        assert!(matches!(val, Value::Float(f) if (f - 3.14).abs() < f64::EPSILON));
+        let val = eval_single(IrNode::FloatLit(2.5)).unwrap();
+        assert!(matches!(val, Value::Float(f) if (f - 2.5).abs() < f64::EPSILON));
     }
 
     #[test]
@@ -0,0 +1,132 @@
+//! Engineering features recorded during evaluation.
+//!
+//! Builtins such as `linear_pattern` produce many solids from one call;
+//! the evaluator logs the call once, with its parameters, so tools can
+//! report "4 × M3 holes at 20mm" instead of N unrelated moves. Cosmetic
+//! threads exist only here: their geometry is a plain hole.
+
+use std::fmt;
+
+/// One feature call and the parameters that define it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Feature {
+    /// `count` copies stepped `spacing` mm along a unit `direction`.
+    LinearPattern {
+        count: usize,
+        direction: [f64; 3],
+        spacing: f64,
+    },
+    /// `count` copies rotated `step` radians apart about `axis` through `origin`.
+    PolarPattern {
+        count: usize,
+        axis: [f64; 3],
+        origin: [f64; 3],
+        step: f64,
+    },
+    /// An `nx` × `ny` grid with `dx`, `dy` mm pitch in X and Y.
+    GridPattern {
+        nx: usize,
+        ny: usize,
+        dx: f64,
+        dy: f64,
+    },
+    /// A thread of `size` drawn as an annotation: `diameter` and `pitch`
+    /// in mm over `depth` mm.
+    CosmeticThread {
+        size: String,
+        diameter: f64,
+        pitch: f64,
+        depth: f64,
+    },
+}
+
+impl Feature {
+    /// Number of instances the feature produces.
+    pub fn instances(&self) -> usize {
+        match self {
+            Self::LinearPattern { count, .. } | Self::PolarPattern { count, .. } => *count,
+            Self::GridPattern { nx, ny, .. } => nx * ny,
+            Self::CosmeticThread { .. } => 1,
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LinearPattern {
+                count,
+                direction: [x, y, z],
+                spacing,
+            } => write!(f, "linear_pattern: {count} × {spacing}mm along ({x}, {y}, {z})"),
+            Self::PolarPattern {
+                count,
+                axis: [x, y, z],
+                step,
+                ..
+            } => {
+                // Round away float noise so 60° prints as 60, not 59.999….
+                let degrees = (step.to_degrees() * 1e9).round() / 1e9;
+                write!(f, "polar_pattern: {count} × {degrees}deg about ({x}, {y}, {z})")
+            }
+            Self::GridPattern { nx, ny, dx, dy } => {
+                write!(f, "grid_pattern: {nx} × {ny} at {dx}mm × {dy}mm")
+            }
+            Self::CosmeticThread {
+                size,
+                diameter,
+                pitch,
+                depth,
+            } => write!(
+                f,
+                "cosmetic thread: {size} (⌀{diameter}mm × {pitch}mm pitch), {depth}mm deep"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instances_and_display() {
+        let linear = Feature::LinearPattern {
+            count: 4,
+            direction: [1.0, 0.0, 0.0],
+            spacing: 20.0,
+        };
+        assert_eq!(linear.instances(), 4);
+        assert_eq!(linear.to_string(), "linear_pattern: 4 × 20mm along (1, 0, 0)");
+
+        let polar = Feature::PolarPattern {
+            count: 6,
+            axis: [0.0, 0.0, 1.0],
+            origin: [0.0; 3],
+            step: std::f64::consts::FRAC_PI_3,
+        };
+        assert_eq!(polar.instances(), 6);
+        assert_eq!(polar.to_string(), "polar_pattern: 6 × 60deg about (0, 0, 1)");
+
+        let grid = Feature::GridPattern {
+            nx: 3,
+            ny: 2,
+            dx: 10.0,
+            dy: 5.0,
+        };
+        assert_eq!(grid.instances(), 6);
+        assert_eq!(grid.to_string(), "grid_pattern: 3 × 2 at 10mm × 5mm");
+
+        let thread = Feature::CosmeticThread {
+            size: "M5".to_string(),
+            diameter: 5.0,
+            pitch: 0.8,
+            depth: 10.0,
+        };
+        assert_eq!(thread.instances(), 1);
+        assert_eq!(
+            thread.to_string(),
+            "cosmetic thread: M5 (⌀5mm × 0.8mm pitch), 10mm deep"
+        );
+    }
+}
@@ -7,12 +7,14 @@ pub mod builtins;
 pub mod env;
 pub mod error;
 pub mod eval;
+pub mod feature;
 pub mod types;
 pub mod units;
 pub mod value;
 
 pub use env::Env;
 pub use error::{EvalError, EvalErrorKind, EvalResult};
-pub use eval::{eval, eval_debug, RawDebugStep};
+pub use eval::{eval, eval_debug, eval_with, EvalOptions, EvalOutput, RawDebugStep};
+pub use feature::Feature;
 pub use types::Ty;
 pub use value::Value;
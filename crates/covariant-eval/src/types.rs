@@ -1,10 +1,13 @@
 //! Type representation for future static type checking.
 //!
-//! Not used by the evaluator in v0.1 (dynamic typing), but defined
-//! here so the type system can be built incrementally.
+//! The evaluator is dynamically typed in v0.1; `Ty` is currently used to
+//! describe built-in function signatures and check their arguments at call
+//! time, so the type system can be built incrementally.
 
 use std::fmt;
 
+use crate::value::Value;
+
 /// Runtime/static type representation.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Ty {
@@ -14,14 +17,54 @@ pub enum Ty {
     Angle,
     Bool,
     String,
+    Vec2,
     Vec3,
+    Plane,
+    Surface,
+    Curve,
     Solid,
     Mesh,
     List(Box<Ty>),
+    /// The inner type, or `Unit` for an argument left out.
+    Optional(Box<Ty>),
     Fn { params: Vec<Ty>, ret: Box<Ty> },
     Data(String),
     Enum(String),
     Unit,
+    /// Accepts any value (e.g. the pass-through argument of `trace`).
+    Any,
+}
+
+impl Ty {
+    /// Returns `true` if a runtime value is acceptable where this type is expected.
+    ///
+    /// Numeric types follow the builtins' coercion rules: `Int` and `Float`
+    /// are accepted wherever a `Length`, `Angle`, or `Float` is expected.
+    pub fn accepts(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Any, _) => true,
+            (Self::Int, Value::Int(_)) => true,
+            (Self::Float, Value::Int(_) | Value::Float(_)) => true,
+            (Self::Length, Value::Int(_) | Value::Float(_) | Value::Length(_)) => true,
+            (Self::Angle, Value::Int(_) | Value::Float(_) | Value::Angle(_)) => true,
+            (Self::Bool, Value::Bool(_)) => true,
+            (Self::String, Value::String(_)) => true,
+            (Self::Vec2, Value::Vec2(_)) => true,
+            (Self::Vec3, Value::Vec3(_)) => true,
+            (Self::Plane, Value::Plane { .. }) => true,
+            (Self::Surface, Value::Face(_)) => true,
+            (Self::Curve, Value::Curve(_)) => true,
+            (Self::Solid, Value::Solid(_)) => true,
+            (Self::Mesh, Value::Mesh(_)) => true,
+            (Self::List(inner), Value::List(items)) => items.iter().all(|v| inner.accepts(v)),
+            (Self::Optional(inner), v) => matches!(v, Value::Unit) || inner.accepts(v),
+            (Self::Fn { .. }, Value::Function { .. } | Value::BuiltinFn { .. }) => true,
+            (Self::Data(name), Value::Data { type_name, .. }) => name == type_name,
+            (Self::Enum(name), Value::EnumVariant { type_name, .. }) => name == type_name,
+            (Self::Unit, Value::Unit) => true,
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for Ty {
@@ -33,10 +76,15 @@ impl fmt::Display for Ty {
             Self::Angle => write!(f, "Angle"),
             Self::Bool => write!(f, "Bool"),
             Self::String => write!(f, "String"),
+            Self::Vec2 => write!(f, "Vec2"),
             Self::Vec3 => write!(f, "Vec3"),
+            Self::Plane => write!(f, "Plane"),
+            Self::Surface => write!(f, "Surface"),
+            Self::Curve => write!(f, "Curve"),
             Self::Solid => write!(f, "Solid"),
             Self::Mesh => write!(f, "Mesh"),
             Self::List(inner) => write!(f, "List[{inner}]"),
+            Self::Optional(inner) => write!(f, "Optional[{inner}]"),
             Self::Fn { params, ret } => {
                 write!(f, "Fn(")?;
                 for (i, p) in params.iter().enumerate() {
@@ -50,6 +98,7 @@ impl fmt::Display for Ty {
             Self::Data(name) => write!(f, "{name}"),
             Self::Enum(name) => write!(f, "{name}"),
             Self::Unit => write!(f, "Unit"),
+            Self::Any => write!(f, "Any"),
         }
     }
 }
@@ -66,7 +115,11 @@ mod tests {
         assert_eq!(format!("{}", Ty::Angle), "Angle");
         assert_eq!(format!("{}", Ty::Bool), "Bool");
         assert_eq!(format!("{}", Ty::String), "String");
+        assert_eq!(format!("{}", Ty::Vec2), "Vec2");
         assert_eq!(format!("{}", Ty::Vec3), "Vec3");
+        assert_eq!(format!("{}", Ty::Plane), "Plane");
+        assert_eq!(format!("{}", Ty::Surface), "Surface");
+        assert_eq!(format!("{}", Ty::Curve), "Curve");
         assert_eq!(format!("{}", Ty::Solid), "Solid");
         assert_eq!(format!("{}", Ty::Mesh), "Mesh");
         assert_eq!(format!("{}", Ty::Unit), "Unit");
@@ -98,4 +151,40 @@ mod tests {
         let ty = Ty::Enum("Color".to_string());
         assert_eq!(format!("{ty}"), "Color");
     }
+
+    #[test]
+    fn length_accepts_plain_numbers() {
+        assert!(Ty::Length.accepts(&Value::Length(5.0)));
+        assert!(Ty::Length.accepts(&Value::Int(5)));
+        assert!(Ty::Length.accepts(&Value::Float(5.0)));
+        assert!(!Ty::Length.accepts(&Value::Angle(1.0)));
+        assert!(!Ty::Length.accepts(&Value::Bool(true)));
+    }
+
+    #[test]
+    fn list_accepts_checks_elements() {
+        let ty = Ty::List(Box::new(Ty::Int));
+        assert!(ty.accepts(&Value::List(vec![Value::Int(1), Value::Int(2)])));
+        assert!(!ty.accepts(&Value::List(vec![Value::Int(1), Value::Bool(false)])));
+    }
+
+    #[test]
+    fn optional_accepts_unit_or_inner_type() {
+        let ty = Ty::Optional(Box::new(Ty::Length));
+        assert_eq!(format!("{ty}"), "Optional[Length]");
+        assert!(ty.accepts(&Value::Unit));
+        assert!(ty.accepts(&Value::Length(5.0)));
+        assert!(!ty.accepts(&Value::String("x".to_string())));
+    }
+
+    #[test]
+    fn enum_accepts_matching_type_name() {
+        let v = Value::EnumVariant {
+            type_name: "ThreadKind".to_string(),
+            variant: "Internal".to_string(),
+        };
+        assert!(Ty::Enum("ThreadKind".to_string()).accepts(&v));
+        assert!(!Ty::Enum("ThreadSize".to_string()).accepts(&v));
+        assert!(Ty::Any.accepts(&v));
+    }
 }
@@ -7,6 +7,7 @@ use covariant_ir::NodeId;
 
 use crate::env::Env;
 use crate::error::EvalResult;
+use crate::types::Ty;
 
 /// A function parameter in a runtime closure.
 #[derive(Debug, Clone)]
@@ -15,10 +16,42 @@ pub struct FnParam {
     pub default: Option<NodeId>,
 }
 
+/// A parameter in a built-in function's signature.
+///
+/// Built-in parameters have no IR node for their default, so the default is
+/// stored as an already-evaluated value.
+#[derive(Debug, Clone)]
+pub struct BuiltinParam {
+    pub name: String,
+    pub ty: Ty,
+    pub default: Option<Value>,
+}
+
+impl BuiltinParam {
+    /// A required parameter.
+    pub fn required(name: &str, ty: Ty) -> Self {
+        Self {
+            name: name.to_string(),
+            ty,
+            default: None,
+        }
+    }
+
+    /// An optional parameter with a default value.
+    pub fn optional(name: &str, ty: Ty, default: Value) -> Self {
+        Self {
+            name: name.to_string(),
+            ty,
+            default: Some(default),
+        }
+    }
+}
+
 /// Type alias for built-in function pointers.
 ///
-/// The function receives evaluated arguments and a mutable reference to
-/// the evaluator context (for accessing the kernel, dag, etc.).
+/// The function receives evaluated arguments — one per declared parameter,
+/// in declaration order, with defaults already filled in — and a mutable
+/// reference to the evaluator context (for accessing the kernel, dag, etc.).
 pub type BuiltinFnPtr = Arc<dyn Fn(&[Value], &mut crate::eval::EvalCtx<'_>) -> EvalResult<Value> + Send + Sync>;
 
 /// A runtime value in the COVARIANT language.
@@ -36,8 +69,16 @@ pub enum Value {
     Bool(bool),
     /// String value.
     String(String),
+    /// 2D vector (x, y) in mm, used for sketch coordinates.
+    Vec2([f64; 2]),
     /// 3D vector (x, y, z) in mm.
     Vec3([f64; 3]),
+    /// Oriented plane through `origin` (mm) with unit `normal`.
+    Plane { origin: [f64; 3], normal: [f64; 3] },
+    /// Planar surface (sketch profile), possibly with holes.
+    Face(covariant_geom::Face),
+    /// One-dimensional curve (line, arc, helix, ...), used as a sweep path.
+    Curve(covariant_geom::Wire),
     /// B-rep solid geometry.
     Solid(covariant_geom::Solid),
     /// Tessellated mesh.
@@ -53,6 +94,7 @@ pub enum Value {
     /// Built-in function.
     BuiltinFn {
         name: String,
+        params: Arc<[BuiltinParam]>,
         func: BuiltinFnPtr,
     },
     /// User-defined data instance.
@@ -79,7 +121,11 @@ impl Value {
             Self::Angle(_) => "Angle",
             Self::Bool(_) => "Bool",
             Self::String(_) => "String",
+            Self::Vec2(_) => "Vec2",
             Self::Vec3(_) => "Vec3",
+            Self::Plane { .. } => "Plane",
+            Self::Face(_) => "Surface",
+            Self::Curve(_) => "Curve",
             Self::Solid(_) => "Solid",
             Self::Mesh(_) => "Mesh",
             Self::List(_) => "List",
@@ -114,7 +160,11 @@ impl fmt::Debug for Value {
             Self::Angle(v) => write!(f, "Angle({v}rad)"),
             Self::Bool(b) => write!(f, "Bool({b})"),
             Self::String(s) => write!(f, "String({s:?})"),
+            Self::Vec2([x, y]) => write!(f, "Vec2({x}, {y})"),
             Self::Vec3([x, y, z]) => write!(f, "Vec3({x}, {y}, {z})"),
+            Self::Plane { origin, normal } => write!(f, "Plane({origin:?}, {normal:?})"),
+            Self::Face(_) => write!(f, "Face(<...>)"),
+            Self::Curve(_) => write!(f, "Curve(<...>)"),
             Self::Solid(_) => write!(f, "Solid(<...>)"),
             Self::Mesh(_) => write!(f, "Mesh(<...>)"),
             Self::List(items) => write!(f, "List({items:?})"),
@@ -147,7 +197,7 @@ mod tests {
 
     #[test]
     fn type_name_float() {
-        assert_eq!(Value::Float(3.14).type_name(), "Float");
+        assert_eq!(Value::Float(2.5).type_name(), "Float");
     }
 
     #[test]
@@ -170,11 +220,25 @@ mod tests {
         assert_eq!(Value::String("hi".to_string()).type_name(), "String");
     }
 
+    #[test]
+    fn type_name_vec2() {
+        assert_eq!(Value::Vec2([1.0, 2.0]).type_name(), "Vec2");
+    }
+
     #[test]
     fn type_name_vec3() {
         assert_eq!(Value::Vec3([1.0, 2.0, 3.0]).type_name(), "Vec3");
     }
 
+    #[test]
+    fn type_name_plane() {
+        let plane = Value::Plane {
+            origin: [0.0, 0.0, 0.0],
+            normal: [0.0, 0.0, 1.0],
+        };
+        assert_eq!(plane.type_name(), "Plane");
+    }
+
     #[test]
     fn type_name_list() {
         assert_eq!(Value::List(vec![]).type_name(), "List");
@@ -210,7 +274,7 @@ mod tests {
 
     #[test]
     fn as_f64_float() {
-        assert_eq!(Value::Float(3.14).as_f64(), Some(3.14));
+        assert_eq!(Value::Float(2.5).as_f64(), Some(2.5));
     }
 
     #[test]
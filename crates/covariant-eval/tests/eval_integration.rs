@@ -127,6 +127,23 @@ fn difference_solids() {
     assert!(matches!(val, Value::Solid(_)));
 }
 
+#[test]
+fn difference_many_cuts_all_pockets() {
+    let val = eval_source(
+        "let pocket = move(box(vec3(4mm, 4mm, 15mm)), vec3(8mm, 8mm, -5mm))\n\
+         let cutters = grid_pattern(pocket, 3, 2, 20mm, 20mm, fuse = false)\n\
+         volume(difference_many(box(vec3(60mm, 40mm, 5mm)), cutters))",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Float(v) if (v - (12000.0 - 6.0 * 80.0)).abs() < 1e-6), "{val:?}");
+}
+
+#[test]
+fn difference_many_rejects_non_solid_cutters() {
+    let err = eval_source("difference_many(box(vec3(1mm, 1mm, 1mm)), [1])").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+}
+
 // ── Threaded hole ───────────────────────────────────────────────────
 
 #[test]
@@ -138,6 +155,874 @@ fn threaded_hole_builtin() {
     assert!(matches!(val, Value::Solid(_)));
 }
 
+#[test]
+fn threaded_hole_named_args() {
+    let val = eval_source(
+        "let h = threaded_hole(ISO_METRIC, M3, TAP, depth = 8mm, chamfer = 0.5mm)\nh",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Solid(_)));
+}
+
+#[test]
+fn threaded_hole_chamfer_defaults() {
+    let val = eval_source("threaded_hole(ISO_METRIC, M3, TAP, depth = 8mm)").unwrap();
+    assert!(matches!(val, Value::Solid(_)));
+}
+
+/// Evaluate with threaded holes rendered in `mode`.
+fn eval_threads(
+    src: &str,
+    mode: covariant_thread::ThreadMode,
+) -> EvalResult<(Value, Vec<covariant_eval::Feature>)> {
+    let (ast, parse_errors) = covariant_syntax::parse(src);
+    assert!(parse_errors.is_empty(), "parse errors: {parse_errors:?}");
+    let (dag, _) = covariant_ir::lower(&ast);
+    let options = covariant_eval::EvalOptions {
+        thread_mode: mode,
+        ..Default::default()
+    };
+    covariant_eval::eval_with(&dag, &TruckKernel, &options).map(|out| (out.value, out.features))
+}
+
+#[test]
+fn threaded_hole_rejects_mismatched_standard() {
+    let err = eval_source("threaded_hole(UTS, M3, TAP, depth = 8mm)").unwrap_err();
+    assert!(
+        err.message.contains("ISO standard, not UTS"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn threaded_hole_fine_pitch() {
+    // M10x1.25 taps at 8.75mm, the coarse M10 at 8.5mm.
+    let val = eval_source(
+        "let probe = vec3(4.3mm, 0mm, 4mm)
+         [contains_point(threaded_hole(ISO_METRIC, M10x1_25, TAP, depth = 8mm), probe),
+          contains_point(threaded_hole(ISO_METRIC, M10, TAP, depth = 8mm), probe),
+          threaded_hole(ISO_METRIC, M64, CLEARANCE, depth = 8mm)]",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected list, got {val:?}");
+    };
+    assert!(matches!(items[0], Value::Bool(true)));
+    assert!(matches!(items[1], Value::Bool(false)));
+    assert!(matches!(items[2], Value::Solid(_)));
+}
+
+#[test]
+fn threaded_hole_british_standards() {
+    let val = eval_source(
+        "[threaded_hole(BSW, Bsw1_4, TAP, depth = 10mm),
+          threaded_hole(BSW, Bsf3_8, CLEARANCE, depth = 10mm),
+          threaded_hole(BSP, G1_4, TAP, depth = 10mm, chamfer = 1mm)]",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::List(ref items) if items.len() == 3));
+
+    let err = eval_source("threaded_hole(BSW, G1_2, TAP, depth = 10mm)").unwrap_err();
+    assert!(
+        err.message.contains("BSP standard, not BSW"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn threaded_hole_chamfer_countersinks_the_entrance() {
+    // M3 tap drill is 2.5mm; a 0.5mm chamfer widens the entrance to 3.5mm.
+    let val = eval_source(
+        "let probe = vec3(1.5mm, 0mm, 0.1mm)
+         [contains_point(threaded_hole(ISO_METRIC, M3, TAP, 8mm, 0.5mm), probe),
+          contains_point(threaded_hole(ISO_METRIC, M3, TAP, 8mm), probe)]",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected list")
+    };
+    assert!(matches!(items[..], [Value::Bool(true), Value::Bool(false)]));
+}
+
+#[test]
+fn threaded_hole_full_mode_cuts_a_helical_thread() {
+    // Between the M3 minor (2.39mm) and major (3mm) diameters the cutter
+    // alternates between thread and flank along the axis.
+    let src = "let h = threaded_hole(ISO_METRIC, M3, TAP, 4mm, 0.3mm)
+               map(|z| contains_point(h, vec3(1.45mm, 0mm, z)),                [1mm, 1.125mm, 1.25mm, 1.375mm])";
+    let hits = |mode| match eval_threads(src, mode).unwrap().0 {
+        Value::List(items) => items
+            .iter()
+            .filter(|v| matches!(v, Value::Bool(true)))
+            .count(),
+        other => panic!("expected list, got {other:?}"),
+    };
+    assert_eq!(hits(covariant_thread::ThreadMode::None), 0);
+    let full = hits(covariant_thread::ThreadMode::Full);
+    assert!(full > 0 && full < 4, "{full}");
+}
+
+#[test]
+fn threaded_hole_cosmetic_mode_records_the_thread() {
+    let (val, features) = eval_threads(
+        "[threaded_hole(ISO_METRIC, M5, TAP, 10mm), threaded_hole(ISO_METRIC, M5, CLEARANCE, 10mm)]",
+        covariant_thread::ThreadMode::Cosmetic,
+    )
+    .unwrap();
+    assert!(matches!(val, Value::List(_)));
+    let labels: Vec<String> = features.iter().map(|f| f.to_string()).collect();
+    assert_eq!(
+        labels,
+        ["cosmetic thread: M5 (⌀5mm × 0.8mm pitch), 10mm deep"]
+    );
+}
+
+#[test]
+fn eval_with_combines_thread_mode_and_debug_steps() {
+    let (ast, _) = covariant_syntax::parse("threaded_hole(ISO_METRIC, M5, TAP, 10mm)");
+    let (dag, _) = covariant_ir::lower(&ast);
+    let options = covariant_eval::EvalOptions {
+        thread_mode: covariant_thread::ThreadMode::Cosmetic,
+        debug_steps: true,
+    };
+    let out = covariant_eval::eval_with(&dag, &TruckKernel, &options).unwrap();
+    assert!(matches!(out.value, Value::Solid(_)));
+    assert_eq!(out.features.len(), 1);
+    assert!(!out.debug_steps.is_empty());
+
+    let plain = covariant_eval::eval_with(&dag, &TruckKernel, &Default::default()).unwrap();
+    assert!(plain.features.is_empty());
+    assert!(plain.debug_steps.is_empty());
+}
+
+#[test]
+fn thread_builtin_reads_callouts() {
+    let val = eval_source(
+        "[thread(\"M6x1-6H\", depth = 8mm),
+          thread(\"1/4-20 UNC-2B\", depth = 10mm, chamfer = 0.5mm),
+          thread(\"G1/4\", 10mm)]",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected list, got {val:?}");
+    };
+    assert!(items.iter().all(|v| matches!(v, Value::Solid(_))));
+}
+
+#[test]
+fn thread_external_callout_builds_a_rod_of_its_length() {
+    // Hole cutters overshoot their entrance; rods start at z = 0.
+    let val = eval_source(
+        "let rod = thread(\"M8x1.25-6g x 20\")
+         [contains_point(rod, vec3(3.9mm, 0mm, 19.9mm)),
+          contains_point(rod, vec3(0mm, 0mm, 20.05mm)),
+          contains_point(rod, vec3(0mm, 0mm, -0.05mm)),
+          contains_point(thread(\"M8-6H\", 20mm), vec3(0mm, 0mm, -0.05mm))]",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected list, got {val:?}");
+    };
+    assert!(matches!(
+        items[..],
+        [Value::Bool(true), Value::Bool(false), Value::Bool(false), Value::Bool(true)]
+    ));
+}
+
+#[test]
+fn thread_left_hand_callout_in_full_mode() {
+    let probes = |callout: &str| {
+        let src = format!(
+            "let h = thread(\"{callout}\", 4mm)
+             map(|z| contains_point(h, vec3(0mm, 1.45mm, z)), [1mm, 1.125mm, 1.25mm, 1.375mm])"
+        );
+        match eval_threads(&src, covariant_thread::ThreadMode::Full).unwrap().0 {
+            Value::List(items) => items
+                .iter()
+                .map(|v| matches!(v, Value::Bool(true)))
+                .collect::<Vec<_>>(),
+            other => panic!("expected list, got {other:?}"),
+        }
+    };
+    // A quarter turn round, the two hands sit half a pitch apart.
+    assert_ne!(probes("M3-6H"), probes("M3-6H-LH"));
+}
+
+#[test]
+fn thread_rejects_bad_callouts() {
+    let err = eval_source("thread(\"M6x1-6Z\", 8mm)").unwrap_err();
+    assert!(
+        err.message.contains("invalid thread callout 'M6x1-6Z'"),
+        "{}",
+        err.message
+    );
+    let err = eval_source("thread(\"M6x1-6H\")").unwrap_err();
+    assert!(err.message.contains("pass a depth"), "{}", err.message);
+}
+
+#[test]
+fn thread_depth_must_be_a_length() {
+    let err = eval_source("thread(\"M6\", depth = \"x\")").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+    assert!(
+        err.message.contains("expected Optional[Length] for 'depth'"),
+        "{}",
+        err.message
+    );
+}
+
+// ── Sketch profiles ─────────────────────────────────────────────────
+
+#[test]
+fn circle2d_builtin() {
+    let val = eval_source("circle2d(5mm, center = vec2(1mm, 2mm))").unwrap();
+    assert!(matches!(val, Value::Face(_)));
+}
+
+#[test]
+fn rectangle2d_builtin() {
+    let val = eval_source("rectangle2d(vec2(20mm, 10mm))").unwrap();
+    assert!(matches!(val, Value::Face(_)));
+}
+
+#[test]
+fn polygon2d_builtin() {
+    let val = eval_source("polygon2d([vec2(0mm, 0mm), vec2(10mm, 0mm), vec2(5mm, 8mm)])").unwrap();
+    assert!(matches!(val, Value::Face(_)));
+}
+
+#[test]
+fn polygon2d_self_intersecting_is_reported() {
+    let err = eval_source(
+        "polygon2d([vec2(0mm, 0mm), vec2(2mm, 2mm), vec2(2mm, 0mm), vec2(0mm, 1mm)])",
+    )
+    .unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("intersect"), "{}", err.message);
+}
+
+#[test]
+fn circle2d_rejects_zero_radius() {
+    let err = eval_source("circle2d(0mm)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+}
+
+#[test]
+fn profile_with_holes() {
+    let val = eval_source(
+        "let plate = rectangle2d(vec2(40mm, 20mm))\n\
+         let h1 = circle2d(3mm, center = vec2(10mm, 10mm))\n\
+         let h2 = circle2d(3mm, center = vec2(30mm, 10mm))\n\
+         with_holes(plate, [h1, h2])",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Face(_)));
+}
+
+#[test]
+fn surface_is_not_a_solid() {
+    let err = eval_source("union(circle2d(5mm), box(vec3(1mm, 1mm, 1mm)))").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+    assert!(err.message.contains("got Surface"), "{}", err.message);
+}
+
+#[test]
+fn surfaces_can_be_moved_and_rotated() {
+    let val = eval_source("rotate(move(circle2d(5mm), vec3(0mm, 0mm, 10mm)), vec3(1, 0, 0), 30deg)")
+        .unwrap();
+    assert!(matches!(val, Value::Face(_)));
+    let err = eval_source("move(line(vec3(0, 0, 0), vec3(1, 0, 0)), vec3(1, 0, 0))").unwrap_err();
+    assert!(err.message.contains("expected Solid or Surface"), "{}", err.message);
+}
+
+// ── Extrude / revolve ───────────────────────────────────────────────
+
+#[test]
+fn extrude_by_height() {
+    let val = eval_source("extrude(rectangle2d(vec2(20mm, 10mm)), 5mm)").unwrap();
+    assert!(matches!(val, Value::Solid(_)));
+}
+
+#[test]
+fn extrude_along_vector() {
+    let val = eval_source("extrude(circle2d(3mm), by = vec3(0mm, 2mm, 10mm))").unwrap();
+    assert!(matches!(val, Value::Solid(_)));
+}
+
+#[test]
+fn extrude_rejects_wrong_type() {
+    let err = eval_source("extrude(circle2d(3mm), true)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+    assert!(err.message.contains("expected Length or Vec3 for 'by'"), "{}", err.message);
+}
+
+#[test]
+fn extrude_in_plane_direction_is_reported() {
+    let err = eval_source("extrude(circle2d(3mm), vec3(1mm, 0mm, 0mm))").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("parallel"), "{}", err.message);
+}
+
+#[test]
+fn revolve_full_turn() {
+    let val = eval_source(
+        "let profile = rectangle2d(vec2(2mm, 10mm), origin = vec2(5mm, 0mm))\n\
+         revolve(profile, vec3(0, 1, 0))",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Solid(_)));
+}
+
+#[test]
+fn revolve_partial_angle() {
+    let val = eval_source(
+        "revolve(circle2d(1mm, center = vec2(5mm, 0mm)), vec3(0, 1, 0), 90deg)",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Solid(_)));
+}
+
+#[test]
+fn revolve_profile_crossing_axis_is_reported() {
+    let err = eval_source("revolve(circle2d(3mm), vec3(0, 1, 0), 180deg)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("crosses the revolve axis"), "{}", err.message);
+}
+
+// ── Curves and path sweeps ──────────────────────────────────────────
+
+#[test]
+fn curve_constructors() {
+    for src in [
+        "line(vec3(0mm, 0mm, 0mm), vec3(10mm, 0mm, 0mm))",
+        "circle(vec3(0mm, 0mm, 0mm), 5mm)",
+        "circle(vec3(0mm, 0mm, 0mm), 5mm, normal = vec3(1, 0, 0))",
+        "arc(vec3(0mm, 0mm, 0mm), 5mm, 0deg, 90deg)",
+        "helix(5mm, 2mm, 10mm, left_handed = true)",
+    ] {
+        let val = eval_source(src).unwrap_or_else(|e| panic!("{src}: {e}"));
+        assert!(matches!(val, Value::Curve(_)), "{src}");
+    }
+}
+
+#[test]
+fn parametric_curve_calls_function() {
+    let val = eval_source(
+        "fn parabola(t: Float) -> Vec3 { vec3(t * 10mm, t * t * 10mm, 0mm) }\n\
+         curve(parabola, 0, 1, samples = 8)",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Curve(_)));
+}
+
+#[test]
+fn parametric_curve_requires_vec3() {
+    let err = eval_source("fn bad(t: Float) -> Float { t }\ncurve(bad, 0, 1)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+    assert!(err.message.contains("expected Vec3 for 'f(t)'"), "{}", err.message);
+}
+
+#[test]
+fn sweep_along_line_and_helix() {
+    let val = eval_source(
+        "sweep(rectangle2d(vec2(2mm, 2mm)), line(vec3(0mm, 0mm, 0mm), vec3(0mm, 20mm, 0mm)), \
+         up = vec3(0, 0, 1))",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Solid(_)));
+
+    let spring = eval_source("sweep(circle2d(0.5mm), along = helix(5mm, 2mm, 4mm))").unwrap();
+    assert!(matches!(spring, Value::Solid(_)));
+}
+
+#[test]
+fn sweep_requires_curve_path() {
+    let err = eval_source("sweep(circle2d(1mm), circle2d(5mm))").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+    assert!(err.message.contains("expected Curve for 'along'"), "{}", err.message);
+}
+
+// ── Loft ────────────────────────────────────────────────────────────
+
+#[test]
+fn loft_round_to_square() {
+    for ruled in ["true", "false"] {
+        let src = format!(
+            "let round = circle2d(10mm)\n\
+             let square = move(rectangle2d(vec2(16mm, 16mm), origin = vec2(-8mm, -8mm)), vec3(0mm, 0mm, 30mm))\n\
+             loft([round, square], ruled = {ruled})"
+        );
+        let val = eval_source(&src).unwrap();
+        assert!(matches!(val, Value::Solid(_)), "ruled = {ruled}");
+    }
+}
+
+#[test]
+fn loft_needs_two_sections() {
+    let err = eval_source("loft([circle2d(10mm)])").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("at least 2 sections"), "{}", err.message);
+}
+
+// ── Fillet / chamfer ────────────────────────────────────────────────
+
+#[test]
+fn fillet_and_chamfer_with_edge_selection() {
+    for src in [
+        "fillet(box(vec3(20mm, 20mm, 10mm)), 2mm)",
+        "fillet(box(vec3(20mm, 20mm, 10mm)), 2mm, edges = edges_parallel(vec3(0, 0, 1)))",
+        "chamfer(box(vec3(20mm, 20mm, 10mm)), 1mm, edges_on_face(vec3(0, 0, 1)))",
+    ] {
+        let val = eval_source(src).unwrap();
+        assert!(matches!(val, Value::Solid(_)), "{src}");
+    }
+}
+
+#[test]
+fn fillet_radius_too_large() {
+    let err = eval_source("fillet(box(vec3(10mm, 10mm, 10mm)), 6mm)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("fillet failed"), "{}", err.message);
+    assert!(err.message.contains("too large"), "{}", err.message);
+}
+
+#[test]
+fn fillet_requires_edge_selector() {
+    let err = eval_source("fillet(box(vec3(10mm, 10mm, 10mm)), 1mm, vec3(0, 0, 1))").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+}
+
+// ── Transforms ──────────────────────────────────────────────────────
+
+/// Evaluate `src` and return the min and max corners of `bbox(result)`.
+fn bounds_of(src: &str) -> ([f64; 3], [f64; 3]) {
+    let val = eval_source(&format!("let b = bbox({src})\n[b.min, b.max]")).unwrap();
+    match val {
+        Value::List(items) => match items[..] {
+            [Value::Vec3(min), Value::Vec3(max)] => (min, max),
+            _ => panic!("unexpected bbox fields {items:?}"),
+        },
+        other => panic!("expected List, got {other:?}"),
+    }
+}
+
+fn assert_near(actual: [f64; 3], expected: [f64; 3]) {
+    assert!(
+        actual.iter().zip(expected).all(|(a, e)| (a - e).abs() < 1e-6),
+        "{actual:?} vs {expected:?}"
+    );
+}
+
+#[test]
+fn rotate_about_hinge_point() {
+    // A 20mm arm swung 90° about its far end.
+    let (min, max) = bounds_of(
+        "rotate(box(vec3(20mm, 5mm, 5mm)), vec3(0, 0, 1), 90deg, origin = vec3(20mm, 0mm, 0mm))",
+    );
+    assert_near(min, [15.0, -20.0, 0.0]);
+    assert_near(max, [20.0, 0.0, 5.0]);
+}
+
+#[test]
+fn scale_per_axis_about_center() {
+    let (min, max) = bounds_of("scale(box(vec3(10mm, 10mm, 10mm)), vec3(2, 1, 0.5))");
+    assert_near(min, [0.0, 0.0, 0.0]);
+    assert_near(max, [20.0, 10.0, 5.0]);
+    let (min, max) =
+        bounds_of("scale(box(vec3(10mm, 10mm, 10mm)), 2, center = vec3(5mm, 5mm, 5mm))");
+    assert_near(min, [-5.0, -5.0, -5.0]);
+    assert_near(max, [15.0, 15.0, 15.0]);
+}
+
+#[test]
+fn scale_rejects_zero_and_bad_factor() {
+    let err = eval_source("scale(box(vec3(1mm, 1mm, 1mm)), vec3(1, 0, 1))").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("singular"), "{}", err.message);
+    let err = eval_source("scale(box(vec3(1mm, 1mm, 1mm)), true)").unwrap_err();
+    assert!(err.message.contains("expected Float or Vec3"), "{}", err.message);
+}
+
+#[test]
+fn transform_with_affine_matrix() {
+    // Shear x by z and translate 3mm along x.
+    let (min, max) = bounds_of(
+        "transform(box(vec3(1mm, 1mm, 2mm)), [[1, 0, 0.5, 3mm], [0, 1, 0, 0], [0, 0, 1, 0], [0, 0, 0, 1]])",
+    );
+    assert_near(min, [3.0, 0.0, 0.0]);
+    assert_near(max, [5.0, 1.0, 2.0]);
+}
+
+#[test]
+fn transform_rejects_singular_and_malformed_matrices() {
+    let err = eval_source(
+        "transform(box(vec3(1mm, 1mm, 1mm)), [[1, 0, 0, 0], [0, 1, 0, 0], [0, 0, 0, 0], [0, 0, 0, 1]])",
+    )
+    .unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("singular"), "{}", err.message);
+    let err = eval_source("transform(box(vec3(1mm, 1mm, 1mm)), [[1, 0, 0], [0, 1, 0], [0, 0, 1]])")
+        .unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+}
+
+// ── Planes / mirror ─────────────────────────────────────────────────
+
+#[test]
+fn plane_constructors_and_fields() {
+    let val = eval_source(
+        "let p = offset_plane(plane(vec3(1mm, 2mm, 3mm), vec3(0, 0, 2)), 5mm)\n\
+         [p.origin, p.normal, offset_plane(XY, -2mm).origin]",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected List, got {val:?}");
+    };
+    let expected = [[1.0, 2.0, 8.0], [0.0, 0.0, 1.0], [0.0, 0.0, -2.0]];
+    for (item, want) in items.iter().zip(expected) {
+        assert!(matches!(item, Value::Vec3(v) if *v == want), "{item:?} vs {want:?}");
+    }
+}
+
+#[test]
+fn plane_rejects_zero_normal() {
+    let err = eval_source("plane(vec3(0, 0, 0), vec3(0, 0, 0))").unwrap_err();
+    assert!(err.message.contains("non-zero"), "{}", err.message);
+}
+
+#[test]
+fn mirrored_half_joins_into_symmetric_part() {
+    let val = eval_source(
+        "let half = difference(box(vec3(20mm, 10mm, 5mm)), move(cylinder(2mm, 10mm), vec3(10mm, 5mm, -2mm)))\n\
+         let other = mirror(half, plane(vec3(20mm, 0mm, 0mm), vec3(1, 0, 0)))\n\
+         let b = bbox(other)\n\
+         [b.min, b.max, volume(other), volume(half)]",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected List, got {val:?}");
+    };
+    let near = |v: &Value, want: [f64; 3]| {
+        matches!(v, Value::Vec3(v) if v.iter().zip(want).all(|(a, b)| (a - b).abs() < 1e-9))
+    };
+    assert!(near(&items[0], [20.0, 0.0, 0.0]), "{:?}", items[0]);
+    assert!(near(&items[1], [40.0, 10.0, 5.0]), "{:?}", items[1]);
+    let (Value::Float(mirrored), Value::Float(original)) = (&items[2], &items[3]) else {
+        panic!("expected volumes, got {items:?}");
+    };
+    assert!((mirrored - original).abs() < 1e-6 * original);
+}
+
+#[test]
+fn mirrored_solid_can_be_cut() {
+    let val = eval_source(
+        "let tool = mirror(move(cylinder(2mm, 20mm), vec3(5mm, 5mm, -5mm)), YZ)\n\
+         volume(difference(move(box(vec3(20mm, 10mm, 10mm)), vec3(-10mm, 0mm, 0mm)), tool))",
+    )
+    .unwrap();
+    let expected = 2000.0 - std::f64::consts::PI * 4.0 * 10.0;
+    assert!(matches!(val, Value::Float(v) if (v - expected).abs() < 1e-3 * expected), "{val:?}");
+}
+
+#[test]
+fn mirror_requires_plane() {
+    let err = eval_source("mirror(box(vec3(1mm, 1mm, 1mm)), vec3(1, 0, 0))").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+}
+
+// ── Shell / offset ──────────────────────────────────────────────────
+
+#[test]
+fn shell_enclosure_and_sleeve() {
+    for src in [
+        "shell(box(vec3(60mm, 40mm, 20mm)), 2mm, open_faces = [vec3(0, 0, 1)])",
+        "shell(cylinder(10mm, 30mm), 1.5mm, [vec3(0, 0, 1), vec3(0, 0, -1)])",
+        "shell(box(vec3(10mm, 10mm, 10mm)), 1mm)",
+        "offset(fillet(box(vec3(20mm, 20mm, 10mm)), 3mm, edges_parallel(vec3(0, 0, 1))), 1mm)",
+    ] {
+        let val = eval_source(src).unwrap();
+        assert!(matches!(val, Value::Solid(_)), "{src}");
+    }
+}
+
+#[test]
+fn shell_wall_too_thick() {
+    let err = eval_source("shell(box(vec3(10mm, 10mm, 10mm)), 6mm)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("shell failed"), "{}", err.message);
+}
+
+// ── Hull / Minkowski ────────────────────────────────────────────────
+
+#[test]
+fn hull_bridges_two_boxes() {
+    let val = eval_source(
+        "let a = box(vec3(2mm, 2mm, 2mm))\n\
+         volume(hull([a, move(a, vec3(8mm, 0mm, 0mm))]))",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Float(v) if (v - 40.0).abs() < 1e-6), "{val:?}");
+}
+
+#[test]
+fn minkowski_rounds_a_plate() {
+    let val = eval_source("minkowski(box(vec3(20mm, 10mm, 2mm)), sphere(1mm))").unwrap();
+    assert!(matches!(val, Value::Solid(_)));
+}
+
+#[test]
+fn minkowski_rejects_non_convex_solid() {
+    let err = eval_source(
+        "let tube = difference(box(vec3(10mm, 10mm, 10mm)), move(box(vec3(5mm, 5mm, 20mm)), vec3(2.5mm, 2.5mm, -5mm)))\n\
+         minkowski(tube, sphere(1mm))",
+    )
+    .unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("convex"), "{}", err.message);
+}
+
+// ── Section / projection ────────────────────────────────────────────
+
+#[test]
+fn section_of_drilled_plate_has_a_hole() {
+    let val = eval_source(
+        "let plate = difference(box(vec3(40mm, 20mm, 5mm)), move(cylinder(4mm, 10mm), vec3(20mm, 10mm, -2mm)))\n\
+         map(|f| volume(extrude(f, 1mm)), section(plate, offset_plane(XY, 2.5mm)))",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected List, got {val:?}");
+    };
+    let [Value::Float(v)] = items[..] else {
+        panic!("expected one area, got {items:?}");
+    };
+    let expected = 800.0 - std::f64::consts::PI * 16.0;
+    assert!((v - expected).abs() < 1e-2 * expected, "{v} vs {expected}");
+}
+
+#[test]
+fn section_missing_solid_is_empty() {
+    let val = eval_source("section(box(vec3(10mm, 10mm, 10mm)), offset_plane(XY, 20mm))").unwrap();
+    assert!(matches!(val, Value::List(ref faces) if faces.is_empty()), "{val:?}");
+}
+
+#[test]
+fn project_l_bracket_outline() {
+    let val = eval_source(
+        "let l = union(box(vec3(30mm, 10mm, 2mm)), move(box(vec3(2mm, 8mm, 20mm)), vec3(1mm, 1mm, 1mm)))\n\
+         map(|f| volume(extrude(f, 1mm)), project(l, XZ))",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected List, got {val:?}");
+    };
+    assert!(matches!(items[..], [Value::Float(v)] if (v - (60.0 + 38.0)).abs() < 1e-6), "{items:?}");
+}
+
+// ── Mass properties ─────────────────────────────────────────────────
+
+#[test]
+fn volume_of_drilled_block() {
+    let val = eval_source(
+        "volume(difference(box(vec3(20mm, 20mm, 10mm)), move(cylinder(5mm, 20mm), vec3(10mm, 10mm, -5mm))))",
+    )
+    .unwrap();
+    let Value::Float(v) = val else {
+        panic!("expected Float, got {val:?}");
+    };
+    let expected = 4000.0 - std::f64::consts::PI * 25.0 * 10.0;
+    assert!((v - expected).abs() < 1e-3 * expected, "{v} vs {expected}");
+}
+
+#[test]
+fn centroid_of_moved_box() {
+    let val = eval_source("centroid(move(box(vec3(2mm, 4mm, 6mm)), vec3(10mm, 0mm, 0mm)))").unwrap();
+    let Value::Vec3([x, y, z]) = val else {
+        panic!("expected Vec3, got {val:?}");
+    };
+    assert!((x - 11.0).abs() < 1e-9 && (y - 2.0).abs() < 1e-9 && (z - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn volume_rejects_bad_tolerance() {
+    let err = eval_source("volume(sphere(5mm), tolerance = 0mm)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("volume failed"), "{}", err.message);
+}
+
+// ── Geometric queries ───────────────────────────────────────────────
+
+#[test]
+fn bbox_fields() {
+    let val = eval_source(
+        "let b = bbox(move(box(vec3(40mm, 20mm, 5mm)), vec3(5mm, 5mm, 0mm)))\n\
+         [b.min, b.max, b.size, b.center]",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected List, got {val:?}");
+    };
+    let expected = [
+        [5.0, 5.0, 0.0],
+        [45.0, 25.0, 5.0],
+        [40.0, 20.0, 5.0],
+        [25.0, 15.0, 2.5],
+    ];
+    for (item, want) in items.iter().zip(expected) {
+        assert!(matches!(item, Value::Vec3(v) if *v == want), "{item:?} vs {want:?}");
+    }
+}
+
+#[test]
+fn hole_centered_on_plate_from_bbox() {
+    let val = eval_source(
+        "let plate = move(box(vec3(40mm, 20mm, 5mm)), vec3(5mm, 5mm, 0mm))\n\
+         let c = bbox(plate).center\n\
+         let hole = move(move(cylinder(3mm, 20mm), c), vec3(0mm, 0mm, -10mm))\n\
+         let part = difference(plate, hole)\n\
+         [contains_point(part, c), contains_point(part, vec3(10mm, 10mm, 2mm))]",
+    )
+    .unwrap();
+    assert!(
+        matches!(&val, Value::List(items) if matches!(items[..], [Value::Bool(false), Value::Bool(true)])),
+        "{val:?}"
+    );
+}
+
+#[test]
+fn closest_distance_between_solids() {
+    let val = eval_source(
+        "closest_distance(box(vec3(10mm, 10mm, 10mm)), move(sphere(2mm), vec3(5mm, 5mm, 15mm)))",
+    )
+    .unwrap();
+    assert!(matches!(val, Value::Length(d) if (d - 3.0).abs() < 0.02), "{val:?}");
+}
+
+// ── Patterns ────────────────────────────────────────────────────────
+
+#[test]
+fn grid_drilled_plate_volume() {
+    let val = eval_source(
+        "let hole = move(cylinder(2mm, 20mm), vec3(10mm, 10mm, -5mm))\n\
+         volume(difference(box(vec3(80mm, 50mm, 5mm)), grid_pattern(hole, 3, 2, 30mm, 30mm)))",
+    )
+    .unwrap();
+    let Value::Float(v) = val else {
+        panic!("expected Float, got {val:?}");
+    };
+    let expected = 80.0 * 50.0 * 5.0 - 6.0 * std::f64::consts::PI * 4.0 * 5.0;
+    assert!((v - expected).abs() < 1e-3 * expected, "{v} vs {expected}");
+}
+
+#[test]
+fn bolt_circle_bounds() {
+    let (min, max) = bounds_of(
+        "polar_pattern(move(cylinder(2mm, 5mm), vec3(20mm, -2mm, 0mm)), vec3(0, 0, 1), 4)",
+    );
+    let (min, max) = (min.map(|c| c.round()), max.map(|c| c.round()));
+    assert_near(min, [-22.0, -22.0, 0.0]);
+    assert_near(max, [22.0, 22.0, 5.0]);
+}
+
+#[test]
+fn unfused_pattern_is_a_list() {
+    let val = eval_source(
+        "linear_pattern(box(vec3(1mm, 1mm, 1mm)), vec3(2, 0, 0), 3, 5mm, fuse = false)",
+    )
+    .unwrap();
+    let Value::List(items) = val else {
+        panic!("expected List, got {val:?}");
+    };
+    assert_eq!(items.len(), 3);
+    assert!(items.iter().all(|item| matches!(item, Value::Solid(_))));
+}
+
+#[test]
+fn partial_polar_pattern_spans_the_angle() {
+    let (min, max) = bounds_of(
+        "polar_pattern(move(box(vec3(2mm, 2mm, 2mm)), vec3(10mm, -1mm, 0mm)), vec3(0, 0, 1), 3, angle = 90deg)",
+    );
+    // Copies at 0°, 45° and 90°: the last one reaches y = 12.
+    assert!((max[1] - 12.0).abs() < 1e-6, "{max:?}");
+    assert!((max[0] - 12.0).abs() < 1e-6 && min[0] > -1.0 - 1e-6, "{min:?} {max:?}");
+}
+
+#[test]
+fn patterns_are_recorded_as_features() {
+    let (ast, _) = covariant_syntax::parse(
+        "let pin = cylinder(1mm, 5mm)\n\
+         let a = linear_pattern(pin, vec3(1, 0, 0), 4, 20mm)\n\
+         polar_pattern(pin, vec3(0, 0, 1), 6, origin = vec3(-10mm, 0, 0), fuse = false)",
+    );
+    let (dag, _) = covariant_ir::lower(&ast);
+    let features = covariant_eval::eval_with(&dag, &TruckKernel, &Default::default())
+        .unwrap()
+        .features;
+    let labels: Vec<String> = features.iter().map(|f| f.to_string()).collect();
+    assert_eq!(
+        labels,
+        [
+            "linear_pattern: 4 × 20mm along (1, 0, 0)",
+            "polar_pattern: 6 × 60deg about (0, 0, 1)",
+        ]
+    );
+}
+
+#[test]
+fn pattern_count_must_be_positive() {
+    let err = eval_source("grid_pattern(box(vec3(1mm, 1mm, 1mm)), 0, 2, 5mm, 5mm)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::Custom);
+    assert!(
+        err.message.contains("'nx' must be at least 1, got 0"),
+        "{}",
+        err.message
+    );
+}
+
+// ── Built-in signatures ─────────────────────────────────────────────
+
+#[test]
+fn builtin_named_args_reordered() {
+    let val = eval_source("vec3(z = 3mm, x = 1mm, y = 2mm)").unwrap();
+    assert!(matches!(val, Value::Vec3([x, y, z]) if x == 1.0 && y == 2.0 && z == 3.0));
+}
+
+#[test]
+fn builtin_unknown_parameter_is_reported() {
+    let err = eval_source("cylinder(5mm, hieght = 20mm)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::ArityMismatch);
+    assert!(err.message.contains("unknown parameter 'hieght'"), "{}", err.message);
+    assert!(err.message.starts_with("cylinder:"), "{}", err.message);
+}
+
+#[test]
+fn builtin_missing_argument_is_reported() {
+    let err = eval_source("cylinder(radius = 5mm)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::ArityMismatch);
+    assert!(err.message.contains("missing argument 'height'"), "{}", err.message);
+}
+
+#[test]
+fn builtin_too_many_arguments_is_reported() {
+    let err = eval_source("sphere(1mm, 2mm)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::ArityMismatch);
+}
+
+#[test]
+fn builtin_duplicate_argument_is_reported() {
+    let err = eval_source("sphere(1mm, radius = 2mm)").unwrap_err();
+    assert!(err.message.contains("given more than once"), "{}", err.message);
+}
+
+#[test]
+fn builtin_argument_type_is_checked() {
+    let err = eval_source("sphere(true)").unwrap_err();
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::TypeError);
+    assert!(err.message.contains("expected Length for 'radius'"), "{}", err.message);
+}
+
 // ── Control flow ────────────────────────────────────────────────────
 
 #[test]
@@ -247,6 +1132,143 @@ fn export_stl_writes_file() {
     let _ = std::fs::remove_file(path);
 }
 
+#[test]
+fn export_step_writes_named_product() {
+    let path = "/tmp/covariant_test_export.step";
+    let _ = std::fs::remove_file(path);
+
+    let val = eval_source(&format!(
+        "export_step(\"{path}\", cylinder(5mm, 10mm), name = \"spacer\")"
+    ))
+    .unwrap();
+    assert!(matches!(val, Value::Unit));
+    let content = std::fs::read_to_string(path).expect("STEP file was not created");
+    let _ = std::fs::remove_file(path);
+    assert!(content.starts_with("ISO-10303-21;"));
+    assert!(content.contains("AUTOMOTIVE_DESIGN"));
+    assert!(content.contains("PRODUCT('spacer','spacer',"));
+}
+
+#[test]
+fn export_3mf_writes_named_bodies_with_metadata() {
+    let path = "/tmp/covariant_test_export.3mf";
+    let _ = std::fs::remove_file(path);
+
+    let val = eval_source(&format!(
+        "data Params {{ width: Length, holes: Int }}\n\
+         let p = Params {{ width = 20mm, holes = 2 }}\n\
+         export_3mf(\"{path}\", [box(vec3(p.width, 10mm, 2mm)), cylinder(2mm, 5mm)],\n\
+           names = [\"plate\"], colors = [\"#FF8000\", \"\"], materials = [\"PLA\"],\n\
+           metadata = p, quality = \"draft\")"
+    ))
+    .unwrap();
+    assert!(matches!(val, Value::Unit));
+    let bytes = std::fs::read(path).expect("3MF file was not created");
+    let _ = std::fs::remove_file(path);
+    assert!(bytes.starts_with(b"PK\x03\x04"));
+    let has = |name: &[u8]| bytes.windows(name.len()).any(|w| w == name);
+    assert!(has(b"[Content_Types].xml"));
+    assert!(has(b"3D/3dmodel.model"));
+}
+
+#[test]
+fn export_3mf_rejects_bad_colors() {
+    let err = eval_source(
+        "export_3mf(\"/tmp/covariant_test_bad_color.3mf\", box(vec3(1mm, 1mm, 1mm)),\n\
+           colors = [\"red\"])",
+    )
+    .unwrap_err();
+    assert!(
+        err.message.contains("invalid color 'red'"),
+        "{}",
+        err.message
+    );
+}
+
+#[test]
+fn export_mesh_picks_format_from_extension() {
+    let path = "/tmp/covariant_test_export_mesh.glb";
+    let _ = std::fs::remove_file(path);
+
+    let val = eval_source(&format!(
+        "export_mesh(\"{path}\", [box(vec3(10mm, 10mm, 2mm)), sphere(2mm)],\n\
+           names = [\"tray\", \"ball\"], colors = [\"#3060C0\"])"
+    ))
+    .unwrap();
+    assert!(matches!(val, Value::Unit));
+    let bytes = std::fs::read(path).expect("glTF file was not created");
+    let _ = std::fs::remove_file(path);
+    assert!(bytes.starts_with(b"glTF"));
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(text.contains(r#"{"name":"tray","mesh":0}"#));
+    assert!(text.contains(r#"{"name":"ball","mesh":1}"#));
+
+    let err = eval_source("export_mesh(\"/tmp/part.dxf\", box(vec3(1mm, 1mm, 1mm)))")
+        .unwrap_err();
+    assert!(
+        err.message.contains("unsupported mesh format"),
+        "{}",
+        err.message
+    );
+}
+
+// ── Mesh import ─────────────────────────────────────────────────────
+
+#[test]
+fn imported_stl_is_cut_around() {
+    let path = "/tmp/covariant_test_import_part.stl";
+    eval_source(&format!(
+        "export_stl(\"{path}\", move(box(vec3(10mm, 10mm, 10mm)), vec3(5mm, 5mm, 3mm)))"
+    ))
+    .unwrap();
+
+    let val = eval_source(&format!(
+        "let part = mesh_to_solid(import_stl(\"{path}\"))\n\
+         volume(difference(box(vec3(20mm, 20mm, 8mm)), part))"
+    ))
+    .unwrap();
+    let _ = std::fs::remove_file(path);
+    assert!(matches!(val, Value::Float(v) if (v - (3200.0 - 500.0)).abs() < 1e-6), "{val:?}");
+}
+
+#[test]
+fn import_obj_open_mesh_is_rejected() {
+    let path = "/tmp/covariant_test_import_open.obj";
+    std::fs::write(path, "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n").unwrap();
+    let err = eval_source(&format!("mesh_to_solid(import_obj(\"{path}\"))")).unwrap_err();
+    let _ = std::fs::remove_file(path);
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("not closed"), "{}", err.message);
+}
+
+// ── Native solid files ──────────────────────────────────────────────
+
+#[test]
+fn saved_solid_loads_with_exact_geometry() {
+    let path = "/tmp/covariant_test_native.covs";
+    eval_source(&format!(
+        "save_solid(\"{path}\", difference(box(vec3(20mm, 20mm, 10mm)), move(cylinder(5mm, 20mm), vec3(10mm, 10mm, -5mm))))"
+    ))
+    .unwrap();
+    let val = eval_source(&format!("volume(load_solid(\"{path}\"))")).unwrap();
+    let _ = std::fs::remove_file(path);
+    let Value::Float(v) = val else {
+        panic!("expected Float, got {val:?}");
+    };
+    let expected = 4000.0 - std::f64::consts::PI * 25.0 * 10.0;
+    assert!((v - expected).abs() < 1e-3 * expected, "{v} vs {expected}");
+}
+
+#[test]
+fn load_solid_rejects_other_files() {
+    let path = "/tmp/covariant_test_native_foreign.covs";
+    std::fs::write(path, "solid empty\nendsolid empty\n").unwrap();
+    let err = eval_source(&format!("load_solid(\"{path}\")")).unwrap_err();
+    let _ = std::fs::remove_file(path);
+    assert_eq!(err.kind, covariant_eval::EvalErrorKind::GeomError);
+    assert!(err.message.contains("not a covariant solid"), "{}", err.message);
+}
+
 // ── Enum definitions ────────────────────────────────────────────────
 
 #[test]
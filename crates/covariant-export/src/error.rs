@@ -9,6 +9,8 @@ pub enum ExportErrorKind {
     GeomError,
     /// Mesh validation detected a fatal issue (e.g. empty mesh).
     ValidationFailed,
+    /// Writing the output file failed.
+    IoError,
 }
 
 impl fmt::Display for ExportErrorKind {
@@ -16,6 +18,7 @@ impl fmt::Display for ExportErrorKind {
         match self {
             Self::GeomError => write!(f, "geometry error"),
             Self::ValidationFailed => write!(f, "mesh validation failed"),
+            Self::IoError => write!(f, "I/O error"),
         }
     }
 }
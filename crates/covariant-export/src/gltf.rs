@@ -0,0 +1,257 @@
+//! Binary glTF 2.0 (`.glb`) writer.
+//!
+//! Each body becomes a node with its own mesh: single-precision positions
+//! and normals, shared where both agree, and 32-bit indices. Coloured
+//! bodies get a PBR material. glTF is Y-up and in metres, so the bodies
+//! hang under one root node that scales millimetres to metres and turns
+//! the model's Z-up onto Y.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use covariant_geom::Mesh;
+
+use crate::mesh::{ModelBody, vertex_key};
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+
+/// Encode tessellated bodies as a GLB file with a root node named `title`.
+pub(crate) fn glb_bytes(title: &str, bodies: &[(&ModelBody, Mesh)]) -> Vec<u8> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut meshes = Vec::new();
+    let mut materials = Vec::new();
+    let mut nodes = vec![String::new()];
+
+    for (body, mesh) in bodies {
+        let (vertices, indices) = vertices_and_indices(mesh);
+        let name = json_string(&body.name);
+
+        let mut view = |bytes: Vec<u8>, target: u32| {
+            let offset = buffer.len();
+            buffer.extend(&bytes);
+            views.push(format!(
+                r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{},"target":{target}}}"#,
+                bytes.len()
+            ));
+            views.len() - 1
+        };
+        let positions = view(
+            vertices.iter().flat_map(|(p, _)| f32_bytes(*p)).collect(),
+            ARRAY_BUFFER,
+        );
+        let normals = view(
+            vertices.iter().flat_map(|(_, n)| f32_bytes(*n)).collect(),
+            ARRAY_BUFFER,
+        );
+        let index_view = view(
+            indices.iter().flat_map(|i| i.to_le_bytes()).collect(),
+            ELEMENT_ARRAY_BUFFER,
+        );
+
+        let (min, max) = bounds(vertices.iter().map(|(p, _)| *p));
+        let first = accessors.len();
+        accessors.push(format!(
+            r#"{{"bufferView":{positions},"componentType":{FLOAT},"count":{},"type":"VEC3","min":{},"max":{}}}"#,
+            vertices.len(),
+            json_numbers(&min),
+            json_numbers(&max)
+        ));
+        accessors.push(format!(
+            r#"{{"bufferView":{normals},"componentType":{FLOAT},"count":{},"type":"VEC3"}}"#,
+            vertices.len()
+        ));
+        accessors.push(format!(
+            r#"{{"bufferView":{index_view},"componentType":{UNSIGNED_INT},"count":{},"type":"SCALAR"}}"#,
+            indices.len()
+        ));
+
+        let material = match body.color {
+            Some(color) => {
+                let factor = color.linear().map(|c| c as f32);
+                let blend = if color.a < 0xFF {
+                    r#","alphaMode":"BLEND""#
+                } else {
+                    ""
+                };
+                materials.push(format!(
+                    r#"{{"name":{name},"pbrMetallicRoughness":{{"baseColorFactor":{},"metallicFactor":0,"roughnessFactor":0.5}}{blend}}}"#,
+                    json_numbers(&factor)
+                ));
+                format!(r#","material":{}"#, materials.len() - 1)
+            }
+            None => String::new(),
+        };
+        meshes.push(format!(
+            r#"{{"name":{name},"primitives":[{{"attributes":{{"POSITION":{},"NORMAL":{}}},"indices":{},"mode":4{material}}}]}}"#,
+            first,
+            first + 1,
+            first + 2
+        ));
+        nodes.push(format!(r#"{{"name":{name},"mesh":{}}}"#, meshes.len() - 1));
+    }
+
+    let children: Vec<String> = (1..nodes.len()).map(|i| i.to_string()).collect();
+    let half = std::f32::consts::FRAC_1_SQRT_2;
+    nodes[0] = format!(
+        r#"{{"name":{},"rotation":[{},0,0,{half}],"scale":[0.001,0.001,0.001],"children":[{}]}}"#,
+        json_string(title),
+        -half,
+        children.join(",")
+    );
+
+    let mut json = String::new();
+    let _ = write!(
+        json,
+        r#"{{"asset":{{"version":"2.0","generator":"covariant"}},"scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{}],"meshes":[{}],"#,
+        nodes.join(","),
+        meshes.join(",")
+    );
+    if !materials.is_empty() {
+        let _ = write!(json, r#""materials":[{}],"#, materials.join(","));
+    }
+    let _ = write!(
+        json,
+        r#""accessors":[{}],"bufferViews":[{}],"buffers":[{{"byteLength":{}}}]}}"#,
+        accessors.join(","),
+        views.join(","),
+        buffer.len()
+    );
+
+    let mut json = json.into_bytes();
+    while !json.len().is_multiple_of(4) {
+        json.push(b' ');
+    }
+    // Every view holds 4-byte components, so the buffer is already aligned.
+    debug_assert_eq!(buffer.len() % 4, 0);
+
+    let total = 12 + 8 + json.len() + 8 + buffer.len();
+    let mut out = Vec::with_capacity(total);
+    out.extend(b"glTF");
+    out.extend(2u32.to_le_bytes());
+    out.extend((total as u32).to_le_bytes());
+    out.extend((json.len() as u32).to_le_bytes());
+    out.extend(b"JSON");
+    out.extend(json);
+    out.extend((buffer.len() as u32).to_le_bytes());
+    out.extend(b"BIN\0");
+    out.extend(buffer);
+    out
+}
+
+/// A vertex position and normal.
+type Vertex = ([f32; 3], [f32; 3]);
+
+/// Distinct (position, normal) pairs and the triangle indices into them.
+fn vertices_and_indices(mesh: &Mesh) -> (Vec<Vertex>, Vec<u32>) {
+    let positions = mesh.positions();
+    let mut index: HashMap<([u64; 3], [u64; 3]), u32> = HashMap::new();
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for (tri, normals) in mesh.tri_faces().iter().zip(mesh.corner_normals()) {
+        for (&v, n) in tri.iter().zip(normals) {
+            let key = (vertex_key(positions[v]), vertex_key(n));
+            let i = *index.entry(key).or_insert_with(|| {
+                vertices.push((positions[v].map(|c| c as f32), n.map(|c| c as f32)));
+                vertices.len() as u32 - 1
+            });
+            indices.push(i);
+        }
+    }
+    (vertices, indices)
+}
+
+fn bounds(points: impl Iterator<Item = [f32; 3]>) -> ([f32; 3], [f32; 3]) {
+    points.fold(([f32::MAX; 3], [f32::MIN; 3]), |(min, max), p| {
+        (
+            [0, 1, 2].map(|k| min[k].min(p[k])),
+            [0, 1, 2].map(|k| max[k].max(p[k])),
+        )
+    })
+}
+
+fn f32_bytes(v: [f32; 3]) -> impl Iterator<Item = u8> {
+    v.into_iter().flat_map(f32::to_le_bytes)
+}
+
+fn json_numbers(values: &[f32]) -> String {
+    let items: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    format!("[{}]", items.join(","))
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Color;
+    use covariant_geom::TruckKernel;
+    use covariant_geom::kernel::GeomKernel;
+
+    fn chunks(bytes: &[u8]) -> (String, &[u8]) {
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap()) as usize;
+        assert_eq!(&bytes[..4], b"glTF");
+        assert_eq!(u32_at(4), 2);
+        assert_eq!(u32_at(8), bytes.len());
+        let json_len = u32_at(12);
+        assert_eq!(&bytes[16..20], b"JSON");
+        let json = String::from_utf8(bytes[20..20 + json_len].to_vec()).unwrap();
+        let bin = 20 + json_len;
+        assert_eq!(&bytes[bin + 4..bin + 8], b"BIN\0");
+        (json, &bytes[bin + 8..bin + 8 + u32_at(bin)])
+    }
+
+    #[test]
+    fn writes_one_node_per_body() {
+        let kernel = TruckKernel;
+        let mut plate = ModelBody::new("plate \"A\"", kernel.box_solid(10.0, 10.0, 1.0));
+        plate.color = Color::parse("#FF0000");
+        let pin = ModelBody::new("pin", kernel.cylinder(1.0, 5.0));
+        let meshes = [&plate, &pin].map(|body| (body, kernel.tessellate(&body.solid, 0.1)));
+        let bytes = glb_bytes("review", &meshes);
+        let (json, bin) = chunks(&bytes);
+
+        assert_eq!(json.len() % 4, 0);
+        assert!(json.contains(r#""name":"review","rotation":[-0.70710677,0,0,0.70710677],"scale":[0.001,0.001,0.001],"children":[1,2]"#));
+        assert!(json.contains(r#"{"name":"plate \"A\"","mesh":0}"#));
+        assert!(json.contains(r#"{"name":"pin","mesh":1}"#));
+        assert!(json.contains(r#""baseColorFactor":[1,0,0,1]"#));
+        assert!(json.contains(r#""min":[0,0,0],"max":[10,10,1]"#));
+        assert!(json.contains(&format!(r#""buffers":[{{"byteLength":{}}}]"#, bin.len())));
+        assert_eq!(json.matches(r#""material":"#).count(), 1);
+    }
+
+    #[test]
+    fn box_keeps_sharp_edges() {
+        let kernel = TruckKernel;
+        let mesh = kernel.tessellate(&kernel.box_solid(1.0, 1.0, 1.0), 0.1);
+        let (vertices, indices) = vertices_and_indices(&mesh);
+        // Each corner is shared by three faces with different normals.
+        assert_eq!(vertices.len(), 24);
+        assert_eq!(indices.len(), 36);
+    }
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\u000a""#);
+    }
+}
@@ -1,16 +1,27 @@
 //! Export pipeline for COVARIANT.
 //!
-//! Provides quality-controlled STL export with thread mode resolution
-//! and mesh validation.
+//! Provides quality-controlled mesh export (STL, OBJ, PLY and binary
+//! glTF) with thread mode resolution and mesh validation, STEP AP214
+//! export of exact geometry, and 3MF export of named, coloured bodies
+//! with model metadata.
 
 pub mod error;
+mod gltf;
+pub mod mesh;
+mod obj;
+mod ply;
 pub mod quality;
+pub mod step;
 pub mod stl;
 pub mod thread;
+pub mod threemf;
 pub mod validate;
 
 pub use error::{ExportError, ExportErrorKind, ExportResult};
-pub use quality::{ExportOptions, Quality, StlFormat};
+pub use mesh::{Color, ModelBody, export_mesh};
+pub use quality::{ExportOptions, MeshFormat, Quality};
+pub use step::{StepOptions, export_step};
 pub use stl::export_stl;
-pub use thread::{EffectiveThreadMode, resolve_thread_mode};
+pub use thread::{EffectiveThreadMode, build_for_export, resolve_thread_mode};
+pub use threemf::export_3mf;
 pub use validate::{MeshReport, MeshWarning, validate_mesh};
@@ -0,0 +1,276 @@
+//! Mesh export orchestration.
+//!
+//! Tessellates named bodies and writes them in the format selected by
+//! [`ExportOptions::format`]: STL, OBJ, PLY or binary glTF.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use covariant_geom::kernel::GeomKernel;
+use covariant_geom::{Mesh, Solid};
+
+use crate::error::{ExportError, ExportErrorKind, ExportResult};
+use crate::quality::{ExportOptions, MeshFormat};
+
+/// An sRGB colour with alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    /// Neutral grey, used for bodies that name a material but no colour.
+    pub const GREY: Self = Self {
+        r: 0x80,
+        g: 0x80,
+        b: 0x80,
+        a: 0xFF,
+    };
+
+    /// Parse `#RRGGBB` or `#RRGGBBAA`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let hex = text.strip_prefix('#')?;
+        if !matches!(hex.len(), 6 | 8) || !hex.is_ascii() {
+            return None;
+        }
+        let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        Some(Self {
+            r: byte(0)?,
+            g: byte(2)?,
+            b: byte(4)?,
+            a: if hex.len() == 8 { byte(6)? } else { 0xFF },
+        })
+    }
+
+    pub(crate) fn hex(self) -> String {
+        format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.a)
+    }
+
+    /// The colour in linear RGB with straight alpha, each in `0..=1`.
+    pub(crate) fn linear(self) -> [f64; 4] {
+        let channel = |c: u8| {
+            let c = f64::from(c) / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        [
+            channel(self.r),
+            channel(self.g),
+            channel(self.b),
+            f64::from(self.a) / 255.0,
+        ]
+    }
+}
+
+/// A named body of an exported model.
+#[derive(Debug, Clone)]
+pub struct ModelBody {
+    /// Object name shown by slicers and viewers.
+    pub name: String,
+    pub solid: Solid,
+    /// Display colour (3MF and glTF).
+    pub color: Option<Color>,
+    /// Material name (3MF); defaults to the body name when only a colour
+    /// is set.
+    pub material: Option<String>,
+}
+
+impl ModelBody {
+    /// A named body without colour or material.
+    pub fn new(name: impl Into<String>, solid: Solid) -> Self {
+        Self {
+            name: name.into(),
+            solid,
+            color: None,
+            material: None,
+        }
+    }
+}
+
+/// Export bodies to a mesh file in the format of `options.format`.
+///
+/// STL has no notion of separate bodies, so they are united into one
+/// solid first; OBJ and glTF keep one object or node per body, and PLY
+/// writes all bodies into one mesh.
+pub fn export_mesh(
+    kernel: &dyn GeomKernel,
+    bodies: &[ModelBody],
+    path: &Path,
+    options: &ExportOptions,
+) -> ExportResult<()> {
+    let bytes = match options.format {
+        MeshFormat::StlBinary | MeshFormat::StlAscii => {
+            let solids: Vec<Solid> = bodies.iter().map(|b| b.solid.clone()).collect();
+            if solids.is_empty() {
+                return Err(no_bodies());
+            }
+            let solid = kernel.union_many(&solids)?;
+            return crate::stl::export_stl(kernel, &solid, path, options);
+        }
+        MeshFormat::Obj => {
+            let meshes = tessellate_bodies(kernel, bodies, options)?;
+            crate::obj::obj_string(&meshes).into_bytes()
+        }
+        MeshFormat::Ply => crate::ply::ply_bytes(&tessellate_bodies(kernel, bodies, options)?),
+        MeshFormat::Glb => {
+            let title = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            crate::gltf::glb_bytes(&title, &tessellate_bodies(kernel, bodies, options)?)
+        }
+    };
+    write_file(path, &bytes)
+}
+
+/// Tessellate each body, rejecting bodies that produce no triangles.
+pub(crate) fn tessellate_bodies<'b>(
+    kernel: &dyn GeomKernel,
+    bodies: &'b [ModelBody],
+    options: &ExportOptions,
+) -> ExportResult<Vec<(&'b ModelBody, Mesh)>> {
+    if bodies.is_empty() {
+        return Err(no_bodies());
+    }
+    let tolerance = options.quality.tolerance();
+    bodies
+        .iter()
+        .map(|body| {
+            let mesh = kernel.tessellate(&body.solid, tolerance);
+            if mesh.is_empty() {
+                return Err(ExportError::new(
+                    ExportErrorKind::ValidationFailed,
+                    format!("body '{}' tessellated to an empty mesh", body.name),
+                ));
+            }
+            Ok((body, mesh))
+        })
+        .collect()
+}
+
+/// Weld the coincident vertices tessellation leaves along face
+/// boundaries, dropping triangles that collapse.
+pub(crate) fn weld(
+    positions: &[[f64; 3]],
+    triangles: &[[usize; 3]],
+) -> (Vec<[f64; 3]>, Vec<[usize; 3]>) {
+    let (welded, remap) = weld_positions(positions);
+    let triangles = triangles
+        .iter()
+        .map(|tri| tri.map(|v| remap[v]))
+        .filter(|[a, b, c]| a != b && b != c && c != a)
+        .collect();
+    (welded, triangles)
+}
+
+/// Merge exactly coincident positions, returning the distinct positions
+/// and the new index of each input position.
+pub(crate) fn weld_positions(positions: &[[f64; 3]]) -> (Vec<[f64; 3]>, Vec<usize>) {
+    let mut index: HashMap<[u64; 3], usize> = HashMap::new();
+    let mut welded = Vec::with_capacity(positions.len());
+    let remap = positions
+        .iter()
+        .map(|p| {
+            *index.entry(vertex_key(*p)).or_insert_with(|| {
+                welded.push(*p);
+                welded.len() - 1
+            })
+        })
+        .collect();
+    (welded, remap)
+}
+
+/// A hashable key for exact coordinates, with -0.0 normalized to 0.0.
+pub(crate) fn vertex_key(p: [f64; 3]) -> [u64; 3] {
+    p.map(|c| (c + 0.0).to_bits())
+}
+
+pub(crate) fn write_file(path: &Path, bytes: &[u8]) -> ExportResult<()> {
+    std::fs::write(path, bytes).map_err(|e| {
+        ExportError::new(
+            ExportErrorKind::IoError,
+            format!("failed to write {}: {e}", path.display()),
+        )
+    })
+}
+
+fn no_bodies() -> ExportError {
+    ExportError::new(
+        ExportErrorKind::ValidationFailed,
+        "nothing to export: no bodies",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use covariant_geom::{TruckKernel, Vector3};
+
+    #[test]
+    fn parses_colors() {
+        let red = Color::parse("#ff0000").unwrap();
+        assert_eq!(red.hex(), "#FF0000FF");
+        assert_eq!(Color::parse("#11223344").unwrap().a, 0x44);
+        assert!(Color::parse("ff0000").is_none());
+        assert!(Color::parse("#ff00").is_none());
+        assert!(Color::parse("#gg0000").is_none());
+        assert_eq!(Color::parse("#FF000080").unwrap().linear()[0], 1.0);
+        assert!((Color::GREY.linear()[1] - 0.2158605).abs() < 1e-6);
+    }
+
+    #[test]
+    fn box_mesh_welds_to_eight_vertices() {
+        let kernel = TruckKernel;
+        let mesh = kernel.tessellate(&kernel.box_solid(1.0, 1.0, 1.0), 0.1);
+        let (positions, triangles) = weld(&mesh.positions(), &mesh.tri_faces());
+        assert_eq!(positions.len(), 8);
+        assert_eq!(triangles.len(), 12);
+    }
+
+    #[test]
+    fn every_format_writes_a_file() {
+        let kernel = TruckKernel;
+        let bodies = [
+            ModelBody::new("a", kernel.box_solid(2.0, 2.0, 2.0)),
+            ModelBody::new(
+                "b",
+                kernel.translate(&kernel.sphere(1.0), Vector3::new(5.0, 0.0, 0.0)),
+            ),
+        ];
+        for (format, name) in [
+            (MeshFormat::StlBinary, "export_test_mesh.stl"),
+            (MeshFormat::StlAscii, "export_test_mesh_ascii.stl"),
+            (MeshFormat::Obj, "export_test_mesh.obj"),
+            (MeshFormat::Ply, "export_test_mesh.ply"),
+            (MeshFormat::Glb, "export_test_mesh.glb"),
+        ] {
+            let path = std::env::temp_dir().join(name);
+            let opts = ExportOptions {
+                format,
+                ..ExportOptions::default()
+            };
+            export_mesh(&kernel, &bodies, &path, &opts).expect("mesh export should succeed");
+            let meta = std::fs::metadata(&path).expect("file should exist");
+            assert!(meta.len() > 0, "{format:?}");
+            std::fs::remove_file(&path).ok();
+        }
+    }
+
+    #[test]
+    fn empty_input_is_rejected() {
+        let kernel = TruckKernel;
+        let path = std::env::temp_dir().join("export_test_empty.obj");
+        let opts = ExportOptions {
+            format: MeshFormat::Obj,
+            ..ExportOptions::default()
+        };
+        let err = export_mesh(&kernel, &[], &path, &opts).unwrap_err();
+        assert_eq!(err.kind, ExportErrorKind::ValidationFailed);
+    }
+}
@@ -0,0 +1,112 @@
+//! Wavefront OBJ writer.
+//!
+//! Each body becomes an `o` object. Vertices are shared between the
+//! triangles of a body and normals between corners that agree, so faces
+//! reference both by index (`f v//vn`). Coordinates are in millimetres.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use covariant_geom::Mesh;
+
+use crate::mesh::{ModelBody, vertex_key, weld_positions};
+
+/// Render tessellated bodies as OBJ text.
+pub(crate) fn obj_string(bodies: &[(&ModelBody, Mesh)]) -> String {
+    let mut out = String::from("# covariant\n# units: millimeter\n");
+    // OBJ indices are 1-based and global across objects.
+    let (mut vertex_base, mut normal_base) = (1, 1);
+    for (body, mesh) in bodies {
+        let (positions, remap) = weld_positions(&mesh.positions());
+        let mut normals: Vec<[f64; 3]> = Vec::new();
+        let mut normal_index: HashMap<[u64; 3], usize> = HashMap::new();
+        let mut faces = Vec::new();
+        for (tri, corner_normals) in mesh.tri_faces().iter().zip(mesh.corner_normals()) {
+            let v = tri.map(|i| remap[i]);
+            if v[0] == v[1] || v[1] == v[2] || v[2] == v[0] {
+                continue;
+            }
+            let n = corner_normals.map(|n| {
+                *normal_index.entry(vertex_key(n)).or_insert_with(|| {
+                    normals.push(n);
+                    normals.len() - 1
+                })
+            });
+            faces.push((v, n));
+        }
+
+        let _ = writeln!(out, "o {}", object_name(&body.name));
+        for [x, y, z] in &positions {
+            let _ = writeln!(out, "v {x} {y} {z}");
+        }
+        for [x, y, z] in &normals {
+            let _ = writeln!(out, "vn {x} {y} {z}");
+        }
+        for (v, n) in &faces {
+            let _ = writeln!(
+                out,
+                "f {}//{} {}//{} {}//{}",
+                v[0] + vertex_base,
+                n[0] + normal_base,
+                v[1] + vertex_base,
+                n[1] + normal_base,
+                v[2] + vertex_base,
+                n[2] + normal_base
+            );
+        }
+        vertex_base += positions.len();
+        normal_base += normals.len();
+    }
+    out
+}
+
+/// OBJ names end at the line and are split on whitespace by many readers.
+fn object_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use covariant_geom::kernel::GeomKernel;
+    use covariant_geom::{TruckKernel, Vector3};
+
+    #[test]
+    fn box_shares_vertices_and_normals() {
+        let kernel = TruckKernel;
+        let body = ModelBody::new("corner block", kernel.box_solid(1.0, 1.0, 1.0));
+        let mesh = kernel.tessellate(&body.solid, 0.1);
+        let text = obj_string(&[(&body, mesh)]);
+        let count = |prefix: &str| text.lines().filter(|l| l.starts_with(prefix)).count();
+        assert!(text.contains("\no corner_block\n"));
+        assert_eq!(count("v "), 8);
+        assert_eq!(count("vn "), 6);
+        assert_eq!(count("f "), 12);
+    }
+
+    #[test]
+    fn indices_continue_across_objects() {
+        let kernel = TruckKernel;
+        let a = ModelBody::new("a", kernel.box_solid(1.0, 1.0, 1.0));
+        let b = ModelBody::new(
+            "b",
+            kernel.translate(
+                &kernel.box_solid(1.0, 1.0, 1.0),
+                Vector3::new(3.0, 0.0, 0.0),
+            ),
+        );
+        let meshes = [&a, &b].map(|body| (body, kernel.tessellate(&body.solid, 0.1)));
+        let text = obj_string(&meshes);
+        let second = text.split("o b\n").nth(1).unwrap();
+        let faces: Vec<usize> = second
+            .lines()
+            .filter_map(|l| l.strip_prefix("f "))
+            .flat_map(|f| {
+                f.split(' ')
+                    .map(|c| c.split("//").next().unwrap().parse().unwrap())
+            })
+            .collect();
+        assert_eq!(faces.iter().min(), Some(&9));
+        assert_eq!(faces.iter().max(), Some(&16));
+    }
+}
@@ -0,0 +1,80 @@
+//! Binary little-endian PLY writer.
+//!
+//! All bodies go into one mesh of shared vertices (single-precision, in
+//! millimetres) and triangles.
+
+use covariant_geom::Mesh;
+
+use crate::mesh::{ModelBody, weld};
+
+/// Encode tessellated bodies as a binary PLY file.
+pub(crate) fn ply_bytes(bodies: &[(&ModelBody, Mesh)]) -> Vec<u8> {
+    let mut positions: Vec<[f64; 3]> = Vec::new();
+    let mut triangles: Vec<[usize; 3]> = Vec::new();
+    for (_, mesh) in bodies {
+        let (welded, tris) = weld(&mesh.positions(), &mesh.tri_faces());
+        let base = positions.len();
+        positions.extend(welded);
+        triangles.extend(tris.iter().map(|t| t.map(|v| v + base)));
+    }
+
+    let header = format!(
+        "ply\n\
+         format binary_little_endian 1.0\n\
+         comment generated by covariant\n\
+         comment units millimeter\n\
+         element vertex {}\n\
+         property float x\n\
+         property float y\n\
+         property float z\n\
+         element face {}\n\
+         property list uchar uint vertex_indices\n\
+         end_header\n",
+        positions.len(),
+        triangles.len()
+    );
+    let mut out = header.into_bytes();
+    out.reserve(positions.len() * 12 + triangles.len() * 13);
+    for p in &positions {
+        for c in p {
+            out.extend((*c as f32).to_le_bytes());
+        }
+    }
+    for tri in &triangles {
+        out.push(3);
+        for v in tri {
+            out.extend((*v as u32).to_le_bytes());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use covariant_geom::TruckKernel;
+    use covariant_geom::kernel::GeomKernel;
+
+    #[test]
+    fn box_layout_matches_header() {
+        let kernel = TruckKernel;
+        let body = ModelBody::new("box", kernel.box_solid(2.0, 3.0, 4.0));
+        let mesh = kernel.tessellate(&body.solid, 0.1);
+        let bytes = ply_bytes(&[(&body, mesh)]);
+
+        let end = b"end_header\n";
+        let split = bytes.windows(end.len()).position(|w| w == end).unwrap() + end.len();
+        let header = std::str::from_utf8(&bytes[..split]).unwrap();
+        assert!(header.contains("format binary_little_endian 1.0\n"));
+        assert!(header.contains("element vertex 8\n"));
+        assert!(header.contains("element face 12\n"));
+        assert_eq!(bytes.len() - split, 8 * 12 + 12 * 13);
+
+        let body = &bytes[split..];
+        let max_z = (0..8)
+            .map(|v| f32::from_le_bytes(body[v * 12 + 8..v * 12 + 12].try_into().unwrap()))
+            .fold(f32::MIN, f32::max);
+        assert_eq!(max_z, 4.0);
+        assert_eq!(body[8 * 12], 3);
+    }
+}
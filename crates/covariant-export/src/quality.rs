@@ -1,6 +1,6 @@
 //! Quality presets and export options.
 
-use covariant_thread::ThreadMode;
+use std::path::Path;
 
 /// Tessellation quality preset.
 ///
@@ -30,32 +30,50 @@ impl Quality {
     }
 }
 
-/// STL output format.
+/// Mesh output format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum StlFormat {
-    /// Compact binary format (default).
-    Binary,
-    /// Human-readable ASCII format.
-    Ascii,
+pub enum MeshFormat {
+    /// Compact binary STL (default).
+    StlBinary,
+    /// Human-readable ASCII STL.
+    StlAscii,
+    /// Wavefront OBJ with shared vertices and normals, one object per body.
+    Obj,
+    /// Binary little-endian PLY with shared vertices.
+    Ply,
+    /// Binary glTF 2.0 (`.glb`), one node per body.
+    Glb,
 }
 
-/// Options controlling an STL export.
+impl MeshFormat {
+    /// Infer the format from a file extension (case-insensitive): `stl`
+    /// (binary), `obj`, `ply` or `glb`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_ascii_lowercase();
+        match extension.as_str() {
+            "stl" => Some(Self::StlBinary),
+            "obj" => Some(Self::Obj),
+            "ply" => Some(Self::Ply),
+            "glb" => Some(Self::Glb),
+            _ => None,
+        }
+    }
+}
+
+/// Options controlling a mesh export.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ExportOptions {
     /// Tessellation quality.
     pub quality: Quality,
     /// Output format.
-    pub format: StlFormat,
-    /// Thread rendering mode.
-    pub thread_mode: ThreadMode,
+    pub format: MeshFormat,
 }
 
 impl Default for ExportOptions {
     fn default() -> Self {
         Self {
             quality: Quality::Standard,
-            format: StlFormat::Binary,
-            thread_mode: ThreadMode::None,
+            format: MeshFormat::StlBinary,
         }
     }
 }
@@ -76,7 +94,17 @@ mod tests {
     fn export_options_default() {
         let opts = ExportOptions::default();
         assert_eq!(opts.quality, Quality::Standard);
-        assert_eq!(opts.format, StlFormat::Binary);
-        assert_eq!(opts.thread_mode, ThreadMode::None);
+        assert_eq!(opts.format, MeshFormat::StlBinary);
+    }
+
+    #[test]
+    fn mesh_format_from_extension() {
+        let format = |p: &str| MeshFormat::from_path(Path::new(p));
+        assert_eq!(format("part.stl"), Some(MeshFormat::StlBinary));
+        assert_eq!(format("part.OBJ"), Some(MeshFormat::Obj));
+        assert_eq!(format("part.ply"), Some(MeshFormat::Ply));
+        assert_eq!(format("review/part.glb"), Some(MeshFormat::Glb));
+        assert_eq!(format("part.step"), None);
+        assert_eq!(format("part"), None);
     }
 }
@@ -0,0 +1,140 @@
+//! STEP export orchestration.
+//!
+//! Splits the solids into their connected bodies, names the product and
+//! each body, and writes the exact B-rep geometry as STEP AP214.
+
+use std::path::Path;
+
+use covariant_geom::Solid;
+use covariant_geom::kernel::GeomKernel;
+
+use crate::error::{ExportError, ExportErrorKind, ExportResult};
+
+/// Options controlling a STEP export.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StepOptions {
+    /// Product name; defaults to the output file stem.
+    pub product_name: Option<String>,
+    /// Names of the input solids, in order. A lone unnamed solid takes the
+    /// product name; otherwise unnamed solids are called `<product>-<n>`.
+    /// A solid of several disconnected pieces names them `<name>-1`,
+    /// `<name>-2`, ...
+    pub body_names: Vec<String>,
+}
+
+/// Export solids to a STEP AP214 file as the bodies of one product.
+///
+/// A solid made of several disconnected pieces contributes one body per
+/// piece.
+pub fn export_step(
+    kernel: &dyn GeomKernel,
+    solids: &[Solid],
+    path: &Path,
+    options: &StepOptions,
+) -> ExportResult<()> {
+    let product = options
+        .product_name
+        .clone()
+        .or_else(|| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "part".to_string());
+
+    let mut named: Vec<(String, Solid)> = Vec::new();
+    for (i, solid) in solids.iter().enumerate() {
+        let name = match options.body_names.get(i) {
+            Some(name) => name.clone(),
+            None if solids.len() == 1 => product.clone(),
+            None => format!("{product}-{}", i + 1),
+        };
+        let pieces = kernel.bodies(solid)?;
+        if pieces.len() == 1 {
+            named.extend(pieces.into_iter().map(|piece| (name.clone(), piece)));
+        } else {
+            named.extend(
+                pieces
+                    .into_iter()
+                    .enumerate()
+                    .map(|(k, piece)| (format!("{name}-{}", k + 1), piece)),
+            );
+        }
+    }
+    if named.is_empty() {
+        return Err(ExportError::new(
+            ExportErrorKind::ValidationFailed,
+            "nothing to export: no solid bodies",
+        ));
+    }
+
+    kernel.export_step(&product, &named, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use covariant_geom::{TruckKernel, Vector3};
+
+    #[test]
+    fn names_default_to_the_file_stem() {
+        let kernel = TruckKernel;
+        let solid = kernel.box_solid(10.0, 10.0, 10.0);
+        let path = std::env::temp_dir().join("export_test_bracket.step");
+        export_step(&kernel, &[solid], &path, &StepOptions::default())
+            .expect("STEP export should succeed");
+        let content = std::fs::read_to_string(&path).expect("should read file");
+        assert!(content.contains("PRODUCT('export_test_bracket','export_test_bracket',"));
+        assert!(content.contains("MANIFOLD_SOLID_BREP('export_test_bracket',"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn disconnected_pieces_become_numbered_bodies() {
+        let kernel = TruckKernel;
+        let a = kernel.box_solid(1.0, 1.0, 1.0);
+        let b = kernel.translate(&a, Vector3::new(3.0, 0.0, 0.0));
+        let pair = kernel.union(&a, &b).unwrap();
+        let path = std::env::temp_dir().join("export_test_pair.step");
+        let opts = StepOptions {
+            product_name: Some("pair".to_string()),
+            body_names: Vec::new(),
+        };
+        export_step(&kernel, &[pair], &path, &opts).expect("STEP export should succeed");
+        let content = std::fs::read_to_string(&path).expect("should read file");
+        assert!(content.contains("MANIFOLD_SOLID_BREP('pair-1',"));
+        assert!(content.contains("MANIFOLD_SOLID_BREP('pair-2',"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn names_follow_input_solids_when_one_splits() {
+        let kernel = TruckKernel;
+        let a = kernel.box_solid(1.0, 1.0, 1.0);
+        let pair = kernel
+            .union(&a, &kernel.translate(&a, Vector3::new(3.0, 0.0, 0.0)))
+            .unwrap();
+        let single = kernel.translate(&a, Vector3::new(0.0, 5.0, 0.0));
+        let unnamed = kernel.translate(&a, Vector3::new(0.0, 10.0, 0.0));
+        let path = std::env::temp_dir().join("export_test_split.step");
+        let opts = StepOptions {
+            product_name: Some("kit".to_string()),
+            body_names: vec!["left".to_string(), "lid".to_string()],
+        };
+        export_step(&kernel, &[pair, single, unnamed], &path, &opts)
+            .expect("STEP export should succeed");
+        let content = std::fs::read_to_string(&path).expect("should read file");
+        for name in ["left-1", "left-2", "lid", "kit-3"] {
+            assert!(
+                content.contains(&format!("MANIFOLD_SOLID_BREP('{name}',")),
+                "{name}"
+            );
+        }
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn empty_input_is_rejected() {
+        let kernel = TruckKernel;
+        let path = std::env::temp_dir().join("export_test_empty.step");
+        let err = export_step(&kernel, &[], &path, &StepOptions::default()).unwrap_err();
+        assert_eq!(err.kind, ExportErrorKind::ValidationFailed);
+    }
+}
@@ -1,7 +1,7 @@
 //! STL export orchestration.
 //!
-//! Ties together quality control, thread mode resolution, tessellation,
-//! mesh validation, and STL writing into a single entry point.
+//! Ties together quality control, tessellation, mesh validation, and STL
+//! writing into a single entry point.
 
 use std::path::Path;
 
@@ -9,34 +9,47 @@ use covariant_geom::Solid;
 use covariant_geom::kernel::GeomKernel;
 
 use crate::error::{ExportError, ExportErrorKind, ExportResult};
-use crate::quality::{ExportOptions, StlFormat};
-use crate::thread::resolve_thread_mode;
+use crate::mesh::{ModelBody, export_mesh};
+use crate::quality::{ExportOptions, MeshFormat};
 use crate::validate::{MeshWarning, validate_mesh};
 
 /// Export a solid to an STL file with the given options.
 ///
 /// Pipeline:
-/// 1. Resolve thread mode (may emit a warning to stderr).
-/// 2. Tessellate the solid at the requested quality.
-/// 3. Validate the resulting mesh.
-/// 4. Write the STL file in the requested format.
+/// 1. Tessellate the solid at the requested quality.
+/// 2. Validate the resulting mesh.
+/// 3. Write the STL file in the requested format.
+///
+/// The solid's threads are exported as modelled; build it with
+/// [`build_for_export`](crate::thread::build_for_export) to choose how
+/// they are rendered.
+///
+/// Non-STL formats are handed to [`export_mesh`] as a single body named
+/// after the file.
 pub fn export_stl(
     kernel: &dyn GeomKernel,
     solid: &Solid,
     path: &Path,
     options: &ExportOptions,
 ) -> ExportResult<()> {
-    // 1. Resolve thread mode
-    let (_effective_mode, warning) = resolve_thread_mode(options.thread_mode);
-    if let Some(msg) = warning {
-        eprintln!("[export] warning: {msg}");
+    if !matches!(options.format, MeshFormat::StlBinary | MeshFormat::StlAscii) {
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        return export_mesh(
+            kernel,
+            &[ModelBody::new(name, solid.clone())],
+            path,
+            options,
+        );
     }
 
-    // 2. Tessellate
+    // 1. Tessellate
     let tolerance = options.quality.tolerance();
     let mesh = kernel.tessellate(solid, tolerance);
 
-    // 3. Validate
+    // 2. Validate
     let report = validate_mesh(&mesh);
     if report.warnings.contains(&MeshWarning::EmptyMesh) {
         return Err(ExportError::new(
@@ -45,10 +58,10 @@ pub fn export_stl(
         ));
     }
 
-    // 4. Write STL
+    // 3. Write STL
     match options.format {
-        StlFormat::Binary => kernel.export_stl(&mesh, path)?,
-        StlFormat::Ascii => kernel.export_stl_ascii(&mesh, path)?,
+        MeshFormat::StlAscii => kernel.export_stl_ascii(&mesh, path)?,
+        _ => kernel.export_stl(&mesh, path)?,
     }
 
     Ok(())
@@ -80,9 +93,8 @@ mod tests {
         let dir = std::env::temp_dir();
         let path = dir.join("export_test_ascii.stl");
         let opts = ExportOptions {
-            format: StlFormat::Ascii,
+            format: MeshFormat::StlAscii,
             quality: Quality::Draft,
-            ..ExportOptions::default()
         };
         export_stl(&kernel, &solid, &path, &opts).expect("ASCII STL export should succeed");
         let content = std::fs::read_to_string(&path).expect("should read file");
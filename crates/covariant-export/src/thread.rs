@@ -1,8 +1,10 @@
-//! Thread mode resolution for STL export.
+//! Thread mode resolution for export.
 //!
-//! For v0.1, `Full` and `Cosmetic` thread modes fall back to `None`
-//! because helical sweep geometry is not yet supported in the truck
-//! kernel, and cosmetic annotations are meaningless for STL.
+//! Threads are modelled when a part is built, not when it is tessellated,
+//! so the resolved mode decides how the model is built: `Full` threads
+//! are real helical solids from the geometry kernel, while `Cosmetic`
+//! falls back to `None` because no export format carries cosmetic
+//! annotations.
 
 use covariant_thread::ThreadMode;
 
@@ -11,9 +13,20 @@ use covariant_thread::ThreadMode;
 pub enum EffectiveThreadMode {
     /// No thread geometry — plain cylindrical holes.
     None,
+    /// Helical thread geometry with the basic thread profile.
+    Full,
 }
 
-/// Resolve a requested `ThreadMode` into an effective mode for STL export.
+impl From<EffectiveThreadMode> for ThreadMode {
+    fn from(mode: EffectiveThreadMode) -> Self {
+        match mode {
+            EffectiveThreadMode::None => ThreadMode::None,
+            EffectiveThreadMode::Full => ThreadMode::Full,
+        }
+    }
+}
+
+/// Resolve a requested `ThreadMode` into an effective mode for export.
 ///
 /// Returns the effective mode and an optional warning message explaining
 /// any fallback that occurred.
@@ -22,13 +35,27 @@ pub fn resolve_thread_mode(mode: ThreadMode) -> (EffectiveThreadMode, Option<&'s
         ThreadMode::None => (EffectiveThreadMode::None, None),
         ThreadMode::Cosmetic => (
             EffectiveThreadMode::None,
-            Some("cosmetic thread annotations are not supported in STL; falling back to None"),
-        ),
-        ThreadMode::Full => (
-            EffectiveThreadMode::None,
-            Some("full helical thread geometry is not yet implemented; falling back to None"),
+            Some("cosmetic thread annotations cannot be exported; falling back to None"),
         ),
+        ThreadMode::Full => (EffectiveThreadMode::Full, None),
+    }
+}
+
+/// Build a model for export in the thread mode `mode` resolves to,
+/// printing any fallback warning to stderr.
+///
+/// Exporters take finished solids, so callers build them through here:
+/// `build` is called with `ThreadMode::Full` for full threads and with
+/// `ThreadMode::None` for plain or cosmetic ones.
+pub fn build_for_export<T, E>(
+    mode: ThreadMode,
+    build: impl FnOnce(ThreadMode) -> Result<T, E>,
+) -> Result<T, E> {
+    let (mode, warning) = resolve_thread_mode(mode);
+    if let Some(msg) = warning {
+        eprintln!("[export] warning: {msg}");
     }
+    build(mode.into())
 }
 
 #[cfg(test)]
@@ -47,14 +74,15 @@ mod tests {
         let (mode, warning) = resolve_thread_mode(ThreadMode::Cosmetic);
         assert_eq!(mode, EffectiveThreadMode::None);
         assert!(warning.is_some());
-        assert!(warning.unwrap().contains("cosmetic"));
+        let warning = warning.unwrap();
+        assert!(warning.contains("cosmetic"));
+        assert!(!warning.contains("STL"));
     }
 
     #[test]
-    fn full_falls_back_with_warning() {
+    fn full_mode_no_warning() {
         let (mode, warning) = resolve_thread_mode(ThreadMode::Full);
-        assert_eq!(mode, EffectiveThreadMode::None);
-        assert!(warning.is_some());
-        assert!(warning.unwrap().contains("helical"));
+        assert_eq!(mode, EffectiveThreadMode::Full);
+        assert!(warning.is_none());
     }
 }
@@ -0,0 +1,355 @@
+//! 3MF export.
+//!
+//! Writes the OPC zip container with the core 3D model part: millimetre
+//! units, one mesh object per named body with an optional base material
+//! (name and display colour), and model metadata. Well-known metadata
+//! names (`Title`, `Designer`, ...) are written as is; any other name is
+//! placed in the `covariant` namespace so consumers can tell them apart.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::Path;
+
+use covariant_geom::Mesh;
+use covariant_geom::kernel::GeomKernel;
+use flate2::Compression;
+use flate2::write::DeflateEncoder;
+
+use crate::error::ExportResult;
+use crate::mesh::{Color, ModelBody, tessellate_bodies, weld, write_file};
+use crate::quality::ExportOptions;
+
+const CORE_NAMESPACE: &str = "http://schemas.microsoft.com/3dmanufacturing/core/2015/02";
+const METADATA_NAMESPACE: &str = "urn:covariant:3mf:metadata";
+const MODEL_PATH: &str = "3D/3dmodel.model";
+
+/// Metadata names defined by the 3MF core specification.
+const WELL_KNOWN_METADATA: [&str; 9] = [
+    "Title",
+    "Designer",
+    "Description",
+    "Copyright",
+    "LicenseTerms",
+    "Rating",
+    "CreationDate",
+    "ModificationDate",
+    "Application",
+];
+
+/// Export bodies to a 3MF file, one object per body, in millimetres.
+///
+/// `metadata` is written as model metadata after a default `Title` (the
+/// file stem) and `Application`, which entries of the same name replace.
+pub fn export_3mf(
+    kernel: &dyn GeomKernel,
+    bodies: &[ModelBody],
+    metadata: &[(String, String)],
+    path: &Path,
+    options: &ExportOptions,
+) -> ExportResult<()> {
+    let meshes = tessellate_bodies(kernel, bodies, options)?;
+    let title = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut entries = vec![
+        ("Title".to_string(), title),
+        ("Application".to_string(), "covariant".to_string()),
+    ];
+    for (name, value) in metadata {
+        match entries.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = value.clone(),
+            None => entries.push((name.clone(), value.clone())),
+        }
+    }
+
+    let model = model_xml(&meshes, &entries);
+    let package = zip(&[
+        ("[Content_Types].xml", CONTENT_TYPES.as_bytes()),
+        ("_rels/.rels", RELATIONSHIPS.as_bytes()),
+        (MODEL_PATH, model.as_bytes()),
+    ]);
+    write_file(path, &package)
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+fn model_xml(bodies: &[(&ModelBody, Mesh)], metadata: &[(String, String)]) -> String {
+    let mut xml = String::new();
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        xml,
+        r#"<model unit="millimeter" xml:lang="en-US" xmlns="{CORE_NAMESPACE}" xmlns:covariant="{METADATA_NAMESPACE}">"#
+    );
+    for (name, value) in metadata {
+        let name = if WELL_KNOWN_METADATA.contains(&name.as_str()) {
+            name.clone()
+        } else {
+            format!("covariant:{}", metadata_name(name))
+        };
+        let _ = writeln!(
+            xml,
+            r#"  <metadata name="{}">{}</metadata>"#,
+            escape(&name),
+            escape(value)
+        );
+    }
+    xml.push_str("  <resources>\n");
+
+    // Object ids start after the material group, which always takes id 1.
+    let materials: Vec<Option<(String, Color)>> = bodies
+        .iter()
+        .map(|(body, _)| match (&body.material, body.color) {
+            (None, None) => None,
+            (material, color) => Some((
+                material.clone().unwrap_or_else(|| body.name.clone()),
+                color.unwrap_or(Color::GREY),
+            )),
+        })
+        .collect();
+    if materials.iter().any(Option::is_some) {
+        xml.push_str("    <basematerials id=\"1\">\n");
+        for (name, color) in materials.iter().flatten() {
+            let _ = writeln!(
+                xml,
+                r#"      <base name="{}" displaycolor="{}"/>"#,
+                escape(name),
+                color.hex()
+            );
+        }
+        xml.push_str("    </basematerials>\n");
+    }
+
+    let mut pindex = 0;
+    for (i, ((body, mesh), material)) in bodies.iter().zip(&materials).enumerate() {
+        let material_ref = match material {
+            Some(_) => {
+                pindex += 1;
+                format!(r#" pid="1" pindex="{}""#, pindex - 1)
+            }
+            None => String::new(),
+        };
+        let _ = writeln!(
+            xml,
+            r#"    <object id="{}" type="model" name="{}"{material_ref}>"#,
+            i + 2,
+            escape(&body.name)
+        );
+        write_mesh(&mut xml, &mesh.positions(), &mesh.tri_faces());
+        xml.push_str("    </object>\n");
+    }
+    xml.push_str("  </resources>\n  <build>\n");
+    for i in 0..bodies.len() {
+        let _ = writeln!(xml, r#"    <item objectid="{}"/>"#, i + 2);
+    }
+    xml.push_str("  </build>\n</model>\n");
+    xml
+}
+
+/// Write a mesh, sharing the vertices of neighbouring triangles.
+fn write_mesh(xml: &mut String, positions: &[[f64; 3]], triangles: &[[usize; 3]]) {
+    let (positions, triangles) = weld(positions, triangles);
+    xml.push_str("      <mesh>\n        <vertices>\n");
+    for [x, y, z] in &positions {
+        let _ = writeln!(xml, r#"          <vertex x="{x}" y="{y}" z="{z}"/>"#);
+    }
+    xml.push_str("        </vertices>\n        <triangles>\n");
+    for [v1, v2, v3] in &triangles {
+        let _ = writeln!(
+            xml,
+            r#"          <triangle v1="{v1}" v2="{v2}" v3="{v3}"/>"#
+        );
+    }
+    xml.push_str("        </triangles>\n      </mesh>\n");
+}
+
+/// Reduce a metadata name to an XML name: invalid characters become `_`.
+fn metadata_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !out.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// ── Zip container ───────────────────────────────────────────────────────
+
+/// Build a zip archive of deflated entries. Timestamps are fixed at the
+/// DOS epoch so identical models produce identical files.
+fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    const DOS_DATE: u16 = (1 << 5) | 1; // 1980-01-01
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for (name, data) in entries {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(data)
+            .expect("writing to a Vec cannot fail");
+        let compressed = encoder.finish().expect("writing to a Vec cannot fail");
+        let mut crc = flate2::Crc::new();
+        crc.update(data);
+
+        let offset = out.len() as u32;
+        // Fields shared by the local and central headers, from "version
+        // needed" through the extra field length.
+        let mut common = Vec::new();
+        common.extend(20u16.to_le_bytes()); // version needed: 2.0
+        common.extend(0u16.to_le_bytes()); // flags
+        common.extend(8u16.to_le_bytes()); // method: deflate
+        common.extend(0u16.to_le_bytes()); // time
+        common.extend(DOS_DATE.to_le_bytes());
+        common.extend(crc.sum().to_le_bytes());
+        common.extend((compressed.len() as u32).to_le_bytes());
+        common.extend((data.len() as u32).to_le_bytes());
+        common.extend((name.len() as u16).to_le_bytes());
+        common.extend(0u16.to_le_bytes()); // extra field length
+
+        out.extend(0x0403_4b50u32.to_le_bytes());
+        out.extend(&common);
+        out.extend(name.as_bytes());
+        out.extend(&compressed);
+
+        central.extend(0x0201_4b50u32.to_le_bytes());
+        central.extend(20u16.to_le_bytes()); // version made by
+        central.extend(&common);
+        central.extend(0u16.to_le_bytes()); // comment length
+        central.extend(0u16.to_le_bytes()); // disk number
+        central.extend(0u16.to_le_bytes()); // internal attributes
+        central.extend(0u32.to_le_bytes()); // external attributes
+        central.extend(offset.to_le_bytes());
+        central.extend(name.as_bytes());
+    }
+    let central_offset = out.len() as u32;
+    out.extend(&central);
+    out.extend(0x0605_4b50u32.to_le_bytes());
+    out.extend(0u16.to_le_bytes()); // this disk
+    out.extend(0u16.to_le_bytes()); // central directory disk
+    out.extend((entries.len() as u16).to_le_bytes());
+    out.extend((entries.len() as u16).to_le_bytes());
+    out.extend((central.len() as u32).to_le_bytes());
+    out.extend(central_offset.to_le_bytes());
+    out.extend(0u16.to_le_bytes()); // comment length
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use covariant_geom::TruckKernel;
+    use std::io::Read;
+
+    /// Read the entries of a zip written by [`zip`].
+    fn unzip(bytes: &[u8]) -> Vec<(String, String)> {
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]) as usize;
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        let mut entries = Vec::new();
+        let mut at = 0;
+        while u32_at(at) == 0x0403_4b50 {
+            let (crc, size) = (u32_at(at + 14), u32_at(at + 18) as usize);
+            let name_len = u16_at(at + 26);
+            let name = String::from_utf8(bytes[at + 30..at + 30 + name_len].to_vec()).unwrap();
+            let start = at + 30 + name_len;
+            let mut text = String::new();
+            flate2::read::DeflateDecoder::new(&bytes[start..start + size])
+                .read_to_string(&mut text)
+                .unwrap();
+            let mut check = flate2::Crc::new();
+            check.update(text.as_bytes());
+            assert_eq!(check.sum(), crc, "{name}");
+            entries.push((name, text));
+            at = start + size;
+        }
+        assert_eq!(u32_at(at), 0x0201_4b50);
+        entries
+    }
+
+    #[test]
+    fn writes_package_with_objects_materials_and_metadata() {
+        let kernel = TruckKernel;
+        let mut base = ModelBody::new("base <plate>", kernel.box_solid(20.0, 20.0, 2.0));
+        base.color = Color::parse("#FF0000");
+        base.material = Some("PLA".to_string());
+        let peg = ModelBody::new("peg", kernel.cylinder(3.0, 10.0));
+        let metadata = vec![
+            ("Designer".to_string(), "R&D".to_string()),
+            ("width".to_string(), "20mm".to_string()),
+        ];
+        let path = std::env::temp_dir().join("export_test_plate.3mf");
+        export_3mf(
+            &kernel,
+            &[base, peg],
+            &metadata,
+            &path,
+            &ExportOptions::default(),
+        )
+        .expect("3MF export should succeed");
+        let bytes = std::fs::read(&path).expect("file should exist");
+        std::fs::remove_file(&path).ok();
+
+        let entries = unzip(&bytes);
+        let names: Vec<_> = entries.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["[Content_Types].xml", "_rels/.rels", MODEL_PATH]);
+        let model = &entries[2].1;
+        assert!(model.contains(r#"unit="millimeter""#));
+        assert!(model.contains(r#"<metadata name="Title">export_test_plate</metadata>"#));
+        assert!(model.contains(r#"<metadata name="Designer">R&amp;D</metadata>"#));
+        assert!(model.contains(r#"<metadata name="covariant:width">20mm</metadata>"#));
+        assert!(model.contains(r##"<base name="PLA" displaycolor="#FF0000FF"/>"##));
+        assert!(model.contains(
+            r#"<object id="2" type="model" name="base &lt;plate&gt;" pid="1" pindex="0">"#
+        ));
+        assert!(model.contains(r#"<object id="3" type="model" name="peg">"#));
+        assert!(model.contains(r#"<item objectid="3"/>"#));
+    }
+
+    #[test]
+    fn box_mesh_is_welded() {
+        let kernel = TruckKernel;
+        let mesh = kernel.tessellate(&kernel.box_solid(1.0, 1.0, 1.0), 0.1);
+        let mut xml = String::new();
+        write_mesh(&mut xml, &mesh.positions(), &mesh.tri_faces());
+        assert_eq!(xml.matches("<vertex ").count(), 8);
+        assert_eq!(xml.matches("<triangle ").count(), 12);
+    }
+
+    #[test]
+    fn metadata_names_become_xml_names() {
+        assert_eq!(metadata_name("hole count"), "hole_count");
+        assert_eq!(metadata_name("3d"), "_3d");
+    }
+}
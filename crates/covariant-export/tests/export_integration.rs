@@ -1,8 +1,12 @@
 //! Integration tests for the covariant-export crate.
 
-use covariant_export::{ExportOptions, Quality, StlFormat, export_stl, validate_mesh};
+use covariant_export::{
+    ExportOptions, MeshFormat, ModelBody, Quality, StepOptions, build_for_export, export_mesh,
+    export_step, export_stl, validate_mesh,
+};
 use covariant_geom::TruckKernel;
 use covariant_geom::kernel::GeomKernel;
+use covariant_thread::{HelixParams, ThreadMode};
 
 #[test]
 fn binary_stl_export_creates_nonempty_file() {
@@ -24,7 +28,7 @@ fn ascii_stl_export_starts_with_solid() {
     let dir = std::env::temp_dir();
     let path = dir.join("integ_ascii.stl");
     let opts = ExportOptions {
-        format: StlFormat::Ascii,
+        format: MeshFormat::StlAscii,
         ..ExportOptions::default()
     };
     export_stl(&kernel, &solid, &path, &opts).expect("ASCII export should succeed");
@@ -71,3 +75,86 @@ fn sphere_export_and_validation_no_warnings() {
     assert!(meta.len() > 0);
     std::fs::remove_file(&path).ok();
 }
+
+#[test]
+fn step_export_keeps_exact_surfaces() {
+    let kernel = TruckKernel;
+    let solid = kernel.cylinder(5.0, 10.0);
+    let dir = std::env::temp_dir();
+    let path = dir.join("integ_cylinder.step");
+    export_step(&kernel, &[solid], &path, &StepOptions::default())
+        .expect("STEP export should succeed");
+    let content = std::fs::read_to_string(&path).expect("should read file");
+    assert!(content.contains("FILE_SCHEMA(('AUTOMOTIVE_DESIGN"));
+    assert!(content.contains("SI_UNIT(.MILLI.,.METRE.)"));
+    assert!(
+        !content.contains("TRIANGULATED"),
+        "STEP export must not tessellate"
+    );
+    assert!(content.contains("SURFACE_OF_REVOLUTION") || content.contains("B_SPLINE_SURFACE"));
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn mesh_format_follows_the_extension() {
+    let kernel = TruckKernel;
+    let bodies = [
+        ModelBody::new("base", kernel.box_solid(20.0, 20.0, 2.0)),
+        ModelBody::new("knob", kernel.cylinder(3.0, 8.0)),
+    ];
+    let dir = std::env::temp_dir();
+
+    let path = dir.join("integ_review.obj");
+    let opts = ExportOptions {
+        format: MeshFormat::from_path(&path).expect("obj is a mesh format"),
+        ..ExportOptions::default()
+    };
+    export_mesh(&kernel, &bodies, &path, &opts).expect("OBJ export should succeed");
+    let content = std::fs::read_to_string(&path).expect("should read file");
+    assert!(content.contains("\no base\n") && content.contains("\no knob\n"));
+    assert!(content.lines().any(|l| l.starts_with("vn ")));
+    std::fs::remove_file(&path).ok();
+
+    let path = dir.join("integ_review.glb");
+    let opts = ExportOptions {
+        format: MeshFormat::from_path(&path).expect("glb is a mesh format"),
+        ..ExportOptions::default()
+    };
+    export_mesh(&kernel, &bodies, &path, &opts).expect("glTF export should succeed");
+    let bytes = std::fs::read(&path).expect("should read file");
+    assert!(bytes.starts_with(b"glTF"));
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(text.contains(r#"{"name":"base","mesh":0}"#));
+    assert!(text.contains(r#"{"name":"knob","mesh":1}"#));
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn full_thread_mode_exports_helical_threads() {
+    let kernel = TruckKernel;
+    // M6 × 1 rod, 5mm long.
+    let helix = HelixParams {
+        major_diameter: 6.0,
+        minor_diameter: 4.917,
+        pitch: 1.0,
+        depth: 5.0,
+        left_hand: false,
+    };
+    let triangles = |thread_mode: ThreadMode| {
+        let opts = ExportOptions::default();
+        let solid = build_for_export(thread_mode, |mode| match mode {
+            ThreadMode::Full => kernel.thread(&helix),
+            _ => Ok(kernel.cylinder(3.0, 5.0)),
+        })
+        .expect("rod should build");
+        let path = std::env::temp_dir().join(format!("integ_threads_{thread_mode:?}.stl"));
+        export_stl(&kernel, &solid, &path, &opts).expect("export should succeed");
+        let bytes = std::fs::read(&path).expect("should read file");
+        std::fs::remove_file(&path).ok();
+        u32::from_le_bytes(bytes[80..84].try_into().unwrap())
+    };
+    let plain = triangles(ThreadMode::None);
+    assert_eq!(triangles(ThreadMode::Cosmetic), plain);
+    let full = triangles(ThreadMode::Full);
+    assert!(full > plain, "full {full}, plain {plain}");
+}
@@ -0,0 +1,1065 @@
+//! Edge blends: fillets and chamfers.
+//!
+//! Blends apply to straight edges between two planar faces. The solid is
+//! rebuilt face by face: each selected edge is replaced by a blend face (a
+//! circular cylinder patch for a fillet, a plane for a chamfer) that meets
+//! the two adjacent faces along setback lines parallel to the edge, and
+//! every face around an affected vertex is re-trimmed.
+//!
+//! A vertex where a blend ends must join exactly three planar faces. A
+//! single blended edge runs out on the third face, two blended edges meet
+//! in a miter, and three are closed off by a corner patch (a sphere patch
+//! for fillets, a triangle for chamfers).
+
+use std::collections::HashMap;
+
+use truck_modeling::{
+    builder, BSplineCurve, BSplineSurface, BoundedCurve, Curve, EdgeID, EuclideanSpace,
+    InnerSpace, KnotVec, NurbsCurve, NurbsSurface, ParametricCurve,
+    ParametricSurface3D, Plane, Point3, SearchParameter, Shell, Surface, Vector3, Vector4,
+    VertexID, Wire,
+};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::{TruckEdge, TruckFace, TruckSolid, TruckVertex};
+
+/// Distance below which two computed points are considered coincident.
+const POINT_TOLERANCE: f64 = 1e-6;
+
+/// Directions whose unit vectors have a dot product above `1 - this` are
+/// treated as parallel (also used to detect smooth edges).
+const DIRECTION_TOLERANCE: f64 = 1e-6;
+
+/// Half-span (radians) in latitude and longitude of the sphere patch that
+/// carries a fillet corner.
+const CORNER_HALF_SPAN: f64 = 1.4;
+
+/// Which edges of a solid a fillet or chamfer applies to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeSelector {
+    /// Every sharp edge of the solid.
+    All,
+    /// Straight edges parallel to a direction (either sense).
+    ParallelTo(Vector3),
+    /// Edges bounding the planar faces whose outward normal points along a
+    /// direction.
+    OnFace(Vector3),
+}
+
+/// Round the selected edges with the given radius.
+pub fn solid_fillet(
+    solid: &TruckSolid,
+    radius: f64,
+    edges: &EdgeSelector,
+) -> GeomResult<TruckSolid> {
+    if radius <= 0.0 {
+        return Err(invalid(format!("fillet radius must be positive, got {radius}")));
+    }
+    blend_solid(solid, BlendKind::Fillet(radius), edges)
+}
+
+/// Bevel the selected edges, cutting back `distance` along both faces.
+pub fn solid_chamfer(
+    solid: &TruckSolid,
+    distance: f64,
+    edges: &EdgeSelector,
+) -> GeomResult<TruckSolid> {
+    if distance <= 0.0 {
+        return Err(invalid(format!(
+            "chamfer distance must be positive, got {distance}"
+        )));
+    }
+    blend_solid(solid, BlendKind::Chamfer(distance), edges)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BlendKind {
+    Fillet(f64),
+    Chamfer(f64),
+}
+
+impl BlendKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Fillet(_) => "fillet",
+            Self::Chamfer(_) => "chamfer",
+        }
+    }
+}
+
+fn blend_solid(
+    solid: &TruckSolid,
+    kind: BlendKind,
+    selector: &EdgeSelector,
+) -> GeomResult<TruckSolid> {
+    let mut selected_any = false;
+    let shells = solid
+        .boundaries()
+        .iter()
+        .map(|shell| {
+            let topology = Topology::new(shell);
+            let selected = topology.select(selector)?;
+            if selected.is_empty() {
+                return Ok(shell.clone());
+            }
+            selected_any = true;
+            Rebuild::new(&topology, &selected, kind)?.shell()
+        })
+        .collect::<GeomResult<Vec<_>>>()?;
+    if !selected_any {
+        return Err(invalid(format!("no edges match the {} selection", kind.name())));
+    }
+    TruckSolid::try_new(shells)
+        .map_err(|e| invalid(format!("{} produced an invalid solid: {e}", kind.name())))
+}
+
+// ── Shell topology ──────────────────────────────────────────────────────
+
+/// A face of the shell with its outward-oriented boundary wires.
+struct FaceData {
+    face: TruckFace,
+    wires: Vec<Wire>,
+    /// Outward unit normal and a point on the plane, for planar faces.
+    plane: Option<(Point3, Vector3)>,
+}
+
+/// An edge of the shell and the faces that use it.
+struct EdgeData {
+    /// The edge in its absolute orientation.
+    edge: TruckEdge,
+    /// Faces along the edge; `true` where the face runs along the edge's
+    /// absolute direction.
+    faces: Vec<(usize, bool)>,
+}
+
+/// Adjacency of the faces, edges and vertices of one shell.
+struct Topology {
+    faces: Vec<FaceData>,
+    edges: HashMap<EdgeID, EdgeData>,
+    /// Edges in first-seen order, for deterministic selection.
+    edge_order: Vec<EdgeID>,
+    /// For each face and vertex, the face's boundary edges entering and
+    /// leaving the vertex. `None` if the face passes the vertex twice.
+    corners: HashMap<(usize, VertexID), Option<(EdgeID, EdgeID)>>,
+    vertex_faces: HashMap<VertexID, Vec<usize>>,
+    vertex_edges: HashMap<VertexID, Vec<EdgeID>>,
+}
+
+impl Topology {
+    fn new(shell: &Shell) -> Self {
+        let mut topology = Self {
+            faces: Vec::new(),
+            edges: HashMap::new(),
+            edge_order: Vec::new(),
+            corners: HashMap::new(),
+            vertex_faces: HashMap::new(),
+            vertex_edges: HashMap::new(),
+        };
+        for (index, face) in shell.face_iter().enumerate() {
+            let wires = face.boundaries();
+            for wire in &wires {
+                let edges: Vec<_> = wire.edge_iter().collect();
+                for (i, edge) in edges.iter().enumerate() {
+                    let next = edges[(i + 1) % edges.len()];
+                    topology.add_edge(edge, index);
+                    let vertex = edge.back().id();
+                    topology
+                        .corners
+                        .entry((index, vertex))
+                        .and_modify(|c| *c = None)
+                        .or_insert(Some((edge.id(), next.id())));
+                    let faces = topology.vertex_faces.entry(vertex).or_default();
+                    if !faces.contains(&index) {
+                        faces.push(index);
+                    }
+                }
+            }
+            let plane = match face.oriented_surface() {
+                Surface::Plane(plane) => Some((plane.origin(), plane.normal())),
+                _ => None,
+            };
+            topology.faces.push(FaceData {
+                face: face.clone(),
+                wires,
+                plane,
+            });
+        }
+        topology
+    }
+
+    fn add_edge(&mut self, edge: &TruckEdge, face: usize) {
+        let id = edge.id();
+        if !self.edges.contains_key(&id) {
+            self.edge_order.push(id);
+            for vertex in [edge.front().id(), edge.back().id()] {
+                let edges = self.vertex_edges.entry(vertex).or_default();
+                if !edges.contains(&id) {
+                    edges.push(id);
+                }
+            }
+        }
+        self.edges
+            .entry(id)
+            .or_insert_with(|| EdgeData {
+                edge: edge.absolute_clone(),
+                faces: Vec::new(),
+            })
+            .faces
+            .push((face, edge.orientation()));
+    }
+
+    /// The edges picked by `selector`, skipping smooth edges.
+    fn select(&self, selector: &EdgeSelector) -> GeomResult<Vec<EdgeID>> {
+        let picked: Vec<EdgeID> = match *selector {
+            EdgeSelector::All => self.edge_order.clone(),
+            EdgeSelector::ParallelTo(direction) => {
+                let direction = unit(direction, "edge direction")?;
+                self.edge_order
+                    .iter()
+                    .copied()
+                    .filter(|id| {
+                        self.line_direction(&self.edges[id].edge)
+                            .is_some_and(|d| d.dot(direction).abs() > 1.0 - DIRECTION_TOLERANCE)
+                    })
+                    .collect()
+            }
+            EdgeSelector::OnFace(normal) => {
+                let normal = unit(normal, "face normal")?;
+                let faces: Vec<usize> = (0..self.faces.len())
+                    .filter(|&i| {
+                        self.faces[i]
+                            .plane
+                            .is_some_and(|(_, n)| n.dot(normal) > 1.0 - DIRECTION_TOLERANCE)
+                    })
+                    .collect();
+                self.edge_order
+                    .iter()
+                    .copied()
+                    .filter(|id| self.edges[id].faces.iter().any(|(f, _)| faces.contains(f)))
+                    .collect()
+            }
+        };
+        Ok(picked.into_iter().filter(|id| !self.is_smooth(id)).collect())
+    }
+
+    /// Unit direction of a straight edge, or `None` for curved edges.
+    fn line_direction(&self, edge: &TruckEdge) -> Option<Vector3> {
+        let (p0, p1) = (edge.front().point(), edge.back().point());
+        let chord = p1 - p0;
+        if chord.magnitude() < POINT_TOLERANCE {
+            return None;
+        }
+        let direction = chord.normalize();
+        let curve = edge.curve();
+        let (t0, t1) = curve.range_tuple();
+        let mid = curve.subs((t0 + t1) / 2.0) - p0;
+        let off_line = mid - direction * mid.dot(direction);
+        (off_line.magnitude() < POINT_TOLERANCE).then_some(direction)
+    }
+
+    /// Whether the two faces along an edge meet tangentially (e.g. the
+    /// seams between the patches of a cylinder).
+    fn is_smooth(&self, id: &EdgeID) -> bool {
+        let data = &self.edges[id];
+        let [(f0, _), (f1, _)] = data.faces[..] else {
+            return false;
+        };
+        let curve = data.edge.curve();
+        let (t0, t1) = curve.range_tuple();
+        let mid = curve.subs((t0 + t1) / 2.0);
+        match (self.normal_at(f0, mid), self.normal_at(f1, mid)) {
+            (Some(n0), Some(n1)) => n0.dot(n1) > 1.0 - DIRECTION_TOLERANCE,
+            _ => false,
+        }
+    }
+
+    /// Outward unit normal of a face at a point on it.
+    fn normal_at(&self, face: usize, point: Point3) -> Option<Vector3> {
+        let data = &self.faces[face];
+        if let Some((_, normal)) = data.plane {
+            return Some(normal);
+        }
+        let surface = data.face.oriented_surface();
+        let (u, v) = surface.search_parameter(point, None, 100)?;
+        Some(surface.normal(u, v))
+    }
+
+    /// The outward plane of a face, or an error naming what needs it.
+    fn plane(&self, face: usize, what: &str) -> GeomResult<(Point3, Vector3)> {
+        self.faces[face]
+            .plane
+            .ok_or_else(|| invalid(format!("{what} must be planar")))
+    }
+}
+
+// ── Blend geometry ──────────────────────────────────────────────────────
+
+/// Cross-section geometry of one blended edge.
+struct Blend {
+    /// The two faces along the edge; the first runs along its absolute
+    /// direction.
+    faces: [usize; 2],
+    /// Absolute front and back vertices of the edge.
+    ends: [VertexID; 2],
+    /// A point on the edge (its front vertex).
+    origin: Point3,
+    /// Unit edge direction (absolute).
+    dir: Vector3,
+    /// Unit vectors perpendicular to the edge, pointing into each face.
+    inward: [Vector3; 2],
+    /// Outward unit normals of the two faces.
+    normals: [Vector3; 2],
+    /// Distance from the edge to the setback line on each face.
+    setback: f64,
+    /// Weight of the middle control point of the fillet arc; `None` for
+    /// chamfers.
+    weight: Option<f64>,
+    /// Whether material is removed (convex edge) rather than added.
+    convex: bool,
+}
+
+impl Blend {
+    fn new(topology: &Topology, id: EdgeID, kind: BlendKind) -> GeomResult<Self> {
+        let data = &topology.edges[&id];
+        let name = kind.name();
+        let dir = topology.line_direction(&data.edge).ok_or_else(|| {
+            invalid(format!(
+                "{name} supports only straight edges between planar faces, \
+                 but the edge at {} is curved",
+                fmt_point(data.edge.front().point())
+            ))
+        })?;
+        let faces = match data.faces[..] {
+            [(f0, true), (f1, false)] => [f0, f1],
+            [(f0, false), (f1, true)] => [f1, f0],
+            _ => {
+                return Err(invalid(format!(
+                    "{name}: the edge at {} is not shared by exactly two faces",
+                    fmt_point(data.edge.front().point())
+                )));
+            }
+        };
+        let what = format!("faces along a {name}ed edge");
+        let n0 = topology.plane(faces[0], &what)?.1;
+        let n1 = topology.plane(faces[1], &what)?.1;
+        let inward = [n0.cross(dir), dir.cross(n1)];
+        let opening = inward[0].dot(inward[1]).clamp(-1.0, 1.0).acos();
+        if !(1e-6..=std::f64::consts::PI - 1e-6).contains(&opening) {
+            return Err(invalid(format!(
+                "{name}: the faces at {} meet tangentially or fold back",
+                fmt_point(data.edge.front().point())
+            )));
+        }
+        let (setback, weight) = match kind {
+            BlendKind::Fillet(radius) => {
+                (radius / (opening / 2.0).tan(), Some((opening / 2.0).sin()))
+            }
+            BlendKind::Chamfer(distance) => (distance, None),
+        };
+        Ok(Self {
+            faces,
+            ends: [data.edge.front().id(), data.edge.back().id()],
+            origin: data.edge.front().point(),
+            dir,
+            inward,
+            normals: [n0, n1],
+            setback,
+            weight,
+            convex: inward[0].dot(n1) < 0.0,
+        })
+    }
+
+    /// Which of the two faces `face` is.
+    fn side(&self, face: usize) -> usize {
+        usize::from(self.faces[1] == face && self.faces[0] != face)
+    }
+
+    /// A point on the setback line in face `side`, and the line direction.
+    fn setback_line(&self, side: usize) -> (Point3, Vector3) {
+        (self.origin + self.inward[side] * self.setback, self.dir)
+    }
+
+    /// Control points of the cross-section at the edge's origin: the two
+    /// setback points with the edge point between them.
+    fn profile(&self) -> [Point3; 3] {
+        [
+            self.origin + self.inward[0] * self.setback,
+            self.origin,
+            self.origin + self.inward[1] * self.setback,
+        ]
+    }
+
+    /// The fillet arc, projected along the edge onto a plane.
+    fn arc_on_plane(&self, point: Point3, normal: Vector3, weight: f64) -> GeomResult<Curve> {
+        let controls = self
+            .profile()
+            .iter()
+            .map(|&p| line_plane(p, self.dir, point, normal))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| invalid("fillet end plane is parallel to the edge".to_string()))?;
+        let weighted = controls
+            .iter()
+            .zip([1.0, weight, 1.0])
+            .map(|(&p, w)| homogeneous(p, w))
+            .collect();
+        Ok(Curve::NurbsCurve(NurbsCurve::new(BSplineCurve::new(
+            KnotVec::bezier_knot(2),
+            weighted,
+        ))))
+    }
+
+    /// The blend surface, covering the edge direction from `t_min` to
+    /// `t_max` (measured from the origin).
+    fn surface(&self, t_min: f64, t_max: f64) -> Surface {
+        let [p0, p1, p2] = self.profile();
+        match self.weight {
+            Some(weight) => {
+                let rows = [(p0, 1.0), (p1, weight), (p2, 1.0)]
+                    .iter()
+                    .map(|&(p, w)| {
+                        [t_min, t_max]
+                            .iter()
+                            .map(|&t| homogeneous(p + self.dir * t, w))
+                            .collect()
+                    })
+                    .collect();
+                Surface::NurbsSurface(NurbsSurface::new(BSplineSurface::new(
+                    (KnotVec::bezier_knot(2), KnotVec::bezier_knot(1)),
+                    rows,
+                )))
+            }
+            None => Surface::Plane(Plane::new(p0, p0 + self.dir, p2)),
+        }
+    }
+}
+
+// ── Rebuilding the shell ────────────────────────────────────────────────
+
+struct Rebuild<'a> {
+    topology: &'a Topology,
+    kind: BlendKind,
+    blends: HashMap<EdgeID, Blend>,
+    order: Vec<EdgeID>,
+    /// End vertex of each setback line, keyed by (edge, face, original vertex).
+    setback_ends: HashMap<(EdgeID, usize, VertexID), TruckVertex>,
+    /// New end vertex of an unselected edge cut back at a blended vertex.
+    cut_ends: HashMap<(EdgeID, VertexID), TruckVertex>,
+    /// Edges created between new vertices (blend ends, miters, corners),
+    /// keyed by their end vertices in both orders.
+    links: HashMap<(VertexID, VertexID), TruckEdge>,
+    /// Setback lines, keyed by (selected edge, face).
+    setback_edges: HashMap<(EdgeID, usize), TruckEdge>,
+    /// Unselected edges with at least one end cut back.
+    shortened: HashMap<EdgeID, TruckEdge>,
+    /// Corner patches closing three blends at a vertex.
+    corners: Vec<TruckFace>,
+}
+
+impl<'a> Rebuild<'a> {
+    fn new(topology: &'a Topology, selected: &[EdgeID], kind: BlendKind) -> GeomResult<Self> {
+        let mut blends = HashMap::new();
+        for &id in selected {
+            blends.insert(id, Blend::new(topology, id, kind)?);
+        }
+        let mut rebuild = Self {
+            topology,
+            kind,
+            blends,
+            order: selected.to_vec(),
+            setback_ends: HashMap::new(),
+            cut_ends: HashMap::new(),
+            links: HashMap::new(),
+            setback_edges: HashMap::new(),
+            shortened: HashMap::new(),
+            corners: Vec::new(),
+        };
+        let mut vertices: Vec<VertexID> = Vec::new();
+        for id in &rebuild.order {
+            for vertex in rebuild.blends[id].ends {
+                if !vertices.contains(&vertex) {
+                    vertices.push(vertex);
+                }
+            }
+        }
+        for vertex in vertices {
+            rebuild.blend_vertex(vertex)?;
+        }
+        rebuild.build_setback_edges()?;
+        rebuild.build_shortened_edges()?;
+        Ok(rebuild)
+    }
+
+    /// Place the new vertices and end curves around one blended vertex.
+    fn blend_vertex(&mut self, vertex: VertexID) -> GeomResult<()> {
+        let topology = self.topology;
+        let name = self.kind.name();
+        let faces = &topology.vertex_faces[&vertex];
+        let edges = &topology.vertex_edges[&vertex];
+        let at = self.vertex_point(vertex);
+        let regular = faces.len() == 3
+            && edges.len() == 3
+            && faces
+                .iter()
+                .all(|&f| matches!(topology.corners.get(&(f, vertex)), Some(Some(_))));
+        if !regular {
+            return Err(invalid(format!(
+                "{name}ed edges must end at vertices joining three faces, \
+                 but {} joins {}",
+                fmt_point(at),
+                faces.len()
+            )));
+        }
+        let what = format!("faces at the end of a {name}ed edge");
+        let planes = faces
+            .iter()
+            .map(|&f| topology.plane(f, &what))
+            .collect::<GeomResult<Vec<_>>>()?;
+
+        let blended: Vec<EdgeID> = edges
+            .iter()
+            .copied()
+            .filter(|id| self.blends.contains_key(id))
+            .collect();
+        if blended.len() > 1 {
+            let convex = self.blends[&blended[0]].convex;
+            if blended.iter().any(|id| self.blends[id].convex != convex) {
+                return Err(invalid(format!(
+                    "{name}ed edges meeting at {} must be all convex or all concave",
+                    fmt_point(at)
+                )));
+            }
+        }
+
+        for (&face, &(_, normal)) in faces.iter().zip(&planes) {
+            let Some(Some((entering, leaving))) = topology.corners.get(&(face, vertex)).copied()
+            else {
+                continue;
+            };
+            match (self.blends.get(&entering), self.blends.get(&leaving)) {
+                (Some(a), Some(b)) => {
+                    let (p, d) = a.setback_line(a.side(face));
+                    let (q, e) = b.setback_line(b.side(face));
+                    let point = line_plane(p, d, q, normal.cross(e))
+                        .ok_or_else(|| self.too_large(at))?;
+                    let new = builder::vertex(point);
+                    self.setback_ends.insert((entering, face, vertex), new.clone());
+                    self.setback_ends.insert((leaving, face, vertex), new);
+                }
+                (Some(blend), None) | (None, Some(blend)) => {
+                    let (selected, other) = if self.blends.contains_key(&entering) {
+                        (entering, leaving)
+                    } else {
+                        (leaving, entering)
+                    };
+                    let &(neighbour, _) = topology.edges[&other]
+                        .faces
+                        .iter()
+                        .find(|(f, _)| *f != face)
+                        .ok_or_else(|| invalid(format!("{name}: open edge at {}", fmt_point(at))))?;
+                    let (q, n) = topology.plane(neighbour, &what)?;
+                    let (p, d) = blend.setback_line(blend.side(face));
+                    let point = line_plane(p, d, q, n).ok_or_else(|| self.too_large(at))?;
+                    let new = match self.cut_ends.get(&(other, vertex)) {
+                        Some(existing) => {
+                            if (existing.point() - point).magnitude() > POINT_TOLERANCE {
+                                return Err(invalid(format!(
+                                    "{name}ed edges meeting at {} do not end at a common \
+                                     point on the edge between them",
+                                    fmt_point(at)
+                                )));
+                            }
+                            existing.clone()
+                        }
+                        None => {
+                            let new = builder::vertex(point);
+                            self.cut_ends.insert((other, vertex), new.clone());
+                            new
+                        }
+                    };
+                    self.setback_ends.insert((selected, face, vertex), new);
+                }
+                (None, None) => {}
+            }
+        }
+
+        let center = match (self.kind, blended.len()) {
+            (BlendKind::Fillet(radius), 2 | 3) => {
+                let convex = self.blends[&blended[0]].convex;
+                let offset = if convex { -radius } else { radius };
+                let offsets: Vec<_> = planes.iter().map(|&(p, n)| (p + n * offset, n)).collect();
+                Some(three_planes(&offsets).ok_or_else(|| self.too_large(at))?)
+            }
+            _ => None,
+        };
+        for &id in &blended {
+            self.end_link(id, vertex, &faces[..], &planes, blended.len(), center)?;
+        }
+        if blended.len() == 3 {
+            let normal = planes.iter().fold(Vector3::new(0.0, 0.0, 0.0), |s, &(_, n)| s + n);
+            self.corner_patch(vertex, &blended, center, normal)?;
+        }
+        Ok(())
+    }
+
+    /// Create the curve closing blend `id` at `vertex`.
+    fn end_link(
+        &mut self,
+        id: EdgeID,
+        vertex: VertexID,
+        faces: &[usize],
+        planes: &[(Point3, Vector3)],
+        blended: usize,
+        center: Option<Point3>,
+    ) -> GeomResult<()> {
+        let blend = &self.blends[&id];
+        let start = self.setback_ends[&(id, blend.faces[0], vertex)].clone();
+        let end = self.setback_ends[&(id, blend.faces[1], vertex)].clone();
+        if self.links.contains_key(&(start.id(), end.id())) {
+            return Ok(());
+        }
+        let curve = match (blend.weight, center) {
+            (None, _) => Curve::Line(truck_modeling::Line(start.point(), end.point())),
+            (Some(weight), None) => {
+                // A single blend runs out on the third face.
+                let third = faces
+                    .iter()
+                    .position(|f| !blend.faces.contains(f))
+                    .expect("a regular vertex has a third face");
+                let (point, normal) = planes[third];
+                blend.arc_on_plane(point, normal, weight)?
+            }
+            (Some(weight), Some(center)) => {
+                let normal = if blended == 3 {
+                    blend.dir
+                } else {
+                    // Two fillets meet along the bisecting plane through the
+                    // corner ball.
+                    (start.point() - center).cross(end.point() - center)
+                };
+                if normal.magnitude() < POINT_TOLERANCE * POINT_TOLERANCE {
+                    return Err(self.too_large(self.vertex_point(vertex)));
+                }
+                blend.arc_on_plane(center, normal.normalize(), weight)?
+            }
+        };
+        let edge = TruckEdge::new(&start, &end, curve);
+        self.links.insert((start.id(), end.id()), edge.clone());
+        self.links.insert((end.id(), start.id()), edge);
+        Ok(())
+    }
+
+    /// Close three blends meeting at `vertex` with a sphere patch (fillet)
+    /// or a flat triangle (chamfer).
+    fn corner_patch(
+        &mut self,
+        vertex: VertexID,
+        blended: &[EdgeID],
+        center: Option<Point3>,
+        outward: Vector3,
+    ) -> GeomResult<()> {
+        // The corner runs each end curve opposite to its blend face.
+        let mut edges: Vec<TruckEdge> = blended
+            .iter()
+            .map(|id| {
+                let blend = &self.blends[id];
+                let a = self.setback_ends[&(*id, blend.faces[0], vertex)].id();
+                let b = self.setback_ends[&(*id, blend.faces[1], vertex)].id();
+                let (from, to) = if vertex == blend.ends[0] { (b, a) } else { (a, b) };
+                self.link(from, to)
+            })
+            .collect::<GeomResult<_>>()?;
+        let mut wire = Wire::new();
+        wire.push_back(edges.remove(0));
+        while !edges.is_empty() {
+            let back = wire.back_vertex().map(|v| v.id());
+            let next = edges
+                .iter()
+                .position(|e| Some(e.front().id()) == back)
+                .ok_or_else(|| self.too_large(self.vertex_point(vertex)))?;
+            wire.push_back(edges.remove(next));
+        }
+        let points: Vec<Point3> = wire.vertex_iter().map(|v| v.point()).collect();
+        let surface = match (self.kind, center) {
+            (BlendKind::Fillet(radius), Some(center)) => {
+                let toward = points.iter().fold(Vector3::new(0.0, 0.0, 0.0), |s, &p| s + (p - center))
+                    / points.len() as f64;
+                if toward.magnitude() < POINT_TOLERANCE
+                    || points
+                        .iter()
+                        .any(|&p| (p - center).normalize().dot(toward.normalize()) < CORNER_HALF_SPAN.cos() + 0.05)
+                {
+                    return Err(invalid(format!(
+                        "fillet corner at {} is too sharp",
+                        fmt_point(self.vertex_point(vertex))
+                    )));
+                }
+                sphere_patch(center, radius, toward.normalize())
+            }
+            _ => Surface::Plane(Plane::new(points[0], points[1], points[2])),
+        };
+        self.corners.push(oriented_face(vec![wire], surface, outward)?);
+        Ok(())
+    }
+
+    fn build_setback_edges(&mut self) -> GeomResult<()> {
+        for id in &self.order {
+            let blend = &self.blends[id];
+            for face in blend.faces {
+                let start = &self.setback_ends[&(*id, face, blend.ends[0])];
+                let end = &self.setback_ends[&(*id, face, blend.ends[1])];
+                if (end.point() - start.point()).dot(blend.dir) < POINT_TOLERANCE {
+                    return Err(self.too_large(blend.origin));
+                }
+                self.setback_edges
+                    .insert((*id, face), builder::line(start, end));
+            }
+        }
+        Ok(())
+    }
+
+    fn build_shortened_edges(&mut self) -> GeomResult<()> {
+        for id in &self.topology.edge_order {
+            let edge = &self.topology.edges[id].edge;
+            let (front, back) = (edge.front(), edge.back());
+            let new_front = self.cut_ends.get(&(*id, front.id()));
+            let new_back = self.cut_ends.get(&(*id, back.id()));
+            if new_front.is_none() && new_back.is_none() {
+                continue;
+            }
+            let start = new_front.unwrap_or(front);
+            let end = new_back.unwrap_or(back);
+            let old = back.point() - front.point();
+            if (end.point() - start.point()).dot(old) < POINT_TOLERANCE * old.magnitude() {
+                return Err(self.too_large(front.point()));
+            }
+            self.shortened.insert(*id, builder::line(start, end));
+        }
+        Ok(())
+    }
+
+    /// Assemble the rebuilt shell.
+    fn shell(self) -> GeomResult<Shell> {
+        let mut faces = (0..self.topology.faces.len())
+            .map(|i| self.rebuild_face(i))
+            .collect::<GeomResult<Vec<_>>>()?;
+        for id in &self.order {
+            faces.push(self.blend_face(*id)?);
+        }
+        faces.extend(self.corners);
+        Ok(Shell::from(faces))
+    }
+
+    fn rebuild_face(&self, index: usize) -> GeomResult<TruckFace> {
+        let data = &self.topology.faces[index];
+        let wires = data
+            .wires
+            .iter()
+            .map(|wire| {
+                let edges: Vec<TruckEdge> = wire
+                    .edge_iter()
+                    .map(|e| self.replacement(e, index))
+                    .collect();
+                let mut rebuilt = Wire::new();
+                for (i, edge) in edges.iter().enumerate() {
+                    let next = &edges[(i + 1) % edges.len()];
+                    rebuilt.push_back(edge.clone());
+                    if edge.back().id() != next.front().id() {
+                        rebuilt.push_back(self.link(edge.back().id(), next.front().id())?);
+                    }
+                }
+                Ok(rebuilt)
+            })
+            .collect::<GeomResult<Vec<_>>>()?;
+        let face = if data.face.orientation() {
+            TruckFace::try_new(wires, data.face.surface())
+        } else {
+            let inverted = wires.iter().map(|w| w.inverse()).collect();
+            TruckFace::try_new(inverted, data.face.surface()).map(|f| f.inverse())
+        };
+        face.map_err(|e| self.invalid_face(e))
+    }
+
+    /// The face's edge, replaced by its setback line or shortened copy.
+    fn replacement(&self, edge: &TruckEdge, face: usize) -> TruckEdge {
+        let id = edge.id();
+        let new = self
+            .setback_edges
+            .get(&(id, face))
+            .or_else(|| self.shortened.get(&id));
+        match new {
+            Some(new) if edge.orientation() => new.clone(),
+            Some(new) => new.inverse(),
+            None => edge.clone(),
+        }
+    }
+
+    fn blend_face(&self, id: EdgeID) -> GeomResult<TruckFace> {
+        let blend = &self.blends[&id];
+        let [f0, f1] = blend.faces;
+        let [front, back] = blend.ends;
+        let mut wire = Wire::new();
+        wire.push_back(self.setback_edges[&(id, f0)].inverse());
+        wire.push_back(self.link(
+            self.setback_ends[&(id, f0, front)].id(),
+            self.setback_ends[&(id, f1, front)].id(),
+        )?);
+        wire.push_back(self.setback_edges[&(id, f1)].clone());
+        wire.push_back(self.link(
+            self.setback_ends[&(id, f1, back)].id(),
+            self.setback_ends[&(id, f0, back)].id(),
+        )?);
+
+        // Extent of the boundary along the edge, including the control
+        // polygons of the end curves.
+        let mut extent = (f64::INFINITY, f64::NEG_INFINITY);
+        for edge in wire.edge_iter() {
+            let points = match edge.curve() {
+                Curve::NurbsCurve(curve) => curve
+                    .control_points()
+                    .iter()
+                    .map(|v| Point3::from_homogeneous(*v))
+                    .collect(),
+                _ => vec![edge.front().point(), edge.back().point()],
+            };
+            for p in points {
+                let t = (p - blend.origin).dot(blend.dir);
+                extent = (extent.0.min(t), extent.1.max(t));
+            }
+        }
+        let margin = 0.1 * (extent.1 - extent.0) + blend.setback;
+        let surface = blend.surface(extent.0 - margin, extent.1 + margin);
+        let outward = blend.normals[0] + blend.normals[1];
+        oriented_face(vec![wire], surface, outward)
+    }
+
+    /// The link edge from `from` to `to`, in that direction.
+    fn link(&self, from: VertexID, to: VertexID) -> GeomResult<TruckEdge> {
+        let edge = self
+            .links
+            .get(&(from, to))
+            .ok_or_else(|| invalid(format!("{} left a gap in a face boundary", self.kind.name())))?;
+        Ok(if edge.front().id() == from {
+            edge.clone()
+        } else {
+            edge.inverse()
+        })
+    }
+
+    fn vertex_point(&self, vertex: VertexID) -> Point3 {
+        let id = self.topology.vertex_edges[&vertex][0];
+        let edge = &self.topology.edges[&id].edge;
+        if edge.front().id() == vertex {
+            edge.front().point()
+        } else {
+            edge.back().point()
+        }
+    }
+
+    fn too_large(&self, at: Point3) -> GeomError {
+        let (what, size) = match self.kind {
+            BlendKind::Fillet(r) => ("fillet radius", r),
+            BlendKind::Chamfer(d) => ("chamfer distance", d),
+        };
+        invalid(format!(
+            "{what} {size} is too large for the faces near {}",
+            fmt_point(at)
+        ))
+    }
+
+    fn invalid_face(&self, e: impl std::fmt::Display) -> GeomError {
+        invalid(format!("{} produced an invalid face: {e}", self.kind.name()))
+    }
+}
+
+// ── Geometry helpers ────────────────────────────────────────────────────
+
+/// Build a face from outward-oriented wires, flipping the surface if its
+/// natural normal points away from `outward`.
+fn oriented_face(wires: Vec<Wire>, surface: Surface, outward: Vector3) -> GeomResult<TruckFace> {
+    let natural = match &surface {
+        Surface::Plane(plane) => plane.normal(),
+        other => other.normal(0.5, 0.5),
+    };
+    let face = if natural.dot(outward) > 0.0 {
+        TruckFace::try_new(wires, surface)
+    } else {
+        let inverted = wires.iter().map(|w| w.inverse()).collect();
+        TruckFace::try_new(inverted, surface).map(|f| f.inverse())
+    };
+    face.map_err(|e| invalid(format!("blend produced an invalid face: {e}")))
+}
+
+/// Exact sphere patch around the direction `toward` from `center`, as a
+/// rational biquadratic surface of revolution spanning
+/// `±CORNER_HALF_SPAN` in latitude and longitude.
+fn sphere_patch(center: Point3, radius: f64, toward: Vector3) -> Surface {
+    let side = crate::curve::perpendicular(toward);
+    let axis = toward.cross(side);
+    let (c, s) = (CORNER_HALF_SPAN.cos(), CORNER_HALF_SPAN.sin());
+    // Rational quadratic arc from -span to +span: (x, y, weight).
+    let arc = [(c, -s, 1.0), (1.0 / c, 0.0, c), (c, s, 1.0)];
+    let rows = arc
+        .iter()
+        .map(|&(radial, height, w_lat)| {
+            arc.iter()
+                .map(|&(x, y, w_lon)| {
+                    let p = center
+                        + (toward * (radial * x) + side * (radial * y) + axis * height) * radius;
+                    homogeneous(p, w_lat * w_lon)
+                })
+                .collect()
+        })
+        .collect();
+    Surface::NurbsSurface(NurbsSurface::new(BSplineSurface::new(
+        (KnotVec::bezier_knot(2), KnotVec::bezier_knot(2)),
+        rows,
+    )))
+}
+
+/// Intersection of the line `p + t d` with the plane through `q` with
+/// normal `n`.
+fn line_plane(p: Point3, d: Vector3, q: Point3, n: Vector3) -> Option<Point3> {
+    let denom = d.dot(n);
+    (denom.abs() > 1e-9 * n.magnitude()).then(|| p + d * ((q - p).dot(n) / denom))
+}
+
+/// Common point of three planes given as (point, normal).
+fn three_planes(planes: &[(Point3, Vector3)]) -> Option<Point3> {
+    let [(p1, n1), (p2, n2), (p3, n3)] = planes[..] else {
+        return None;
+    };
+    let det = n1.dot(n2.cross(n3));
+    if det.abs() < 1e-9 {
+        return None;
+    }
+    let (d1, d2, d3) = (n1.dot(p1.to_vec()), n2.dot(p2.to_vec()), n3.dot(p3.to_vec()));
+    let v = (n2.cross(n3) * d1 + n3.cross(n1) * d2 + n1.cross(n2) * d3) / det;
+    Some(Point3::from_vec(v))
+}
+
+fn homogeneous(p: Point3, w: f64) -> Vector4 {
+    Vector4::new(p.x * w, p.y * w, p.z * w, w)
+}
+
+fn unit(v: Vector3, what: &str) -> GeomResult<Vector3> {
+    if v.magnitude() < POINT_TOLERANCE {
+        return Err(invalid(format!("{what} must be non-zero")));
+    }
+    Ok(v.normalize())
+}
+
+fn fmt_point(p: Point3) -> String {
+    format!("({}, {}, {})", p.x, p.y, p.z)
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder};
+    use std::f64::consts::PI;
+    use truck_modeling::Point2;
+
+    fn mesh_volume(solid: &TruckSolid) -> f64 {
+        let mesh = crate::tessellate::mesh_solid(solid, 0.001);
+        let pos = mesh.positions();
+        mesh.face_iter()
+            .map(|f| {
+                let a = pos[f[0].pos].to_vec();
+                (1..f.len() - 1)
+                    .map(|i| a.dot(pos[f[i].pos].to_vec().cross(pos[f[i + 1].pos].to_vec())))
+                    .sum::<f64>()
+                    / 6.0
+            })
+            .sum()
+    }
+
+    fn assert_volume(solid: &TruckSolid, expected: f64) {
+        let v = mesh_volume(solid);
+        assert!((v - expected).abs() < 0.01 * expected, "volume {v}, expected {expected}");
+    }
+
+    fn cube() -> TruckSolid {
+        make_box(10.0, 10.0, 10.0)
+    }
+
+    #[test]
+    fn fillet_vertical_edges() {
+        let solid = solid_fillet(&cube(), 2.0, &EdgeSelector::ParallelTo(Vector3::unit_z())).unwrap();
+        // Each edge loses (1 - π/4) r² along its length.
+        assert_volume(&solid, 1000.0 - 4.0 * (1.0 - PI / 4.0) * 4.0 * 10.0);
+        assert_eq!(solid.boundaries()[0].len(), 10);
+    }
+
+    #[test]
+    fn chamfer_vertical_edges() {
+        let solid =
+            solid_chamfer(&cube(), 2.0, &EdgeSelector::ParallelTo(Vector3::unit_z())).unwrap();
+        assert_volume(&solid, 1000.0 - 4.0 * 2.0 * 10.0);
+    }
+
+    #[test]
+    fn fillet_top_face_miters_corners() {
+        let solid = solid_fillet(&cube(), 2.0, &EdgeSelector::OnFace(Vector3::unit_z())).unwrap();
+        // The removed cross-section swept around the mitered square, whose
+        // centroid sits r (10 - 3π) / (12 - 3π) in from each face.
+        let area = (1.0 - PI / 4.0) * 4.0;
+        let inset = 2.0 * (10.0 - 3.0 * PI) / (12.0 - 3.0 * PI);
+        assert_volume(&solid, 1000.0 - area * 4.0 * (10.0 - 2.0 * inset));
+    }
+
+    #[test]
+    fn fillet_all_edges_rounds_corners() {
+        let solid = solid_fillet(&cube(), 2.0, &EdgeSelector::All).unwrap();
+        // Rounded box: inner box + face slabs + edge quarter cylinders +
+        // corner eighth spheres.
+        let (r, a) = (2.0_f64, 6.0_f64);
+        let expected = a.powi(3) + 6.0 * a * a * r + 3.0 * PI * r * r * a + 4.0 / 3.0 * PI * r.powi(3);
+        assert_volume(&solid, expected);
+        // 6 faces, 12 edge blends, 8 corners.
+        assert_eq!(solid.boundaries()[0].len(), 26);
+    }
+
+    #[test]
+    fn chamfer_all_edges_cuts_corners() {
+        let solid = solid_chamfer(&cube(), 1.0, &EdgeSelector::All).unwrap();
+        // 12 edge prisms of d²/2 · (10 - 2d), plus 8 corners of 5d³/6.
+        assert_volume(&solid, 1000.0 - 12.0 * 0.5 * 8.0 - 8.0 * 5.0 / 6.0);
+    }
+
+    #[test]
+    fn concave_edge_fillet_adds_material() {
+        let points = [(0.0, 0.0), (4.0, 0.0), (4.0, 1.0), (1.0, 1.0), (1.0, 4.0), (0.0, 4.0)]
+            .map(|(x, y)| Point2::new(x, y));
+        let profile = crate::sketch::make_polygon(&points).unwrap();
+        let solid = crate::sweep::solid_extrude(&profile, 2.0).unwrap();
+        let before = mesh_volume(&solid);
+        let filleted =
+            solid_fillet(&solid, 0.4, &EdgeSelector::ParallelTo(Vector3::unit_z())).unwrap();
+        // Five convex edges lose material, the inner corner gains it.
+        let spandrel = (1.0 - PI / 4.0) * 0.16 * 2.0;
+        let v = mesh_volume(&filleted);
+        assert!((v - (before - 4.0 * spandrel)).abs() < 0.01, "volume {v}");
+    }
+
+    #[test]
+    fn fillet_rejects_bad_input() {
+        let selector = EdgeSelector::ParallelTo(Vector3::unit_z());
+        let err = solid_fillet(&cube(), 6.0, &selector).unwrap_err();
+        assert!(err.message.contains("too large"), "{}", err.message);
+        assert!(solid_fillet(&cube(), 0.0, &selector).is_err());
+
+        let err = solid_fillet(&make_cylinder(2.0, 3.0), 0.5, &EdgeSelector::All).unwrap_err();
+        assert!(err.message.contains("straight edges"), "{}", err.message);
+
+        let err = solid_chamfer(&cube(), 1.0, &EdgeSelector::OnFace(Vector3::new(1.0, 1.0, 0.0)))
+            .unwrap_err();
+        assert!(err.message.contains("no edges"), "{}", err.message);
+        let err = solid_chamfer(&cube(), 1.0, &EdgeSelector::ParallelTo(Vector3::new(0.0, 0.0, 0.0)))
+            .unwrap_err();
+        assert!(err.message.contains("non-zero"), "{}", err.message);
+    }
+}
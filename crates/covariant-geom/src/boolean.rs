@@ -1,7 +1,16 @@
 //! Boolean operations on solids via truck_shapeops.
 
+use rayon::prelude::*;
+use truck_modeling::Vector3;
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::query::BoundingBox;
 use crate::types::TruckSolid;
 
+/// Chordal tolerance of the coarse tessellation used to bound solids before
+/// a many-way union. Boxes are padded by it, so they never under-cover.
+const GROUPING_TOLERANCE: f64 = 0.1;
+
 /// Boolean union of two solids.
 pub fn solid_union(a: &TruckSolid, b: &TruckSolid, tol: f64) -> Option<TruckSolid> {
     truck_shapeops::or(a, b, tol)
@@ -21,11 +30,170 @@ pub fn solid_difference(a: &TruckSolid, b: &TruckSolid, tol: f64) -> Option<Truc
     truck_shapeops::and(a, &b_inv, tol)
 }
 
+/// Union of many solids.
+///
+/// Solids whose bounding boxes are apart cannot intersect, so they are
+/// split into groups that overlap, each group is reduced on its own, and
+/// the results are gathered into one solid without a boolean. Within a
+/// group the solids are halved spatially and unioned as a balanced tree,
+/// with the two halves of every split evaluated in parallel.
+pub fn solid_union_many(solids: Vec<TruckSolid>, tol: f64) -> GeomResult<TruckSolid> {
+    union_items(bounded(solids, "solid")?, tol)
+        .map(|(solid, _)| solid)
+        .ok_or_else(|| GeomError::new(GeomErrorKind::BooleanFailed, "union failed"))
+}
+
+/// `base` minus every cutter. Cutters clear of the base are dropped and the
+/// rest are combined with [`solid_union_many`], so the base is cut once.
+pub fn solid_difference_many(
+    base: &TruckSolid,
+    cutters: Vec<TruckSolid>,
+    tol: f64,
+) -> GeomResult<TruckSolid> {
+    let reach = grouping_box(base).ok_or_else(|| empty("base solid"))?;
+    let cutters: Vec<_> = bounded(cutters, "cutter")?
+        .into_iter()
+        .filter(|(_, b)| b.distance(&reach) == 0.0)
+        .collect();
+    if cutters.is_empty() {
+        return Ok(base.clone());
+    }
+    union_items(cutters, tol)
+        .and_then(|(tool, _)| solid_difference(base, &tool, tol))
+        .ok_or_else(|| GeomError::new(GeomErrorKind::BooleanFailed, "difference failed"))
+}
+
+/// Pair each solid with its grouping box, computed in parallel.
+///
+/// Empty solids are rejected up front, naming the first one as
+/// `<what> <index>`, rather than failing somewhere inside the union.
+fn bounded(solids: Vec<TruckSolid>, what: &str) -> GeomResult<Vec<(TruckSolid, BoundingBox)>> {
+    let items: Vec<_> = solids
+        .into_par_iter()
+        .map(|solid| {
+            let bounds = grouping_box(&solid);
+            (solid, bounds)
+        })
+        .collect();
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, (solid, bounds))| {
+            bounds
+                .map(|b| (solid, b))
+                .ok_or_else(|| empty(&format!("{what} {i}")))
+        })
+        .collect()
+}
+
+fn empty(what: &str) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, format!("{what} is empty"))
+}
+
+fn union_items(
+    mut items: Vec<(TruckSolid, BoundingBox)>,
+    tol: f64,
+) -> Option<(TruckSolid, BoundingBox)> {
+    if items.len() == 1 {
+        return items.pop();
+    }
+    let groups = overlapping_groups(items);
+    if groups.len() > 1 {
+        let parts = groups
+            .into_par_iter()
+            .map(|group| union_items(group, tol))
+            .collect::<Option<Vec<_>>>()?;
+        return Some(gather(parts));
+    }
+
+    // One overlapping group: split at the median along its longest extent.
+    let mut items = groups.into_iter().next()?;
+    let bounds = enclosing(items.iter().map(|(_, b)| b));
+    let size = bounds.size();
+    let axis = if size.x >= size.y && size.x >= size.z {
+        0
+    } else if size.y >= size.z {
+        1
+    } else {
+        2
+    };
+    items.sort_by(|(_, a), (_, b)| a.center()[axis].total_cmp(&b.center()[axis]));
+    let right = items.split_off(items.len() / 2);
+    let (left, right) = rayon::join(|| union_items(items, tol), || union_items(right, tol));
+    let (left, right) = (left?, right?);
+    if left.1.distance(&right.1) > 0.0 {
+        return Some(gather(vec![left, right]));
+    }
+    let bounds = enclosing([&left.1, &right.1]);
+    Some((solid_union(&left.0, &right.0, tol)?, bounds))
+}
+
+/// Partition solids into groups whose bounding boxes touch transitively.
+fn overlapping_groups(
+    items: Vec<(TruckSolid, BoundingBox)>,
+) -> Vec<Vec<(TruckSolid, BoundingBox)>> {
+    // Union-find over box overlap.
+    let mut parent: Vec<usize> = (0..items.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for i in 0..items.len() {
+        for j in i + 1..items.len() {
+            if items[i].1.distance(&items[j].1) == 0.0 {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a] = b;
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<_>> = Vec::new();
+    let mut group_of_root = vec![usize::MAX; items.len()];
+    for (i, item) in items.into_iter().enumerate() {
+        let r = root(&mut parent, i);
+        if group_of_root[r] == usize::MAX {
+            group_of_root[r] = groups.len();
+            groups.push(Vec::new());
+        }
+        groups[group_of_root[r]].push(item);
+    }
+    groups
+}
+
+/// Combine solids known to be apart by collecting their boundary shells.
+fn gather(parts: Vec<(TruckSolid, BoundingBox)>) -> (TruckSolid, BoundingBox) {
+    let bounds = enclosing(parts.iter().map(|(_, b)| b));
+    let shells = parts
+        .into_iter()
+        .flat_map(|(solid, _)| solid.into_boundaries())
+        .collect();
+    (TruckSolid::debug_new(shells), bounds)
+}
+
+/// Padded bounding box of a coarse tessellation, or `None` for an empty solid.
+fn grouping_box(solid: &TruckSolid) -> Option<BoundingBox> {
+    let mesh = crate::tessellate::mesh_solid(solid, GROUPING_TOLERANCE);
+    let b = BoundingBox::of_points(mesh.positions())?;
+    let pad = Vector3::new(1.0, 1.0, 1.0) * (2.0 * GROUPING_TOLERANCE);
+    Some(BoundingBox {
+        min: b.min - pad,
+        max: b.max + pad,
+    })
+}
+
+fn enclosing<'a>(boxes: impl IntoIterator<Item = &'a BoundingBox>) -> BoundingBox {
+    BoundingBox::of_points(boxes.into_iter().flat_map(|b| [&b.min, &b.max]))
+        .expect("enclosing at least one box")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mass::mass_properties;
     use crate::primitives::make_box;
-    use crate::DEFAULT_TOLERANCE;
     use truck_modeling::{builder, Vector3};
 
     /// Make a translated copy of a solid.
@@ -59,4 +227,76 @@ mod tests {
         let result = solid_intersection(&a, &b, tol);
         assert!(result.is_some(), "intersection of overlapping boxes should succeed");
     }
+
+    fn volume(solid: &TruckSolid) -> f64 {
+        mass_properties(solid, 1.0, 0.01).unwrap().volume
+    }
+
+    #[test]
+    fn union_many_gathers_disjoint_solids_without_booleans() {
+        let boxes: Vec<_> = (0..3)
+            .map(|i| translated(&make_box(4.0, 4.0, 4.0), Vector3::new(10.0 * i as f64, 0.0, 0.0)))
+            .collect();
+        let result = solid_union_many(boxes, 0.05).unwrap();
+        assert_eq!(result.boundaries().len(), 3);
+        assert!((volume(&result) - 192.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn union_many_matches_pairwise_fold() {
+        // A diagonal chain of overlapping cubes with no coplanar faces.
+        let cubes: Vec<_> = (0..6)
+            .map(|i| translated(&make_box(5.0, 5.0, 5.0), Vector3::new(2.0, 2.0, 2.0) * i as f64))
+            .collect();
+        let folded = cubes[1..]
+            .iter()
+            .try_fold(cubes[0].clone(), |acc, c| solid_union(&acc, c, 0.05))
+            .unwrap();
+        let balanced = solid_union_many(cubes, 0.05).unwrap();
+        assert_eq!(balanced.boundaries().len(), 1);
+        assert!((volume(&balanced) - volume(&folded)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn difference_many_cuts_every_pocket() {
+        let plate = make_box(60.0, 40.0, 5.0);
+        let cutter = |x: f64, y: f64| translated(&make_box(4.0, 4.0, 15.0), Vector3::new(x, y, -5.0));
+        let mut cutters: Vec<_> = (0..3)
+            .flat_map(|i| (0..2).map(move |j| (i, j)))
+            .map(|(i, j)| cutter(8.0 + 20.0 * i as f64, 8.0 + 20.0 * j as f64))
+            .collect();
+        // An overlapping cutter (offset in z to avoid coplanar caps), and
+        // one clear of the plate.
+        cutters.push(translated(&make_box(4.0, 4.0, 17.0), Vector3::new(10.0, 10.0, -6.0)));
+        cutters.push(cutter(100.0, 0.0));
+        let result = solid_difference_many(&plate, cutters, 0.05).unwrap();
+        let pockets = 5.0 * 16.0 + (16.0 + 16.0 - 4.0);
+        let expected = 60.0 * 40.0 * 5.0 - 5.0 * pockets;
+        assert!((volume(&result) - expected).abs() < 1e-6, "{}", volume(&result));
+    }
+
+    #[test]
+    fn difference_many_without_cutters_is_the_base() {
+        let plate = make_box(10.0, 10.0, 2.0);
+        let result = solid_difference_many(&plate, Vec::new(), 0.05).unwrap();
+        assert!((volume(&result) - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn many_way_booleans_name_empty_inputs() {
+        let cube = make_box(1.0, 1.0, 1.0);
+        let empty = TruckSolid::new_unchecked(Vec::new());
+
+        let err = solid_union_many(vec![cube.clone(), empty.clone()], 0.05).unwrap_err();
+        assert_eq!(err.kind, GeomErrorKind::InvalidInput);
+        assert_eq!(err.message, "solid 1 is empty");
+
+        let err = solid_difference_many(&cube, vec![empty.clone()], 0.05).unwrap_err();
+        assert_eq!(err.kind, GeomErrorKind::InvalidInput);
+        assert_eq!(err.message, "cutter 0 is empty");
+
+        let err = solid_difference_many(&empty, vec![cube], 0.05).unwrap_err();
+        assert_eq!(err.kind, GeomErrorKind::InvalidInput);
+        assert_eq!(err.message, "base solid is empty");
+    }
 }
@@ -0,0 +1,299 @@
+//! One-dimensional curve construction.
+//!
+//! Curves are returned as wires so that closed curves (circles, closed
+//! parametric loops) and piecewise curves (helices, interpolated paths)
+//! share one representation. Smooth free-form curves are built from cubic
+//! Hermite segments, one edge per segment.
+
+use std::f64::consts::{PI, TAU};
+use truck_modeling::{
+    builder, BSplineCurve, Curve, Edge, InnerSpace, KnotVec, MetricSpace, Point3, Rad, Vector3,
+};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::TruckWire;
+
+/// Distance below which two curve points are considered coincident.
+const POINT_TOLERANCE: f64 = 1e-9;
+
+/// Hermite segments per helix turn; keeps the deviation from a true helix
+/// around 1e-5 of the radius.
+const HELIX_SEGMENTS_PER_TURN: usize = 16;
+
+/// Straight line segment from `p0` to `p1`.
+pub fn line_curve(p0: Point3, p1: Point3) -> GeomResult<TruckWire> {
+    if p0.distance(p1) < POINT_TOLERANCE {
+        return Err(invalid("line end points must differ".to_string()));
+    }
+    let (v0, v1) = (builder::vertex(p0), builder::vertex(p1));
+    Ok(TruckWire::from(vec![builder::line(&v0, &v1)]))
+}
+
+/// Full circle around `normal`, wound counter-clockwise when viewed from
+/// the tip of the normal. The curve starts on the circle's local X axis.
+pub fn circle_curve(center: Point3, radius: f64, normal: Vector3) -> GeomResult<TruckWire> {
+    check_radius(radius)?;
+    if normal.magnitude() < POINT_TOLERANCE {
+        return Err(invalid("circle normal must be non-zero".to_string()));
+    }
+    let normal = normal.normalize();
+    let start = center + perpendicular(normal) * radius;
+    Ok(builder::rsweep(
+        &builder::vertex(start),
+        center,
+        normal,
+        Rad(TAU),
+    ))
+}
+
+/// Circular arc in the XY plane, from `start_angle` to `end_angle`
+/// (radians, counter-clockwise from +X). A span of a full turn or more
+/// gives a full circle.
+pub fn arc_curve(
+    center: Point3,
+    radius: f64,
+    start_angle: f64,
+    end_angle: f64,
+) -> GeomResult<TruckWire> {
+    check_radius(radius)?;
+    let span = end_angle - start_angle;
+    if span.abs() < POINT_TOLERANCE {
+        return Err(invalid("arc start and end angles must differ".to_string()));
+    }
+    let at = |angle: f64| center + Vector3::new(angle.cos(), angle.sin(), 0.0) * radius;
+    if span.abs() >= TAU {
+        let axis = Vector3::unit_z() * span.signum();
+        return Ok(builder::rsweep(
+            &builder::vertex(at(start_angle)),
+            center,
+            axis,
+            Rad(TAU),
+        ));
+    }
+    let (v0, v1) = (builder::vertex(at(start_angle)), builder::vertex(at(end_angle)));
+    let transit = at(start_angle + span / 2.0);
+    Ok(TruckWire::from(vec![builder::circle_arc(&v0, &v1, transit)]))
+}
+
+/// Cylindrical helix around the Z axis, starting at `(radius, 0, 0)` and
+/// rising `height` at `pitch` per turn. Right-handed unless `left_handed`.
+pub fn helix_curve(
+    radius: f64,
+    pitch: f64,
+    height: f64,
+    left_handed: bool,
+) -> GeomResult<TruckWire> {
+    check_radius(radius)?;
+    if pitch <= 0.0 || height <= 0.0 {
+        return Err(invalid(format!(
+            "helix pitch and height must be positive, got {pitch} and {height}"
+        )));
+    }
+    let turns = height / pitch;
+    let segments = ((turns * HELIX_SEGMENTS_PER_TURN as f64).ceil() as usize).max(2);
+    let hand = if left_handed { -1.0 } else { 1.0 };
+    let rate = hand * 2.0 * PI / pitch;
+
+    let zs: Vec<f64> = (0..=segments)
+        .map(|i| height * i as f64 / segments as f64)
+        .collect();
+    let points: Vec<Point3> = zs
+        .iter()
+        .map(|&z| Point3::new(radius * (rate * z).cos(), radius * (rate * z).sin(), z))
+        .collect();
+    // Derivatives with respect to z, matching the segment parameter span.
+    let tangents: Vec<Vector3> = zs
+        .iter()
+        .map(|&z| {
+            Vector3::new(
+                -radius * rate * (rate * z).sin(),
+                radius * rate * (rate * z).cos(),
+                1.0,
+            )
+        })
+        .collect();
+    Ok(hermite_wire(&points, &tangents, height / segments as f64, false))
+}
+
+/// Smooth curve through `points` (Catmull–Rom interpolation).
+///
+/// If the last point coincides with the first, the curve is closed and
+/// smooth across the seam.
+pub fn interpolated_curve(points: &[Point3]) -> GeomResult<TruckWire> {
+    let mut pts = points.to_vec();
+    let closed = pts.len() > 2 && pts[0].distance(pts[pts.len() - 1]) < POINT_TOLERANCE;
+    if closed {
+        pts.pop();
+    }
+    if pts.len() < 2 {
+        return Err(invalid(format!(
+            "curve needs at least 2 distinct points, got {}",
+            pts.len()
+        )));
+    }
+    for (i, w) in pts.windows(2).enumerate() {
+        if w[0].distance(w[1]) < POINT_TOLERANCE {
+            return Err(invalid(format!("curve has repeated consecutive point {i}")));
+        }
+    }
+
+    let n = pts.len();
+    let tangents: Vec<Vector3> = (0..n)
+        .map(|i| match (closed, i) {
+            (true, _) => (pts[(i + 1) % n] - pts[(i + n - 1) % n]) / 2.0,
+            (false, 0) => pts[1] - pts[0],
+            (false, i) if i == n - 1 => pts[i] - pts[i - 1],
+            (false, i) => (pts[i + 1] - pts[i - 1]) / 2.0,
+        })
+        .collect();
+    if closed {
+        pts.push(pts[0]);
+        let mut tangents = tangents;
+        tangents.push(tangents[0]);
+        return Ok(hermite_wire(&pts, &tangents, 1.0, true));
+    }
+    Ok(hermite_wire(&pts, &tangents, 1.0, false))
+}
+
+/// Chain cubic Hermite segments through `points` with the given end
+/// derivatives, each segment spanning a parameter interval of `step`.
+///
+/// When `closed`, the last point must equal the first and the wire reuses
+/// the first vertex.
+fn hermite_wire(points: &[Point3], tangents: &[Vector3], step: f64, closed: bool) -> TruckWire {
+    let mut vertices: Vec<_> = points.iter().map(|&p| builder::vertex(p)).collect();
+    if closed {
+        let last = vertices.len() - 1;
+        vertices[last] = vertices[0].clone();
+    }
+    let edges: Vec<_> = (0..points.len() - 1)
+        .map(|i| {
+            let (p0, p1) = (points[i], points[i + 1]);
+            let (d0, d1) = (tangents[i] * step / 3.0, tangents[i + 1] * step / 3.0);
+            let curve = BSplineCurve::new(KnotVec::bezier_knot(3), vec![p0, p0 + d0, p1 - d1, p1]);
+            Edge::new(&vertices[i], &vertices[i + 1], Curve::BSplineCurve(curve))
+        })
+        .collect();
+    TruckWire::from(edges)
+}
+
+/// A unit vector perpendicular to `normal`, preferring the world X axis.
+pub(crate) fn perpendicular(normal: Vector3) -> Vector3 {
+    let reference = if normal.x.abs() < 0.9 {
+        Vector3::unit_x()
+    } else {
+        Vector3::unit_y()
+    };
+    (reference - normal * reference.dot(normal)).normalize()
+}
+
+fn check_radius(radius: f64) -> GeomResult<()> {
+    if radius <= 0.0 {
+        return Err(invalid(format!("radius must be positive, got {radius}")));
+    }
+    Ok(())
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use truck_modeling::{BoundedCurve, EuclideanSpace, ParametricCurve};
+
+    fn end_points(wire: &TruckWire) -> (Point3, Point3) {
+        (
+            wire.front_vertex().unwrap().point(),
+            wire.back_vertex().unwrap().point(),
+        )
+    }
+
+    #[test]
+    fn line_between_points() {
+        let wire = line_curve(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0)).unwrap();
+        assert_eq!(wire.len(), 1);
+        assert_eq!(end_points(&wire).1, Point3::new(1.0, 2.0, 3.0));
+        assert!(line_curve(Point3::origin(), Point3::origin()).is_err());
+    }
+
+    #[test]
+    fn circle_is_closed_and_on_radius() {
+        let wire = circle_curve(Point3::new(1.0, 0.0, 0.0), 2.0, Vector3::unit_y()).unwrap();
+        assert!(wire.is_closed());
+        for edge in wire.edge_iter() {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.range_tuple();
+            let p = curve.subs((t0 + t1) / 2.0);
+            assert!((p.distance(Point3::new(1.0, 0.0, 0.0)) - 2.0).abs() < 1e-9);
+            assert!(p.y.abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn arc_end_points_follow_angles() {
+        let wire = arc_curve(Point3::origin(), 2.0, 0.0, PI / 2.0).unwrap();
+        let (p0, p1) = end_points(&wire);
+        assert!(p0.distance(Point3::new(2.0, 0.0, 0.0)) < 1e-9);
+        assert!(p1.distance(Point3::new(0.0, 2.0, 0.0)) < 1e-9);
+        assert!(arc_curve(Point3::origin(), 2.0, 1.0, 1.0).is_err());
+        assert!(arc_curve(Point3::origin(), 2.0, 0.0, TAU).unwrap().is_closed());
+    }
+
+    #[test]
+    fn helix_stays_on_cylinder() {
+        let wire = helix_curve(3.0, 2.0, 5.0, false).unwrap();
+        let (p0, p1) = end_points(&wire);
+        assert!(p0.distance(Point3::new(3.0, 0.0, 0.0)) < 1e-9);
+        // 2.5 turns ends half way round.
+        assert!(p1.distance(Point3::new(-3.0, 0.0, 5.0)) < 1e-9);
+        for edge in wire.edge_iter() {
+            let curve = edge.oriented_curve();
+            let (t0, t1) = curve.range_tuple();
+            let p = curve.subs((t0 + t1) / 2.0);
+            assert!(((p.x * p.x + p.y * p.y).sqrt() - 3.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn helix_handedness() {
+        let right = helix_curve(1.0, 4.0, 1.0, false).unwrap();
+        let left = helix_curve(1.0, 4.0, 1.0, true).unwrap();
+        // A quarter turn: right-handed goes to +Y, left-handed to -Y.
+        assert!(end_points(&right).1.y > 0.99);
+        assert!(end_points(&left).1.y < -0.99);
+    }
+
+    #[test]
+    fn interpolated_curve_passes_through_points() {
+        let pts = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(2.0, 0.0, 1.0),
+        ];
+        let wire = interpolated_curve(&pts).unwrap();
+        let vertices: Vec<_> = wire.vertex_iter().map(|v| v.point()).collect();
+        assert_eq!(vertices, pts.to_vec());
+        assert!(!wire.is_closed());
+    }
+
+    #[test]
+    fn interpolated_curve_closes_loop() {
+        let pts = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 0.0, 0.0),
+        ];
+        let wire = interpolated_curve(&pts).unwrap();
+        assert!(wire.is_closed());
+        assert_eq!(wire.len(), 3);
+    }
+
+    #[test]
+    fn interpolated_curve_rejects_degenerate_input() {
+        assert!(interpolated_curve(&[Point3::origin()]).is_err());
+        assert!(interpolated_curve(&[Point3::origin(), Point3::origin()]).is_err());
+    }
+}
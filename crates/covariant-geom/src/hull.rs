@@ -0,0 +1,391 @@
+//! Convex hulls and Minkowski sums.
+//!
+//! Both work on the vertices of a tessellation ([`HULL_TOLERANCE`]) and
+//! rebuild the result as a polyhedron of planar faces, so curved inputs are
+//! approximated by their inscribed tessellation. The hull is computed with
+//! quickhull; coplanar hull triangles are merged into one polygonal face.
+//!
+//! The Minkowski sum is the hull of all pairwise vertex sums, which is only
+//! correct for convex operands; non-convex inputs are rejected.
+
+use std::collections::{HashMap, HashSet};
+
+use truck_modeling::{EuclideanSpace, InnerSpace, Point3, Vector3};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::TruckSolid;
+
+/// Chordal tolerance of the tessellation hulls are built from.
+pub const HULL_TOLERANCE: f64 = 0.05;
+
+/// Relative volume a Minkowski operand may fall short of its own hull by
+/// and still count as convex.
+const CONVEXITY_TOLERANCE: f64 = 1e-3;
+
+/// Convex hull of one or more solids.
+pub fn solid_hull(solids: &[TruckSolid]) -> GeomResult<TruckSolid> {
+    if solids.is_empty() {
+        return Err(invalid("hull requires at least one solid".to_string()));
+    }
+    let mut points = Vec::new();
+    for (i, solid) in solids.iter().enumerate() {
+        let mesh = crate::tessellate::mesh_solid(solid, HULL_TOLERANCE);
+        if mesh.positions().is_empty() {
+            return Err(invalid(format!("solid {i} is empty")));
+        }
+        points.extend_from_slice(mesh.positions());
+    }
+    let hull = convex_hull(&points)?;
+    crate::polyhedron::solid_from_triangles(&points, &hull.triangles)
+}
+
+/// Minkowski sum of two convex solids: `a` grown by every point of `b`.
+pub fn solid_minkowski(a: &TruckSolid, b: &TruckSolid) -> GeomResult<TruckSolid> {
+    let a = convex_vertices(a, "first")?;
+    let b = convex_vertices(b, "second")?;
+    let sums: Vec<_> = a
+        .iter()
+        .flat_map(|p| b.iter().map(move |q| *p + q.to_vec()))
+        .collect();
+    let hull = convex_hull(&sums)?;
+    crate::polyhedron::solid_from_triangles(&sums, &hull.triangles)
+}
+
+/// Vertices of a solid's hull, after checking the solid is convex.
+fn convex_vertices(solid: &TruckSolid, which: &str) -> GeomResult<Vec<Point3>> {
+    let mesh = crate::tessellate::mesh_solid(solid, HULL_TOLERANCE);
+    let positions = mesh.positions();
+    if positions.is_empty() {
+        return Err(invalid(format!("{which} minkowski operand is empty")));
+    }
+    let volume: f64 = mesh
+        .face_iter()
+        .flat_map(|face| {
+            let a = positions[face[0].pos].to_vec();
+            (1..face.len() - 1).map(move |i| {
+                let (b, c) = (positions[face[i].pos], positions[face[i + 1].pos]);
+                a.dot(b.to_vec().cross(c.to_vec())) / 6.0
+            })
+        })
+        .sum();
+    let hull = convex_hull(positions)?;
+    let hull_volume = hull.volume(positions);
+    if volume < hull_volume * (1.0 - CONVEXITY_TOLERANCE) {
+        return Err(GeomError::new(
+            GeomErrorKind::InvalidInput,
+            format!(
+                "minkowski supports convex solids only; the {which} operand fills {:.1}% of its hull",
+                100.0 * volume / hull_volume
+            ),
+        ));
+    }
+    Ok(hull.vertices().into_iter().map(|i| positions[i]).collect())
+}
+
+/// A closed triangulated hull, wound counter-clockwise seen from outside.
+struct Hull {
+    triangles: Vec<[usize; 3]>,
+}
+
+impl Hull {
+    fn vertices(&self) -> Vec<usize> {
+        let mut vertices: Vec<_> = self.triangles.iter().flatten().copied().collect();
+        vertices.sort_unstable();
+        vertices.dedup();
+        vertices
+    }
+
+    fn volume(&self, points: &[Point3]) -> f64 {
+        self.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| points[i].to_vec());
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
+}
+
+/// A quickhull face: a triangle with its outward plane and the points that
+/// lie above it and have not been processed yet.
+struct HullFace {
+    vertices: [usize; 3],
+    normal: Vector3,
+    offset: f64,
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl HullFace {
+    fn new(points: &[Point3], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|i| points[i]);
+        let normal = (b - a).cross(c - a).normalize();
+        Self {
+            vertices,
+            normal,
+            offset: normal.dot(a.to_vec()),
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+
+    fn distance(&self, p: Point3) -> f64 {
+        self.normal.dot(p.to_vec()) - self.offset
+    }
+}
+
+/// Convex hull of a point cloud by quickhull.
+fn convex_hull(points: &[Point3]) -> GeomResult<Hull> {
+    let scale = points
+        .iter()
+        .map(|p| p.x.abs().max(p.y.abs()).max(p.z.abs()))
+        .fold(0.0, f64::max)
+        .max(1.0);
+    let eps = scale * 1e-9;
+    let flat = || invalid("hull of the input is flat; it has no volume".to_string());
+
+    // Initial tetrahedron from extreme points.
+    let extremes: Vec<usize> = (0..3)
+        .flat_map(|axis| {
+            let by_axis = |&i: &usize, &j: &usize| points[i][axis].total_cmp(&points[j][axis]);
+            let all = 0..points.len();
+            [all.clone().min_by(by_axis), all.max_by(by_axis)]
+        })
+        .flatten()
+        .collect();
+    let (mut p0, mut p1, mut best) = (0, 0, -1.0);
+    for &i in &extremes {
+        for &j in &extremes {
+            let d = (points[i] - points[j]).magnitude2();
+            if d > best {
+                (p0, p1, best) = (i, j, d);
+            }
+        }
+    }
+    if best <= eps * eps {
+        return Err(flat());
+    }
+    let axis = (points[p1] - points[p0]).normalize();
+    let p2 = farthest(points, |p| {
+        let v = p - points[p0];
+        (v - axis * v.dot(axis)).magnitude()
+    });
+    let normal = (points[p1] - points[p0]).cross(points[p2] - points[p0]);
+    if normal.magnitude() <= eps * scale {
+        return Err(flat());
+    }
+    let normal = normal.normalize();
+    let p3 = farthest(points, |p| (p - points[p0]).dot(normal).abs());
+    let lift = (points[p3] - points[p0]).dot(normal);
+    if lift.abs() <= eps {
+        return Err(flat());
+    }
+
+    let mut faces = if lift < 0.0 {
+        vec![[p0, p1, p2], [p0, p3, p1], [p1, p3, p2], [p2, p3, p0]]
+    } else {
+        vec![[p0, p2, p1], [p0, p1, p3], [p1, p2, p3], [p2, p0, p3]]
+    }
+    .into_iter()
+    .map(|v| HullFace::new(points, v))
+    .collect::<Vec<_>>();
+    let seeds = [p0, p1, p2, p3];
+    assign(
+        points,
+        &mut faces,
+        0,
+        (0..points.len()).filter(|i| !seeds.contains(i)),
+        eps,
+    );
+    // Directed edge → the face it bounds (counter-clockwise from outside).
+    let mut owner: HashMap<(usize, usize), usize> = HashMap::new();
+    for (i, face) in faces.iter().enumerate() {
+        for edge in face.edges() {
+            owner.insert(edge, i);
+        }
+    }
+
+    while let Some(f) = faces.iter().position(|f| f.alive && !f.outside.is_empty()) {
+        let eye_index = farthest_in(points, &faces[f]);
+        let eye = points[eye_index];
+
+        // Grow the region of faces the eye sees from `f`; its rim is the
+        // horizon the new faces are fanned from.
+        let mut visible = vec![f];
+        let mut seen = HashSet::from([f]);
+        let mut horizon = Vec::new();
+        let mut k = 0;
+        while k < visible.len() {
+            for (a, b) in faces[visible[k]].edges() {
+                let n = owner[&(b, a)];
+                if seen.contains(&n) {
+                    continue;
+                }
+                if faces[n].distance(eye) > eps {
+                    seen.insert(n);
+                    visible.push(n);
+                } else {
+                    horizon.push((a, b));
+                }
+            }
+            k += 1;
+        }
+
+        let mut orphans = Vec::new();
+        for &i in &visible {
+            faces[i].alive = false;
+            orphans.append(&mut faces[i].outside);
+        }
+        let first_new = faces.len();
+        for &(a, b) in &horizon {
+            let face = HullFace::new(points, [a, b, eye_index]);
+            for edge in face.edges() {
+                owner.insert(edge, faces.len());
+            }
+            faces.push(face);
+        }
+        assign(
+            points,
+            &mut faces,
+            first_new,
+            orphans.into_iter().filter(|&i| i != eye_index),
+            eps,
+        );
+    }
+
+    Ok(Hull {
+        triangles: faces
+            .into_iter()
+            .filter(|f| f.alive)
+            .map(|f| f.vertices)
+            .collect(),
+    })
+}
+
+/// Give each point to the first face from `first` it lies above, if any.
+fn assign(
+    points: &[Point3],
+    faces: &mut [HullFace],
+    first: usize,
+    candidates: impl Iterator<Item = usize>,
+    eps: f64,
+) {
+    for i in candidates {
+        if let Some(face) = faces[first..]
+            .iter_mut()
+            .find(|f| f.alive && f.distance(points[i]) > eps)
+        {
+            face.outside.push(i);
+        }
+    }
+}
+
+fn farthest(points: &[Point3], measure: impl Fn(Point3) -> f64) -> usize {
+    (0..points.len())
+        .max_by(|&i, &j| measure(points[i]).total_cmp(&measure(points[j])))
+        .expect("at least one point")
+}
+
+fn farthest_in(points: &[Point3], face: &HullFace) -> usize {
+    face.outside
+        .iter()
+        .copied()
+        .max_by(|&i, &j| {
+            face.distance(points[i])
+                .total_cmp(&face.distance(points[j]))
+        })
+        .expect("outside set is not empty")
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mass::mass_properties;
+    use crate::primitives::{make_box, make_cylinder, make_sphere};
+    use std::f64::consts::PI;
+    use truck_modeling::builder;
+
+    fn volume(solid: &TruckSolid) -> f64 {
+        mass_properties(solid, 1.0, 0.01).unwrap().volume
+    }
+
+    /// A 10 mm cube with a square hole through it.
+    fn pierced_box() -> TruckSolid {
+        let cutter = builder::translated(&make_box(5.0, 5.0, 20.0), Vector3::new(2.5, 2.5, -5.0));
+        crate::boolean::solid_difference(&make_box(10.0, 10.0, 10.0), &cutter, 0.05).unwrap()
+    }
+
+    #[test]
+    fn hull_of_a_box_is_the_box() {
+        let hull = solid_hull(&[make_box(2.0, 3.0, 4.0)]).unwrap();
+        assert_eq!(hull.boundaries()[0].len(), 6);
+        assert!((volume(&hull) - 24.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hull_bridges_separate_solids() {
+        let a = make_box(2.0, 2.0, 2.0);
+        let b = builder::translated(&make_box(2.0, 2.0, 2.0), Vector3::new(8.0, 0.0, 0.0));
+        let hull = solid_hull(&[a, b]).unwrap();
+        assert_eq!(hull.boundaries()[0].len(), 6);
+        assert!((volume(&hull) - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hull_of_a_non_convex_solid_fills_it() {
+        let tube = pierced_box();
+        let hull = solid_hull(&[tube]).unwrap();
+        assert_eq!(hull.boundaries()[0].len(), 6);
+        assert!((volume(&hull) - 1000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn hull_of_a_cylinder_keeps_flat_caps() {
+        let hull = solid_hull(&[make_cylinder(2.0, 5.0)]).unwrap();
+        let v = volume(&hull);
+        assert!(v < PI * 4.0 * 5.0 && v > PI * 4.0 * 5.0 * 0.98, "{v}");
+    }
+
+    #[test]
+    fn minkowski_of_boxes_adds_sizes() {
+        let a = make_box(2.0, 3.0, 4.0);
+        let b = builder::translated(&make_box(1.0, 1.0, 1.0), Vector3::new(-0.5, -0.5, -0.5));
+        let sum = solid_minkowski(&a, &b).unwrap();
+        assert_eq!(sum.boundaries()[0].len(), 6);
+        assert!((volume(&sum) - 3.0 * 4.0 * 5.0).abs() < 1e-9);
+        let bounds = crate::query::bounding_box(&sum).unwrap();
+        assert_eq!(bounds.min, Point3::new(-0.5, -0.5, -0.5));
+    }
+
+    #[test]
+    fn minkowski_with_a_sphere_rounds_a_box() {
+        let sum = solid_minkowski(&make_box(4.0, 4.0, 4.0), &make_sphere(1.0)).unwrap();
+        // Box + slabs on faces + quarter cylinders on edges + sphere.
+        let exact = 64.0 + 6.0 * 16.0 + 12.0 * PI * 4.0 / 4.0 + 4.0 / 3.0 * PI;
+        let v = volume(&sum);
+        assert!(v < exact && v > exact * 0.97, "{v} vs {exact}");
+    }
+
+    #[test]
+    fn minkowski_rejects_non_convex_operands() {
+        let err = solid_minkowski(&make_sphere(1.0), &pierced_box()).unwrap_err();
+        assert!(err.message.contains("convex"), "{}", err.message);
+    }
+
+    #[test]
+    fn rejects_empty_and_flat_input() {
+        assert!(solid_hull(&[]).is_err());
+        let flat =
+            [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)].map(|(x, y)| Point3::new(x, y, 0.0));
+        assert!(convex_hull(&flat).is_err());
+    }
+}
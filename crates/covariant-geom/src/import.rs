@@ -0,0 +1,299 @@
+//! Mesh import (STL, OBJ) and conversion of closed meshes to solids.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use truck_meshalgo::filters::OptimizingFilter;
+use truck_modeling::{EuclideanSpace, InnerSpace, Point3};
+use truck_polymesh::stl::StlType;
+use truck_polymesh::{Faces, PolygonMesh, StandardAttributes};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::TruckSolid;
+
+/// Tolerance, relative to the mesh bounding box, for welding the separate
+/// corners STL stores for every triangle.
+const WELD_TOLERANCE: f64 = 1e-9;
+
+/// Read an STL file (binary or ASCII).
+pub fn read_stl(path: &Path) -> GeomResult<PolygonMesh> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        GeomError::new(
+            GeomErrorKind::IoError,
+            format!("failed to read STL file {}: {e}", path.display()),
+        )
+    })?;
+    // Binary files may also start with "solid", so trust the size check.
+    let binary = bytes.len() >= 84 && {
+        let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+        bytes.len() == 84 + 50 * count
+    };
+    let stl_type = if binary {
+        StlType::Binary
+    } else {
+        StlType::Ascii
+    };
+    let mesh = truck_polymesh::stl::read(&bytes[..], stl_type).map_err(|e| {
+        GeomError::new(
+            GeomErrorKind::InvalidInput,
+            format!("failed to parse STL file {}: {e}", path.display()),
+        )
+    })?;
+    non_empty(mesh, path)
+}
+
+/// Read the vertices and faces of a Wavefront OBJ file.
+///
+/// Texture coordinates, normals, groups and materials are ignored;
+/// negative (relative) indices are supported.
+pub fn read_obj(path: &Path) -> GeomResult<PolygonMesh> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        GeomError::new(
+            GeomErrorKind::IoError,
+            format!("failed to read OBJ file {}: {e}", path.display()),
+        )
+    })?;
+    let parse_error = |line: usize, what: &str| {
+        GeomError::new(
+            GeomErrorKind::InvalidInput,
+            format!("{}:{line}: {what}", path.display()),
+        )
+    };
+
+    let mut positions = Vec::new();
+    let mut faces = Faces::default();
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let mut fields = line.split('#').next().unwrap_or("").split_whitespace();
+        match fields.next() {
+            Some("v") => {
+                let coords = fields
+                    .take(3)
+                    .map(|f| f.parse::<f64>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| parse_error(line_no, &format!("bad vertex coordinate: {e}")))?;
+                let [x, y, z] = coords[..] else {
+                    return Err(parse_error(line_no, "vertex needs three coordinates"));
+                };
+                positions.push(Point3::new(x, y, z));
+            }
+            Some("f") => {
+                let face = fields
+                    .map(|f| {
+                        let index = f.split('/').next().unwrap_or("");
+                        let index: i64 = index
+                            .parse()
+                            .map_err(|_| parse_error(line_no, &format!("bad face index '{f}'")))?;
+                        let resolved = match index {
+                            0 => None,
+                            i if i > 0 => Some(i - 1),
+                            i => Some(positions.len() as i64 + i),
+                        };
+                        resolved
+                            .filter(|&i| (0..positions.len() as i64).contains(&i))
+                            .map(|i| i as usize)
+                            .ok_or_else(|| {
+                                parse_error(line_no, &format!("face index {index} is out of range"))
+                            })
+                    })
+                    .collect::<GeomResult<Vec<usize>>>()?;
+                if face.len() < 3 {
+                    return Err(parse_error(line_no, "face needs at least three vertices"));
+                }
+                faces.push(&face[..]);
+            }
+            _ => {}
+        }
+    }
+    let mesh = PolygonMesh::new(
+        StandardAttributes {
+            positions,
+            ..Default::default()
+        },
+        faces,
+    );
+    non_empty(mesh, path)
+}
+
+/// Convert a closed triangle mesh into a polyhedral solid.
+///
+/// Coincident vertices are welded, coplanar neighbouring triangles become
+/// one planar face, and a mesh wound inside out is flipped. Every edge must
+/// be shared by exactly two faces that traverse it in opposite directions.
+pub fn mesh_to_solid(mesh: &PolygonMesh) -> GeomResult<TruckSolid> {
+    let mut mesh = mesh.clone();
+    mesh.put_together_same_attrs(WELD_TOLERANCE);
+    let points = mesh.positions();
+    let mut triangles: Vec<[usize; 3]> = mesh
+        .face_iter()
+        .flat_map(|face| {
+            (1..face.len() - 1).map(move |i| [face[0].pos, face[i].pos, face[i + 1].pos])
+        })
+        .filter(|t| {
+            let [a, b, c] = t.map(|i| points[i]);
+            (b - a).cross(c - a).magnitude2() > 0.0
+        })
+        .collect();
+    if triangles.is_empty() {
+        return Err(invalid("mesh has no triangles".to_string()));
+    }
+
+    let mut uses: HashMap<(usize, usize), usize> = HashMap::new();
+    for t in &triangles {
+        for k in 0..3 {
+            *uses.entry((t[k], t[(k + 1) % 3])).or_default() += 1;
+        }
+    }
+    for (&(a, b), &count) in &uses {
+        let twin = uses.get(&(b, a)).copied().unwrap_or(0);
+        if count > 1 || twin > 1 {
+            return Err(invalid(format!(
+                "mesh is not manifold or not consistently oriented at the edge from {} to {}",
+                fmt_point(points[a]),
+                fmt_point(points[b])
+            )));
+        }
+        if twin == 0 {
+            return Err(invalid(format!(
+                "mesh is not closed; the edge from {} to {} borders only one face",
+                fmt_point(points[a]),
+                fmt_point(points[b])
+            )));
+        }
+    }
+
+    let volume: f64 = triangles
+        .iter()
+        .map(|t| {
+            let [a, b, c] = t.map(|i| points[i].to_vec());
+            a.dot(b.cross(c))
+        })
+        .sum();
+    if volume < 0.0 {
+        for t in &mut triangles {
+            t.swap(1, 2);
+        }
+    }
+    crate::polyhedron::solid_from_triangles(points, &triangles)
+}
+
+fn non_empty(mesh: PolygonMesh, path: &Path) -> GeomResult<PolygonMesh> {
+    if mesh.faces().is_empty() {
+        return Err(invalid(format!("{} contains no faces", path.display())));
+    }
+    Ok(mesh)
+}
+
+fn fmt_point(p: Point3) -> String {
+    format!("({}, {}, {})", p.x, p.y, p.z)
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mass::mass_properties;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::tessellate::{mesh_solid, write_stl, write_stl_ascii};
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("covariant_import_{name}"))
+    }
+
+    const CUBE_OBJ: &str = "# unit cube\n\
+        v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nv 0 0 1\nv 1 0 1\nv 0 1 1\nv 1 1 1\n\
+        f 1 3 4 2\nf 5 6 8 7\nf 1/1 2/2 6/3 5/4\nf 3 7 8 4\nf 1 5 7 3\nf -7 -5 -1 -3\n";
+
+    #[test]
+    fn stl_round_trip_becomes_a_solid() {
+        let cube = make_box(10.0, 20.0, 5.0);
+        for (name, write) in [
+            (
+                "binary.stl",
+                write_stl as fn(&PolygonMesh, &Path) -> GeomResult<()>,
+            ),
+            ("ascii.stl", write_stl_ascii),
+        ] {
+            let path = temp_path(name);
+            write(&mesh_solid(&cube, 0.1), &path).unwrap();
+            let mesh = read_stl(&path).unwrap();
+            std::fs::remove_file(&path).ok();
+            let solid = mesh_to_solid(&mesh).unwrap();
+            assert_eq!(solid.boundaries()[0].len(), 6, "{name}");
+            let props = mass_properties(&solid, 1.0, 0.01).unwrap();
+            assert!(
+                (props.volume - 1000.0).abs() < 1e-6,
+                "{name}: {}",
+                props.volume
+            );
+        }
+    }
+
+    #[test]
+    fn curved_mesh_keeps_flat_caps() {
+        let path = temp_path("cylinder.stl");
+        write_stl(&mesh_solid(&make_cylinder(5.0, 10.0), 0.05), &path).unwrap();
+        let mesh = read_stl(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let solid = mesh_to_solid(&mesh).unwrap();
+        let volume = mass_properties(&solid, 1.0, 0.01).unwrap().volume;
+        let exact = std::f64::consts::PI * 25.0 * 10.0;
+        assert!(volume < exact && volume > 0.99 * exact, "{volume}");
+    }
+
+    #[test]
+    fn obj_quads_and_relative_indices() {
+        let path = temp_path("cube.obj");
+        std::fs::write(&path, CUBE_OBJ).unwrap();
+        let mesh = read_obj(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(mesh.faces().len(), 6);
+        let solid = mesh_to_solid(&mesh).unwrap();
+        assert_eq!(solid.boundaries()[0].len(), 6);
+        assert!((mass_properties(&solid, 1.0, 0.01).unwrap().volume - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn inside_out_mesh_is_flipped() {
+        let path = temp_path("inverted.obj");
+        let inverted: String = CUBE_OBJ
+            .lines()
+            .map(|l| match l.strip_prefix("f ") {
+                Some(rest) => format!(
+                    "f {}\n",
+                    rest.split(' ').rev().collect::<Vec<_>>().join(" ")
+                ),
+                None => format!("{l}\n"),
+            })
+            .collect();
+        std::fs::write(&path, inverted).unwrap();
+        let mesh = read_obj(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let solid = mesh_to_solid(&mesh).unwrap();
+        assert!((mass_properties(&solid, 1.0, 0.01).unwrap().volume - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_open_meshes_and_bad_files() {
+        let path = temp_path("open.obj");
+        let open: String = CUBE_OBJ
+            .lines()
+            .take(14)
+            .map(|l| format!("{l}\n"))
+            .collect();
+        std::fs::write(&path, open).unwrap();
+        let err = mesh_to_solid(&read_obj(&path).unwrap()).unwrap_err();
+        assert!(err.message.contains("not closed"), "{}", err.message);
+
+        std::fs::write(&path, "v 0 0 0\nv 1 0 0\nf 1 2 3\n").unwrap();
+        let err = read_obj(&path).unwrap_err();
+        assert!(err.message.contains(":3:"), "{}", err.message);
+        std::fs::remove_file(&path).ok();
+
+        let err = read_stl(&temp_path("missing.stl")).unwrap_err();
+        assert_eq!(err.kind, GeomErrorKind::IoError);
+    }
+}
@@ -1,6 +1,10 @@
 //! The `GeomKernel` trait — geometry kernel abstraction.
 
-use crate::{Face, GeomResult, Mesh, Point3, Solid, Vector3};
+use crate::{
+    BoundingBox, EdgeSelector, Face, GeomResult, MassProperties, Matrix4, Mesh, Point2, Point3,
+    Solid, SweepFrame, Vector3, Wire,
+};
+use covariant_thread::HelixParams;
 use std::path::Path;
 
 /// Abstraction over a geometry kernel that provides solid modeling operations.
@@ -18,6 +22,51 @@ pub trait GeomKernel {
     /// Create a sphere centered at the origin.
     fn sphere(&self, radius: f64) -> Solid;
 
+    // ── Planar profiles ─────────────────────────────────────────────────
+
+    /// Create a circular profile in the XY plane.
+    fn circle_profile(&self, center: Point2, radius: f64) -> GeomResult<Face>;
+
+    /// Create a rectangular profile in the XY plane spanning `origin` to
+    /// `origin + (width, height)`.
+    fn rectangle_profile(&self, origin: Point2, width: f64, height: f64) -> GeomResult<Face>;
+
+    /// Create a polygonal profile in the XY plane from a closed point loop.
+    fn polygon_profile(&self, points: &[Point2]) -> GeomResult<Face>;
+
+    /// Cut the outer boundaries of `holes` out of a planar profile.
+    fn profile_with_holes(&self, outer: &Face, holes: &[Face]) -> GeomResult<Face>;
+
+    // ── Curves ──────────────────────────────────────────────────────────
+
+    /// Create a straight line segment.
+    fn line_curve(&self, p0: Point3, p1: Point3) -> GeomResult<Wire>;
+
+    /// Create a circular arc in the XY plane between two angles (radians).
+    fn arc_curve(
+        &self,
+        center: Point3,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    ) -> GeomResult<Wire>;
+
+    /// Create a full circle in the plane perpendicular to `normal`.
+    fn circle_curve(&self, center: Point3, radius: f64, normal: Vector3) -> GeomResult<Wire>;
+
+    /// Create a helix around the Z axis starting at `(radius, 0, 0)`.
+    fn helix_curve(
+        &self,
+        radius: f64,
+        pitch: f64,
+        height: f64,
+        left_handed: bool,
+    ) -> GeomResult<Wire>;
+
+    /// Create a smooth curve through a sequence of points; closed if the
+    /// last point equals the first.
+    fn interpolated_curve(&self, points: &[Point3]) -> GeomResult<Wire>;
+
     // ── Boolean operations ──────────────────────────────────────────────
 
     /// Boolean union of two solids.
@@ -29,20 +78,29 @@ pub trait GeomKernel {
     /// Boolean intersection of two solids.
     fn intersection(&self, a: &Solid, b: &Solid) -> GeomResult<Solid>;
 
-    /// Union of many solids (default: left fold).
+    /// Union of many solids (default: balanced pairwise reduction, so no
+    /// step unions against the whole accumulated result).
     fn union_many(&self, solids: &[Solid]) -> GeomResult<Solid> {
-        solids
-            .split_first()
-            .ok_or_else(|| {
-                crate::GeomError::new(
-                    crate::GeomErrorKind::InvalidInput,
-                    "union_many requires at least one solid",
-                )
-            })
-            .and_then(|(first, rest)| {
-                rest.iter()
-                    .try_fold(first.clone(), |acc, s| self.union(&acc, s))
-            })
+        match solids {
+            [] => Err(crate::GeomError::new(
+                crate::GeomErrorKind::InvalidInput,
+                "union_many requires at least one solid",
+            )),
+            [single] => Ok(single.clone()),
+            _ => {
+                let (left, right) = solids.split_at(solids.len() / 2);
+                self.union(&self.union_many(left)?, &self.union_many(right)?)
+            }
+        }
+    }
+
+    /// `base` minus every solid in `cutters`, which are unioned first so
+    /// the base is cut once. No cutters returns `base` unchanged.
+    fn difference_many(&self, base: &Solid, cutters: &[Solid]) -> GeomResult<Solid> {
+        if cutters.is_empty() {
+            return Ok(base.clone());
+        }
+        self.difference(base, &self.union_many(cutters)?)
     }
 
     // ── Transformations ─────────────────────────────────────────────────
@@ -53,25 +111,127 @@ pub trait GeomKernel {
     /// Rotate a solid around an axis through `origin`.
     fn rotate(&self, solid: &Solid, origin: Point3, axis: Vector3, angle_rad: f64) -> Solid;
 
-    /// Uniformly scale a solid about `center`.
-    fn scale(&self, solid: &Solid, center: Point3, factor: f64) -> Solid;
+    /// Scale a solid about `center` by per-axis `factors`.
+    ///
+    /// Negative factors mirror the solid; a zero factor is an error.
+    fn scale(&self, solid: &Solid, center: Point3, factors: Vector3) -> GeomResult<Solid>;
+
+    /// Apply a 4×4 affine matrix (column vectors, translation in the last
+    /// column). Fails for singular or non-affine matrices.
+    fn transform(&self, solid: &Solid, matrix: Matrix4) -> GeomResult<Solid>;
 
     /// Mirror a solid across a plane defined by `origin` and `normal`.
     fn mirror(&self, solid: &Solid, origin: Point3, normal: Vector3) -> Solid;
 
+    /// Translate a planar profile.
+    fn translate_profile(&self, profile: &Face, v: Vector3) -> Face;
+
+    /// Rotate a planar profile around an axis through `origin`.
+    fn rotate_profile(&self, profile: &Face, origin: Point3, axis: Vector3, angle_rad: f64)
+        -> Face;
+
     // ── Sweep / revolve ─────────────────────────────────────────────────
 
     /// Translational sweep of a face along a direction vector.
-    fn sweep(&self, profile: &Face, direction: Vector3) -> Solid;
+    ///
+    /// Fails if the direction is zero or parallel to the profile plane.
+    fn sweep(&self, profile: &Face, direction: Vector3) -> GeomResult<Solid>;
+
+    /// Extrude a face along its normal; negative distances go the other way.
+    fn extrude(&self, profile: &Face, distance: f64) -> GeomResult<Solid>;
 
     /// Rotational sweep (revolve) of a face around an axis.
+    ///
+    /// Angles of a full turn or more give a closed solid of revolution.
+    /// Fails if the axis passes through the profile.
     fn revolve(
         &self,
         profile: &Face,
         origin: Point3,
         axis: Vector3,
         angle_rad: f64,
-    ) -> Solid;
+    ) -> GeomResult<Solid>;
+
+    /// Sweep a face along a path, keeping its orientation relative to the
+    /// path according to `frame`.
+    fn sweep_along(&self, profile: &Face, path: &Wire, frame: SweepFrame) -> GeomResult<Solid>;
+
+    /// Loft a solid through planar sections (without holes), in order.
+    ///
+    /// `ruled` joins consecutive sections with ruled surfaces; otherwise
+    /// the lateral surface passes smoothly through all of them.
+    fn loft(&self, sections: &[Face], ruled: bool) -> GeomResult<Solid>;
+
+    // ── Edge treatment ──────────────────────────────────────────────────
+
+    /// Round the selected edges of a solid with the given radius.
+    ///
+    /// Supports straight edges between planar faces; blends that meet at a
+    /// vertex are mitered or closed with a spherical corner.
+    fn fillet(&self, solid: &Solid, radius: f64, edges: &EdgeSelector) -> GeomResult<Solid>;
+
+    /// Bevel the selected edges of a solid, cutting `distance` back along
+    /// both adjacent faces.
+    fn chamfer(&self, solid: &Solid, distance: f64, edges: &EdgeSelector) -> GeomResult<Solid>;
+
+    // ── Offsets ─────────────────────────────────────────────────────────
+
+    /// Hollow a solid, leaving walls of `thickness` inside its boundary.
+    ///
+    /// Planar faces whose outward normal points along one of `open_faces`
+    /// are removed; with none, the cavity is an enclosed void.
+    fn shell(&self, solid: &Solid, thickness: f64, open_faces: &[Vector3]) -> GeomResult<Solid>;
+
+    /// Grow (positive) or shrink (negative) a solid by moving every face
+    /// `distance` along its normal, keeping sharp edges sharp.
+    fn offset(&self, solid: &Solid, distance: f64) -> GeomResult<Solid>;
+
+    // ── Hulls ───────────────────────────────────────────────────────────
+
+    /// Convex hull of one or more solids, built from their tessellation.
+    fn hull(&self, solids: &[Solid]) -> GeomResult<Solid>;
+
+    /// Minkowski sum of two convex solids (`a` swept over every point of
+    /// `b`). Non-convex operands are an error.
+    fn minkowski(&self, a: &Solid, b: &Solid) -> GeomResult<Solid>;
+
+    // ── Threads ─────────────────────────────────────────────────────────
+
+    /// Helical thread with the ISO 68-1 / UTS basic profile along +Z from
+    /// the origin to `helix.depth`. It is the external thread as is, and
+    /// cuts the matching internal thread when subtracted from a part.
+    fn thread(&self, helix: &HelixParams) -> GeomResult<Solid>;
+
+    // ── Sections ────────────────────────────────────────────────────────
+
+    /// Cross-section of a solid by the plane through `origin` with
+    /// `normal`, as planar faces (with holes) lying in that plane. A plane
+    /// that misses the solid gives no faces.
+    fn section(&self, solid: &Solid, origin: Point3, normal: Vector3) -> GeomResult<Vec<Face>>;
+
+    /// Silhouette of a solid projected along `normal` onto the plane
+    /// through `origin`, as planar faces (with holes) lying in that plane.
+    fn project(&self, solid: &Solid, origin: Point3, normal: Vector3) -> GeomResult<Vec<Face>>;
+
+    // ── Analysis ────────────────────────────────────────────────────────
+
+    /// Volume, surface area, centroid and inertia of a solid with uniform
+    /// `density`, integrated over a tessellation at `tolerance`.
+    fn mass_properties(
+        &self,
+        solid: &Solid,
+        density: f64,
+        tolerance: f64,
+    ) -> GeomResult<MassProperties>;
+
+    /// Axis-aligned bounding box of a solid.
+    fn bounding_box(&self, solid: &Solid) -> GeomResult<BoundingBox>;
+
+    /// Shortest distance between two solids; zero if they touch or overlap.
+    fn closest_distance(&self, a: &Solid, b: &Solid) -> GeomResult<f64>;
+
+    /// Whether a point lies inside a solid or on its boundary.
+    fn contains_point(&self, solid: &Solid, point: Point3) -> GeomResult<bool>;
 
     // ── Tessellation / export ───────────────────────────────────────────
 
@@ -83,4 +243,32 @@ pub trait GeomKernel {
 
     /// Export a mesh to an STL file (ASCII format).
     fn export_stl_ascii(&self, mesh: &Mesh, path: &Path) -> GeomResult<()>;
+
+    /// Split a solid into its disconnected bodies, each with the voids
+    /// inside it.
+    fn bodies(&self, solid: &Solid) -> GeomResult<Vec<Solid>>;
+
+    /// Export named bodies as one product to a STEP AP214 file, with exact
+    /// geometry in millimetres.
+    fn export_step(&self, product: &str, bodies: &[(String, Solid)], path: &Path)
+        -> GeomResult<()>;
+
+    // ── Mesh import ─────────────────────────────────────────────────────
+
+    /// Read a mesh from an STL file (binary or ASCII).
+    fn import_stl(&self, path: &Path) -> GeomResult<Mesh>;
+
+    /// Read a mesh from a Wavefront OBJ file.
+    fn import_obj(&self, path: &Path) -> GeomResult<Mesh>;
+
+    /// Convert a closed, manifold mesh into a solid with planar faces.
+    fn mesh_to_solid(&self, mesh: &Mesh) -> GeomResult<Solid>;
+
+    // ── Native format ───────────────────────────────────────────────────
+
+    /// Save a solid losslessly in the versioned native B-rep format.
+    fn save_solid(&self, solid: &Solid, path: &Path) -> GeomResult<()>;
+
+    /// Load a solid saved with [`save_solid`](Self::save_solid).
+    fn load_solid(&self, path: &Path) -> GeomResult<Solid>;
 }
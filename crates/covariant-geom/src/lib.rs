@@ -1,19 +1,39 @@
 //! Geometry kernel abstraction for COVARIANT.
 //!
-//! Provides primitives, boolean operations, transformations, sweep/revolve,
-//! tessellation, and STL export via the **truck** B-rep kernel.
+//! Provides primitives, planar profiles, curves, boolean operations, transformations,
+//! sweeps, hulls, sections, mass properties, tessellation, mesh import, helical threads, STL and STEP export, and a native B-rep file format via the **truck** B-rep kernel.
 
+pub mod blend;
 pub mod boolean;
+pub mod curve;
 pub mod error;
+pub mod hull;
+pub mod import;
 pub mod kernel;
+pub mod loft;
+pub mod mass;
+pub mod native;
+pub mod offset;
+pub mod polyhedron;
 pub mod primitives;
+pub mod query;
+pub mod section;
+pub mod sketch;
+pub mod step;
 pub mod sweep;
 pub mod tessellate;
+pub mod thread;
 pub mod transform;
 pub mod truck_kernel;
 pub mod types;
 
+pub use blend::EdgeSelector;
 pub use error::{GeomError, GeomErrorKind, GeomResult};
 pub use kernel::GeomKernel;
+pub use mass::MassProperties;
+pub use query::BoundingBox;
+pub use sweep::SweepFrame;
 pub use truck_kernel::TruckKernel;
-pub use types::{Edge, Face, Mesh, Point3, Solid, Vector3, Wire, DEFAULT_TOLERANCE};
+pub use types::{
+    Edge, Face, Matrix4, Mesh, Point2, Point3, Solid, Vector3, Wire, DEFAULT_TOLERANCE,
+};
@@ -0,0 +1,515 @@
+//! Loft: a solid through an ordered list of planar sections.
+//!
+//! Sections may have different shapes and edge counts. Each section's
+//! boundary is re-split at common arc-length fractions so that all sections
+//! have matching edges, with section starts rotated to minimise twist. The
+//! lateral surface is then either ruled between consecutive sections or a
+//! single smooth (C1, Catmull–Rom) surface per edge through all of them.
+
+use truck_modeling::{
+    builder, BSplineCurve, BSplineSurface, BoundedCurve, Curve, Cut, Edge, EuclideanSpace,
+    InnerSpace, Invertible, KnotVec, MetricSpace, ParametricCurve, Point3, Shell, Surface,
+    Vector3, Vertex,
+};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::{TruckFace, TruckSolid, TruckWire};
+
+/// Chord samples per edge used to measure arc length.
+const LENGTH_SAMPLES: usize = 32;
+
+/// Candidate start positions tried when aligning consecutive sections.
+const ALIGN_SAMPLES: usize = 64;
+
+/// Breakpoints closer than this (as a fraction of the perimeter) are merged.
+const FRACTION_TOLERANCE: f64 = 1e-6;
+
+/// Positional tolerance when approximating rational section edges by
+/// cubic B-splines for smooth lofts.
+const APPROX_TOLERANCE: f64 = 1e-4;
+
+/// Loft a solid through `sections`, in order.
+///
+/// Sections must be planar faces without holes. With `ruled`, consecutive
+/// sections are joined by ruled surfaces; otherwise the lateral surface
+/// passes smoothly through every section.
+pub fn solid_loft(sections: &[TruckFace], ruled: bool) -> GeomResult<TruckSolid> {
+    if sections.len() < 2 {
+        return Err(invalid(format!(
+            "loft needs at least 2 sections, got {}",
+            sections.len()
+        )));
+    }
+    let profiles = sections
+        .iter()
+        .enumerate()
+        .map(|(i, face)| Profile::new(face, i))
+        .collect::<GeomResult<Vec<_>>>()?;
+    let profiles = orient_profiles(profiles)?;
+    let offsets = align_starts(&profiles);
+    let breaks = common_breaks(&profiles, &offsets);
+    let split: Vec<Vec<Curve>> = profiles
+        .iter()
+        .zip(&offsets)
+        .map(|(p, &s)| p.split(&breaks, s))
+        .collect();
+
+    let shell = if ruled {
+        ruled_shell(&split)?
+    } else {
+        smooth_shell(&split)?
+    };
+    TruckSolid::try_new(vec![shell])
+        .map_err(|e| invalid(format!("loft did not produce a closed solid: {e}")))
+}
+
+/// A section boundary measured by arc length.
+struct Profile {
+    /// Oriented edge curves of the outer boundary, in order.
+    curves: Vec<Curve>,
+    /// For each curve, sampled `(parameter, perimeter fraction)` pairs.
+    tables: Vec<Vec<(f64, f64)>>,
+    /// Perimeter fractions at which each curve starts, plus a final 1.0.
+    starts: Vec<f64>,
+    centroid: Point3,
+    normal: Vector3,
+}
+
+impl Profile {
+    fn new(face: &TruckFace, index: usize) -> GeomResult<Self> {
+        let normal = crate::sweep::profile_normal(face)
+            .map_err(|e| invalid(format!("section {index}: {}", e.message)))?;
+        let boundaries = face.boundaries();
+        if boundaries.len() != 1 {
+            return Err(invalid(format!("section {index} must not have holes")));
+        }
+        let curves = boundaries[0].edge_iter().map(|e| e.oriented_curve()).collect();
+        Ok(Self::from_curves(curves, normal))
+    }
+
+    fn from_curves(curves: Vec<Curve>, normal: Vector3) -> Self {
+        let mut tables = Vec::with_capacity(curves.len());
+        let mut length = 0.0;
+        let mut points = Vec::new();
+        for curve in &curves {
+            let (t0, t1) = curve.range_tuple();
+            let mut prev = curve.subs(t0);
+            let mut table = vec![(t0, length)];
+            for i in 1..=LENGTH_SAMPLES {
+                let t = t0 + (t1 - t0) * i as f64 / LENGTH_SAMPLES as f64;
+                let p = curve.subs(t);
+                length += prev.distance(p);
+                table.push((t, length));
+                points.push(p);
+                prev = p;
+            }
+            tables.push(table);
+        }
+        for table in &mut tables {
+            table.iter_mut().for_each(|(_, f)| *f /= length);
+        }
+        let mut starts: Vec<f64> = tables.iter().map(|t| t[0].1).collect();
+        starts.push(1.0);
+        let centroid =
+            Point3::from_vec(points.iter().map(|p| p.to_vec()).sum::<Vector3>() / points.len() as f64);
+        Self {
+            curves,
+            tables,
+            starts,
+            centroid,
+            normal,
+        }
+    }
+
+    /// The same boundary traversed the other way round.
+    fn inverse(&self) -> Self {
+        let curves = self.curves.iter().rev().map(Invertible::inverse).collect();
+        Self::from_curves(curves, -self.normal)
+    }
+
+    /// The parameter of curve `curve` at perimeter fraction `f`.
+    fn locate(&self, f: f64, curve: usize) -> f64 {
+        let table = &self.tables[curve];
+        let f = f.clamp(table[0].1, table[table.len() - 1].1);
+        let k = table
+            .windows(2)
+            .position(|w| f <= w[1].1)
+            .unwrap_or(table.len() - 2);
+        let ((ta, fa), (tb, fb)) = (table[k], table[k + 1]);
+        if fb - fa < 1e-15 {
+            ta
+        } else {
+            ta + (tb - ta) * (f - fa) / (fb - fa)
+        }
+    }
+
+    fn point_at(&self, f: f64) -> Point3 {
+        let f = f.rem_euclid(1.0);
+        let curve = self.curve_at(f);
+        self.curves[curve].subs(self.locate(f, curve))
+    }
+
+    fn curve_at(&self, f: f64) -> usize {
+        self.starts[1..]
+            .iter()
+            .position(|&s| f < s)
+            .unwrap_or(self.curves.len() - 1)
+    }
+
+    /// Cut the boundary into one curve per interval between consecutive
+    /// `breaks`, measured from perimeter fraction `offset`.
+    fn split(&self, breaks: &[f64], offset: f64) -> Vec<Curve> {
+        breaks
+            .iter()
+            .zip(breaks[1..].iter().chain([&1.0]))
+            .map(|(&b0, &b1)| {
+                let f0 = (b0 + offset).rem_euclid(1.0);
+                let f1 = f0 + (b1 - b0);
+                let index = self.curve_at((f0 + f1) / 2.0);
+                let (t0, t1) = self.curves[index].range_tuple();
+                let (ta, tb) = (self.locate(f0, index), self.locate(f1, index));
+                let mut piece = self.curves[index].clone();
+                if ta > t0 + FRACTION_TOLERANCE {
+                    piece = piece.cut(ta);
+                }
+                if tb < t1 - FRACTION_TOLERANCE {
+                    piece.cut(tb);
+                }
+                piece
+            })
+            .collect()
+    }
+}
+
+/// Wind every section counter-clockwise about the local loft direction.
+fn orient_profiles(profiles: Vec<Profile>) -> GeomResult<Vec<Profile>> {
+    let n = profiles.len();
+    let directions: Vec<Vector3> = (0..n)
+        .map(|i| {
+            let (a, b) = if i + 1 < n { (i, i + 1) } else { (i - 1, i) };
+            profiles[b].centroid - profiles[a].centroid
+        })
+        .collect();
+    profiles
+        .into_iter()
+        .zip(directions)
+        .enumerate()
+        .map(|(i, (p, d))| {
+            let along = p.normal.dot(d);
+            if along.abs() < FRACTION_TOLERANCE * d.magnitude().max(1.0) {
+                return Err(invalid(format!(
+                    "section {i} must not be parallel to the loft direction"
+                )));
+            }
+            Ok(if along < 0.0 { p.inverse() } else { p })
+        })
+        .collect()
+}
+
+/// Choose a start fraction for every section that minimises twist against
+/// the previous one, snapping to an existing vertex when close.
+fn align_starts(profiles: &[Profile]) -> Vec<f64> {
+    let samples = |p: &Profile, offset: f64| -> Vec<Vector3> {
+        (0..ALIGN_SAMPLES)
+            .map(|k| p.point_at(offset + k as f64 / ALIGN_SAMPLES as f64) - p.centroid)
+            .collect()
+    };
+    let mut offsets = vec![0.0];
+    for pair in profiles.windows(2) {
+        let reference = samples(&pair[0], offsets[offsets.len() - 1]);
+        let cost = |offset: f64| {
+            samples(&pair[1], offset)
+                .iter()
+                .zip(&reference)
+                .map(|(a, b)| (a - b).magnitude2())
+                .sum::<f64>()
+        };
+        let best = (0..ALIGN_SAMPLES)
+            .map(|k| k as f64 / ALIGN_SAMPLES as f64)
+            .min_by(|a, b| cost(*a).total_cmp(&cost(*b)))
+            .unwrap_or(0.0);
+        let snap = pair[1].starts[..pair[1].curves.len()]
+            .iter()
+            .copied()
+            .find(|&s| {
+                let d = (s - best).rem_euclid(1.0);
+                d.min(1.0 - d) <= 0.5 / ALIGN_SAMPLES as f64
+            });
+        offsets.push(snap.unwrap_or(best));
+    }
+    offsets
+}
+
+/// Sorted breakpoints (relative to each section's start) covering every
+/// original vertex of every section.
+fn common_breaks(profiles: &[Profile], offsets: &[f64]) -> Vec<f64> {
+    let mut breaks: Vec<f64> = vec![0.0];
+    for (p, &offset) in profiles.iter().zip(offsets) {
+        for &s in &p.starts[..p.curves.len()] {
+            breaks.push((s - offset).rem_euclid(1.0));
+        }
+    }
+    breaks.sort_by(f64::total_cmp);
+    breaks.dedup_by(|b, a| *b - *a < FRACTION_TOLERANCE);
+    if 1.0 - breaks[breaks.len() - 1] < FRACTION_TOLERANCE {
+        breaks.pop();
+    }
+    breaks
+}
+
+/// Build closed wires from split section curves; all sections share the
+/// same edge count.
+fn section_wires(split: &[Vec<Curve>]) -> Vec<TruckWire> {
+    split
+        .iter()
+        .map(|curves| {
+            let vertices: Vec<Vertex> = curves
+                .iter()
+                .map(|c| builder::vertex(c.subs(c.range_tuple().0)))
+                .collect();
+            curves
+                .iter()
+                .enumerate()
+                .map(|(j, c)| {
+                    Edge::new(&vertices[j], &vertices[(j + 1) % vertices.len()], c.clone())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// End caps: the first section facing backwards, the last facing forwards.
+fn caps(first: &TruckWire, last: &TruckWire) -> GeomResult<[TruckFace; 2]> {
+    let cap = |w: &TruckWire| {
+        builder::try_attach_plane(std::slice::from_ref(w))
+            .map_err(|e| invalid(format!("loft section is not planar: {e}")))
+    };
+    Ok([cap(first)?.inverse(), cap(last)?])
+}
+
+fn ruled_shell(split: &[Vec<Curve>]) -> GeomResult<Shell> {
+    let wires = section_wires(split);
+    let mut shell = Shell::new();
+    for pair in wires.windows(2) {
+        let strip = builder::try_wire_homotopy(&pair[0], &pair[1])
+            .map_err(|e| invalid(format!("failed to build loft surface: {e}")))?;
+        shell.extend(strip);
+    }
+    shell.extend(caps(&wires[0], &wires[wires.len() - 1])?);
+    Ok(shell)
+}
+
+fn smooth_shell(split: &[Vec<Curve>]) -> GeomResult<Shell> {
+    let sections = split.len();
+    let edges = split[0].len();
+
+    // Polynomial copies of every section curve; column j holds edge j of
+    // each section with a shared degree and knot vector.
+    let mut columns: Vec<Vec<BSplineCurve<Point3>>> = (0..edges)
+        .map(|j| split.iter().map(|curves| polynomial(&curves[j])).collect())
+        .collect::<GeomResult<_>>()?;
+    for column in &mut columns {
+        // Two passes each: the first gathers the maximum degree and every
+        // knot into the first curve, the second hands them to the rest.
+        let (first, rest) = column.split_at_mut(1);
+        for _ in 0..2 {
+            rest.iter_mut().for_each(|c| first[0].syncro_degree(c));
+        }
+        for _ in 0..2 {
+            rest.iter_mut().for_each(|c| first[0].syncro_knots(c));
+        }
+    }
+
+    // Vertices and longitudinal edges through vertex j of every section.
+    let vertex_rows: Vec<Vec<Vertex>> = (0..sections)
+        .map(|i| {
+            (0..edges)
+                .map(|j| builder::vertex(columns[j][i].front()))
+                .collect()
+        })
+        .collect();
+    let knot_v = catmull_rom_knots(sections);
+    let rails: Vec<Edge> = (0..edges)
+        .map(|j| {
+            let points: Vec<Point3> = (0..sections).map(|i| columns[j][i].front()).collect();
+            let curve = BSplineCurve::new(knot_v.clone(), catmull_rom(&points));
+            Edge::new(
+                &vertex_rows[0][j],
+                &vertex_rows[sections - 1][j],
+                Curve::BSplineCurve(curve),
+            )
+        })
+        .collect();
+
+    let ring = |i: usize| -> TruckWire {
+        (0..edges)
+            .map(|j| {
+                Edge::new(
+                    &vertex_rows[i][j],
+                    &vertex_rows[i][(j + 1) % edges],
+                    Curve::BSplineCurve(columns[j][i].clone()),
+                )
+            })
+            .collect()
+    };
+    let (first, last) = (ring(0), ring(sections - 1));
+
+    let mut shell = Shell::new();
+    for j in 0..edges {
+        let ctrl: Vec<Vec<Point3>> = (0..columns[j][0].control_points().len())
+            .map(|k| {
+                let points: Vec<Point3> =
+                    (0..sections).map(|i| *columns[j][i].control_point(k)).collect();
+                catmull_rom(&points)
+            })
+            .collect();
+        let surface = BSplineSurface::new(
+            (columns[j][0].knot_vec().clone(), knot_v.clone()),
+            ctrl,
+        );
+        let boundary: TruckWire = vec![
+            first[j].clone(),
+            rails[(j + 1) % edges].clone(),
+            last[j].inverse(),
+            rails[j].inverse(),
+        ]
+        .into();
+        shell.push(truck_modeling::Face::new(
+            vec![boundary],
+            Surface::BSplineSurface(surface),
+        ));
+    }
+    shell.extend(caps(&first, &last)?);
+    Ok(shell)
+}
+
+/// A non-rational B-spline copy of `curve` with normalised knots.
+fn polynomial(curve: &Curve) -> GeomResult<BSplineCurve<Point3>> {
+    let range = curve.range_tuple();
+    let mut bsp = match curve {
+        Curve::Line(line) => BSplineCurve::new(KnotVec::bezier_knot(1), vec![line.0, line.1]),
+        Curve::BSplineCurve(bsp) => bsp.clone(),
+        _ => BSplineCurve::cubic_approximation(curve, range, APPROX_TOLERANCE, APPROX_TOLERANCE, 12)
+            .ok_or_else(|| invalid("failed to approximate loft section edge".to_string()))?,
+    };
+    bsp.knot_normalize();
+    Ok(bsp)
+}
+
+/// Knot vector for [`catmull_rom`] control points through `n` values: one
+/// cubic Bézier span per interval.
+fn catmull_rom_knots(n: usize) -> KnotVec {
+    let mut knots = vec![0.0; 4];
+    for i in 1..n - 1 {
+        knots.extend([i as f64; 3]);
+    }
+    knots.extend([(n - 1) as f64; 4]);
+    KnotVec::from(knots)
+}
+
+/// Cubic Bézier control points of a C1 Catmull–Rom spline through `points`.
+fn catmull_rom(points: &[Point3]) -> Vec<Point3> {
+    let n = points.len();
+    let tangent = |i: usize| -> Vector3 {
+        if i == 0 {
+            points[1] - points[0]
+        } else if i == n - 1 {
+            points[n - 1] - points[n - 2]
+        } else {
+            (points[i + 1] - points[i - 1]) / 2.0
+        }
+    };
+    let mut ctrl = vec![points[0]];
+    for i in 0..n - 1 {
+        ctrl.push(points[i] + tangent(i) / 3.0);
+        ctrl.push(points[i + 1] - tangent(i + 1) / 3.0);
+        ctrl.push(points[i + 1]);
+    }
+    ctrl
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+    use truck_modeling::Point2;
+
+    fn mesh_volume(solid: &TruckSolid) -> f64 {
+        let mesh = crate::tessellate::mesh_solid(solid, 0.01);
+        let pos = mesh.positions();
+        mesh.face_iter()
+            .map(|f| {
+                let a = pos[f[0].pos].to_vec();
+                (1..f.len() - 1)
+                    .map(|i| a.dot(pos[f[i].pos].to_vec().cross(pos[f[i + 1].pos].to_vec())))
+                    .sum::<f64>()
+                    / 6.0
+            })
+            .sum()
+    }
+
+    fn at_height(face: TruckFace, z: f64) -> TruckFace {
+        builder::translated(&face, Vector3::new(0.0, 0.0, z))
+    }
+
+    fn circle(r: f64, z: f64) -> TruckFace {
+        at_height(crate::sketch::make_circle(Point2::new(0.0, 0.0), r).unwrap(), z)
+    }
+
+    fn square(side: f64, z: f64) -> TruckFace {
+        let h = side / 2.0;
+        at_height(
+            crate::sketch::make_rectangle(Point2::new(-h, -h), side, side).unwrap(),
+            z,
+        )
+    }
+
+    #[test]
+    fn ruled_frustum_volume() {
+        let solid = solid_loft(&[circle(2.0, 0.0), circle(1.0, 3.0)], true).unwrap();
+        // π h / 3 (R² + R r + r²) = 7π
+        let v = mesh_volume(&solid);
+        assert!((v - 7.0 * PI).abs() < 0.1, "volume {v}");
+    }
+
+    #[test]
+    fn smooth_loft_through_equal_sections_is_a_prism() {
+        let solid = solid_loft(&[square(2.0, 0.0), square(2.0, 1.0), square(2.0, 2.0)], false)
+            .unwrap();
+        let v = mesh_volume(&solid);
+        assert!((v - 8.0).abs() < 1e-3, "volume {v}");
+    }
+
+    #[test]
+    fn round_to_square_transition() {
+        for ruled in [true, false] {
+            let solid = solid_loft(&[circle(1.0, 0.0), square(2.0, 4.0)], ruled).unwrap();
+            let v = mesh_volume(&solid);
+            // Between the pure cylinder (4π) and the pure prism (16).
+            assert!(v > 4.0 * PI && v < 16.0, "ruled {ruled}: volume {v}");
+        }
+    }
+
+    #[test]
+    fn sections_with_opposite_normals_are_reoriented() {
+        let flipped = circle(1.0, 2.0).inverse();
+        let solid = solid_loft(&[circle(1.0, 0.0), flipped], true).unwrap();
+        let v = mesh_volume(&solid);
+        assert!((v - 2.0 * PI).abs() < 0.1, "volume {v}");
+    }
+
+    #[test]
+    fn loft_rejects_bad_sections() {
+        assert!(solid_loft(&[circle(1.0, 0.0)], true).is_err());
+        let err = solid_loft(&[circle(1.0, 0.0), circle(2.0, 0.0)], true).unwrap_err();
+        assert!(err.message.contains("parallel"), "{}", err.message);
+        let holed = crate::sketch::face_with_holes(&square(4.0, 0.0), &[circle(1.0, 0.0)])
+            .unwrap();
+        let err = solid_loft(&[holed, circle(1.0, 3.0)], true).unwrap_err();
+        assert!(err.message.contains("holes"), "{}", err.message);
+    }
+}
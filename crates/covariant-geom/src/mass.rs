@@ -0,0 +1,162 @@
+//! Mass properties: volume, surface area, centroid and inertia.
+//!
+//! Properties are integrated over a tessellation of the solid's boundary
+//! (divergence theorem, one tetrahedron per triangle against the origin),
+//! so their accuracy follows the tessellation tolerance. Hollow solids
+//! work naturally: the inner shells face inward and subtract.
+
+use truck_modeling::{EuclideanSpace, InnerSpace, Matrix3, Point3, SquareMatrix, Vector3, Zero};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::TruckSolid;
+
+/// Volume, area and inertia of a solid of uniform density.
+///
+/// Lengths are in model units; `mass` and the inertia tensors are scaled by
+/// the density passed to [`mass_properties`] (mass per cubic model unit).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MassProperties {
+    /// Enclosed volume.
+    pub volume: f64,
+    /// Total boundary surface area.
+    pub area: f64,
+    /// Centre of mass.
+    pub centroid: Point3,
+    /// Density the mass and inertia were computed with.
+    pub density: f64,
+    /// `density * volume`.
+    pub mass: f64,
+    /// Inertia tensor about the centre of mass (row-major).
+    pub inertia_centroid: [[f64; 3]; 3],
+    /// Inertia tensor about the origin (row-major).
+    pub inertia_origin: [[f64; 3]; 3],
+}
+
+/// Measure a solid tessellated at `tolerance`, with uniform `density`.
+pub fn mass_properties(
+    solid: &TruckSolid,
+    density: f64,
+    tolerance: f64,
+) -> GeomResult<MassProperties> {
+    if density < 0.0 {
+        return Err(invalid(format!("density must not be negative, got {density}")));
+    }
+    if tolerance <= 0.0 {
+        return Err(invalid(format!("tolerance must be positive, got {tolerance}")));
+    }
+    let mesh = crate::tessellate::mesh_solid(solid, tolerance);
+    let positions = mesh.positions();
+
+    let mut volume = 0.0;
+    let mut area = 0.0;
+    let mut moment = Vector3::zero();
+    // Second moments ∫ x xᵀ dV.
+    let mut second = Matrix3::zero();
+    for face in mesh.face_iter() {
+        let a = positions[face[0].pos].to_vec();
+        for i in 1..face.len() - 1 {
+            let b = positions[face[i].pos].to_vec();
+            let c = positions[face[i + 1].pos].to_vec();
+            area += (b - a).cross(c - a).magnitude() / 2.0;
+            let det = a.dot(b.cross(c));
+            volume += det / 6.0;
+            moment += (a + b + c) * (det / 24.0);
+            // ∫ over the tetrahedron (0, a, b, c) of x xᵀ.
+            let sum = a + b + c;
+            second += (outer(a, a) + outer(b, b) + outer(c, c) + outer(sum, sum)) * (det / 120.0);
+        }
+    }
+    if volume <= 0.0 {
+        return Err(invalid(
+            "solid has no enclosed volume; its boundary may be open or inside out".to_string(),
+        ));
+    }
+
+    let centroid = Point3::from_vec(moment / volume);
+    let about_centroid = second - outer(centroid.to_vec(), centroid.to_vec()) * volume;
+    Ok(MassProperties {
+        volume,
+        area,
+        centroid,
+        density,
+        mass: density * volume,
+        inertia_centroid: inertia(about_centroid * density),
+        inertia_origin: inertia(second * density),
+    })
+}
+
+/// The inertia tensor `tr(S) I - S` from second moments `S`.
+fn inertia(second: Matrix3) -> [[f64; 3]; 3] {
+    let tensor = Matrix3::identity() * second.trace() - second;
+    // cgmath matrices are column-major; the tensor is symmetric.
+    tensor.into()
+}
+
+fn outer(u: Vector3, v: Vector3) -> Matrix3 {
+    Matrix3::from_cols(u * v.x, u * v.y, u * v.z)
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder, make_sphere};
+    use std::f64::consts::PI;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() <= tolerance * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn box_properties_are_exact() {
+        let props = mass_properties(&make_box(2.0, 4.0, 6.0), 0.5, 0.01).unwrap();
+        assert!(close(props.volume, 48.0, 1e-9));
+        assert!(close(props.area, 2.0 * (8.0 + 12.0 + 24.0), 1e-9));
+        assert!(close(props.mass, 24.0, 1e-9));
+        assert!((props.centroid - Point3::new(1.0, 2.0, 3.0)).magnitude() < 1e-9);
+        // Ixx = m (b² + c²) / 12 about the centre.
+        let i = props.inertia_centroid;
+        assert!(close(i[0][0], 24.0 * (16.0 + 36.0) / 12.0, 1e-9));
+        assert!(close(i[1][1], 24.0 * (4.0 + 36.0) / 12.0, 1e-9));
+        assert!(close(i[2][2], 24.0 * (4.0 + 16.0) / 12.0, 1e-9));
+        assert!(i[0][1].abs() < 1e-9 && i[1][2].abs() < 1e-9);
+        // Parallel axis theorem: Ixx(origin) = Ixx(centre) + m (y² + z²).
+        let o = props.inertia_origin;
+        assert!(close(o[0][0], i[0][0] + 24.0 * (4.0 + 9.0), 1e-9));
+        assert!(close(o[0][1], -24.0 * 1.0 * 2.0, 1e-9));
+    }
+
+    #[test]
+    fn curved_solids_converge_with_tolerance() {
+        let props = mass_properties(&make_cylinder(2.0, 5.0), 1.0, 0.001).unwrap();
+        assert!(close(props.volume, PI * 4.0 * 5.0, 1e-3));
+        assert!(close(props.area, 2.0 * PI * 4.0 + 2.0 * PI * 2.0 * 5.0, 1e-3));
+        assert!((props.centroid - Point3::new(0.0, 0.0, 2.5)).magnitude() < 1e-3);
+
+        let props = mass_properties(&make_sphere(3.0), 1.0, 0.001).unwrap();
+        let mass = 4.0 / 3.0 * PI * 27.0;
+        assert!(close(props.volume, mass, 1e-3));
+        // 2/5 m r² about any axis through the centre.
+        for k in 0..3 {
+            assert!(close(props.inertia_centroid[k][k], 0.4 * mass * 9.0, 2e-3));
+        }
+    }
+
+    #[test]
+    fn hollow_solid_subtracts_cavity() {
+        let hollow = crate::offset::solid_shell(&make_box(10.0, 10.0, 10.0), 1.0, &[]).unwrap();
+        let props = mass_properties(&hollow, 1.0, 0.01).unwrap();
+        assert!(close(props.volume, 1000.0 - 512.0, 1e-9));
+        assert!(close(props.area, 600.0 + 384.0, 1e-9));
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let cube = make_box(1.0, 1.0, 1.0);
+        assert!(mass_properties(&cube, -1.0, 0.01).is_err());
+        assert!(mass_properties(&cube, 1.0, 0.0).is_err());
+    }
+}
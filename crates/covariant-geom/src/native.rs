@@ -0,0 +1,139 @@
+//! Native B-rep file format: a lossless, versioned serialization of solids.
+//!
+//! A file starts with the magic bytes `COVSOLID` and a little-endian `u32`
+//! format version, followed by truck's compressed topology (shared
+//! vertices, edges and faces with their exact curves and surfaces) encoded
+//! with bincode's default options.
+
+use std::path::Path;
+
+use bincode::Options;
+use truck_modeling::{Curve, Point3, Surface};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::TruckSolid;
+
+/// The format version written by this build. Files with a higher version
+/// are rejected rather than misread.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MAGIC: &[u8; 8] = b"COVSOLID";
+
+type CompressedSolid = truck_topology::compress::CompressedSolid<Point3, Curve, Surface>;
+
+/// Serialize a solid into the native format.
+pub fn solid_to_bytes(solid: &TruckSolid) -> GeomResult<Vec<u8>> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(FORMAT_VERSION.to_le_bytes());
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut bytes, &solid.compress())
+        .map_err(|e| invalid(format!("failed to encode solid: {e}")))?;
+    Ok(bytes)
+}
+
+/// Deserialize a solid from the native format, checking its topology.
+pub fn solid_from_bytes(bytes: &[u8]) -> GeomResult<TruckSolid> {
+    let Some(body) = bytes.strip_prefix(MAGIC) else {
+        return Err(invalid("not a covariant solid file".to_string()));
+    };
+    let Some((version, body)) = body.split_first_chunk::<4>() else {
+        return Err(invalid("truncated solid file header".to_string()));
+    };
+    let version = u32::from_le_bytes(*version);
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(invalid(format!(
+            "unsupported solid format version {version} (this build reads up to {FORMAT_VERSION})"
+        )));
+    }
+    let compressed: CompressedSolid = bincode::DefaultOptions::new()
+        .deserialize(body)
+        .map_err(|e| invalid(format!("corrupt solid data: {e}")))?;
+    TruckSolid::extract(compressed).map_err(|e| invalid(format!("corrupt solid data: {e}")))
+}
+
+/// Write a solid to a native format file.
+pub fn write_solid(solid: &TruckSolid, path: &Path) -> GeomResult<()> {
+    let bytes = solid_to_bytes(solid)?;
+    std::fs::write(path, bytes).map_err(|e| {
+        GeomError::new(
+            GeomErrorKind::IoError,
+            format!("failed to write {}: {e}", path.display()),
+        )
+    })
+}
+
+/// Read a solid from a native format file.
+pub fn read_solid(path: &Path) -> GeomResult<TruckSolid> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        GeomError::new(
+            GeomErrorKind::IoError,
+            format!("failed to read {}: {e}", path.display()),
+        )
+    })?;
+    solid_from_bytes(&bytes).map_err(|e| invalid(format!("{}: {}", path.display(), e.message)))
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean::solid_difference;
+    use crate::mass::mass_properties;
+    use crate::primitives::{make_box, make_cylinder};
+    use crate::types::DEFAULT_TOLERANCE;
+    use truck_modeling::{builder, Vector3};
+
+    fn drilled_block() -> TruckSolid {
+        let hole = builder::translated(&make_cylinder(3.0, 20.0), Vector3::new(10.0, 10.0, -5.0));
+        solid_difference(&make_box(20.0, 20.0, 10.0), &hole, DEFAULT_TOLERANCE).unwrap()
+    }
+
+    #[test]
+    fn round_trip_is_lossless() {
+        let solid = drilled_block();
+        let bytes = solid_to_bytes(&solid).unwrap();
+        let loaded = solid_from_bytes(&bytes).unwrap();
+        assert_eq!(solid_to_bytes(&loaded).unwrap(), bytes);
+        assert_eq!(loaded.face_iter().count(), solid.face_iter().count());
+        let (a, b) = (
+            mass_properties(&solid, 1.0, 0.01).unwrap().volume,
+            mass_properties(&loaded, 1.0, 0.01).unwrap().volume,
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let path = std::env::temp_dir().join("covariant_native_round_trip.covs");
+        write_solid(&make_box(1.0, 2.0, 3.0), &path).unwrap();
+        let loaded = read_solid(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!((mass_properties(&loaded, 1.0, 0.01).unwrap().volume - 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_foreign_newer_and_truncated_data() {
+        let bytes = solid_to_bytes(&make_box(1.0, 1.0, 1.0)).unwrap();
+
+        let err = solid_from_bytes(b"solid ascii stl").unwrap_err();
+        assert!(
+            err.message.contains("not a covariant solid"),
+            "{}",
+            err.message
+        );
+
+        let mut newer = bytes.clone();
+        newer[8..12].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        let err = solid_from_bytes(&newer).unwrap_err();
+        assert!(err.message.contains("version"), "{}", err.message);
+
+        let err = solid_from_bytes(&bytes[..bytes.len() - 5]).unwrap_err();
+        assert!(err.message.contains("corrupt"), "{}", err.message);
+
+        let err = read_solid(Path::new("/nonexistent/part.covs")).unwrap_err();
+        assert_eq!(err.kind, GeomErrorKind::IoError);
+    }
+}
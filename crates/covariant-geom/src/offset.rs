@@ -0,0 +1,755 @@
+//! Offset and shell (hollow) solids.
+//!
+//! Every face is moved along its outward normal; the topology is kept and
+//! vertices and edges are recomputed where the moved faces meet, so sharp
+//! edges stay sharp (faces are extended or trimmed, not rounded). Faces
+//! must be planar, cylindrical or spherical, which covers primitives,
+//! extrusions of lines and arcs, boolean results of those, and fillets.
+
+use std::collections::HashMap;
+
+use truck_modeling::{
+    builder, BoundedCurve, EdgeID, EuclideanSpace, InnerSpace, Matrix3, Matrix4,
+    ParametricCurve, ParametricSurface, ParametricSurface3D, Point3, Shell, SquareMatrix,
+    Surface, Transform, Transformed, Vector3, VertexID, Wire, Zero,
+};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::{TruckEdge, TruckFace, TruckSolid, TruckVertex};
+
+/// Distance below which two computed points are considered coincident.
+const POINT_TOLERANCE: f64 = 1e-6;
+
+/// Samples per parameter direction used to recognise a face's surface.
+const SURFACE_SAMPLES: usize = 4;
+
+/// Newton iterations when solving for the point where moved faces meet.
+const MEET_ITERATIONS: usize = 100;
+
+/// Grow (positive `distance`) or shrink (negative) a solid, moving every
+/// face along its outward normal.
+pub fn solid_offset(solid: &TruckSolid, distance: f64) -> GeomResult<TruckSolid> {
+    let shells = solid
+        .boundaries()
+        .iter()
+        .map(|shell| {
+            let offset = Offset::new(shell, |_| distance)?;
+            (0..offset.faces.len())
+                .map(|i| offset.face(i))
+                .collect::<GeomResult<Shell>>()
+        })
+        .collect::<GeomResult<Vec<_>>>()?;
+    TruckSolid::try_new(shells).map_err(|e| invalid(format!("offset produced an invalid solid: {e}")))
+}
+
+/// Hollow a solid, leaving walls of `thickness` inside its boundary.
+///
+/// Planar faces whose outward normal points along one of `open_faces` are
+/// removed, opening the cavity; without open faces the result is a closed
+/// solid with an internal void.
+pub fn solid_shell(
+    solid: &TruckSolid,
+    thickness: f64,
+    open_faces: &[Vector3],
+) -> GeomResult<TruckSolid> {
+    if thickness <= 0.0 {
+        return Err(invalid(format!(
+            "shell thickness must be positive, got {thickness}"
+        )));
+    }
+    let [shell] = &solid.boundaries()[..] else {
+        return Err(invalid("shell needs a solid with a single boundary".to_string()));
+    };
+    let faces: Vec<TruckFace> = shell.face_iter().cloned().collect();
+    let open = open_face_mask(&faces, open_faces)?;
+    if open.iter().all(|&o| o) {
+        return Err(invalid("shell cannot open every face".to_string()));
+    }
+    let inner = Offset::new(shell, |i| if open[i] { 0.0 } else { -thickness })?;
+    for (id, adjacent) in &inner.edge_faces {
+        if adjacent.iter().all(|&f| open[f]) {
+            let edge = &inner.old_edges[id];
+            return Err(invalid(format!(
+                "open faces must not share an edge, but two meet at {}",
+                fmt_point(edge.front().point())
+            )));
+        }
+    }
+
+    let mut outer = Shell::new();
+    let mut cavity = Shell::new();
+    for (i, face) in faces.iter().enumerate() {
+        if open[i] {
+            // The rim between the outer boundary and the cavity, in the
+            // plane of the removed face.
+            let mut wires = face.absolute_clone().boundaries();
+            wires.extend(inner.wires(i).iter().map(|w| w.inverse()));
+            outer.push(oriented(wires, face.surface(), face.orientation())?);
+        } else {
+            outer.push(face.clone());
+            cavity.push(inner.face(i)?.inverse());
+        }
+    }
+    let shells = if open.iter().any(|&o| o) {
+        outer.extend(cavity);
+        vec![outer]
+    } else {
+        vec![outer, cavity]
+    };
+    TruckSolid::try_new(shells).map_err(|e| invalid(format!("shell produced an invalid solid: {e}")))
+}
+
+/// Mark the planar faces facing along any of `directions`; every direction
+/// must pick at least one face.
+fn open_face_mask(faces: &[TruckFace], directions: &[Vector3]) -> GeomResult<Vec<bool>> {
+    let normals: Vec<Option<Vector3>> = faces
+        .iter()
+        .map(|face| match face.oriented_surface() {
+            Surface::Plane(plane) => Some(plane.normal()),
+            _ => None,
+        })
+        .collect();
+    let mut open = vec![false; faces.len()];
+    for &direction in directions {
+        if direction.magnitude() < POINT_TOLERANCE {
+            return Err(invalid("open face direction must be non-zero".to_string()));
+        }
+        let direction = direction.normalize();
+        let mut found = false;
+        for (i, normal) in normals.iter().enumerate() {
+            if normal.is_some_and(|n| n.dot(direction) > 1.0 - POINT_TOLERANCE) {
+                open[i] = true;
+                found = true;
+            }
+        }
+        if !found {
+            return Err(invalid(format!(
+                "no planar face points along {}",
+                fmt_point(Point3::from_vec(direction))
+            )));
+        }
+    }
+    Ok(open)
+}
+
+// ── Face surfaces ───────────────────────────────────────────────────────
+
+/// The surfaces an offset can move exactly. Normals are outward; for
+/// cylinders and spheres `radius` is negative when the outward normal
+/// points toward the axis or centre (holes and cavities).
+#[derive(Debug, Clone, Copy)]
+enum Carrier {
+    Plane { point: Point3, normal: Vector3 },
+    Cylinder { point: Point3, axis: Vector3, radius: f64 },
+    Sphere { center: Point3, radius: f64 },
+}
+
+impl Carrier {
+    /// Recognise the surface of a face by sampling its normals.
+    fn of_face(face: &TruckFace) -> GeomResult<Self> {
+        let surface = face.oriented_surface();
+        if let Surface::Plane(plane) = &surface {
+            return Ok(Self::Plane {
+                point: plane.origin(),
+                normal: plane.normal(),
+            });
+        }
+        let unsupported = || {
+            invalid(
+                "offset supports only planar, cylindrical and spherical faces".to_string(),
+            )
+        };
+        let (u_range, v_range) = surface.parameter_range();
+        let (u0, u1) = bounded(u_range).ok_or_else(unsupported)?;
+        let (v0, v1) = bounded(v_range).ok_or_else(unsupported)?;
+        let samples: Vec<(Point3, Vector3)> = (0..SURFACE_SAMPLES * SURFACE_SAMPLES)
+            .map(|k| {
+                let s = (k / SURFACE_SAMPLES) as f64 + 0.5;
+                let t = (k % SURFACE_SAMPLES) as f64 + 0.5;
+                let u = u0 + (u1 - u0) * s / SURFACE_SAMPLES as f64;
+                let v = v0 + (v1 - v0) * t / SURFACE_SAMPLES as f64;
+                (surface.subs(u, v), surface.normal(u, v))
+            })
+            .filter(|(_, n)| n.x.is_finite() && n.y.is_finite() && n.z.is_finite())
+            .collect();
+        Self::fit(&samples).ok_or_else(unsupported)
+    }
+
+    /// Fit a plane, cylinder or sphere through points with outward normals.
+    fn fit(samples: &[(Point3, Vector3)]) -> Option<Self> {
+        let &(p0, n0) = samples.first()?;
+        let size = samples
+            .iter()
+            .map(|(p, _)| (p - p0).magnitude())
+            .fold(1.0, f64::max);
+        let tolerance = POINT_TOLERANCE * size;
+
+        // The pair of samples whose normals differ the most.
+        let mut best = (0, 0, 0.0);
+        for i in 0..samples.len() {
+            for j in i + 1..samples.len() {
+                let spread = (samples[i].1 - samples[j].1).magnitude();
+                if spread > best.2 {
+                    best = (i, j, spread);
+                }
+            }
+        }
+        let (i, j, spread) = best;
+        if spread < POINT_TOLERANCE {
+            let flat = samples
+                .iter()
+                .all(|(p, _)| n0.dot(p - p0).abs() < tolerance);
+            return flat.then_some(Self::Plane {
+                point: p0,
+                normal: n0,
+            });
+        }
+
+        let ((pi, ni), (pj, nj)) = (samples[i], samples[j]);
+        let axis = ni.cross(nj);
+        let cylinder = axis.magnitude() > POINT_TOLERANCE && {
+            let axis = axis.normalize();
+            samples.iter().all(|(_, n)| n.dot(axis).abs() < POINT_TOLERANCE)
+        };
+        // p = c + r n for every sample, with c on the axis or at the centre.
+        let flatten = |v: Vector3| {
+            if cylinder {
+                let axis = axis.normalize();
+                v - axis * v.dot(axis)
+            } else {
+                v
+            }
+        };
+        let dn = ni - nj;
+        let radius = flatten(pi - pj).dot(dn) / dn.magnitude2();
+        let center = pi - ni * radius;
+        let fits = samples
+            .iter()
+            .all(|&(p, n)| (flatten(p - center) - n * radius).magnitude() < tolerance);
+        match (fits, cylinder) {
+            (false, _) => None,
+            (true, true) => Some(Self::Cylinder {
+                point: center,
+                axis: axis.normalize(),
+                radius,
+            }),
+            (true, false) => Some(Self::Sphere { center, radius }),
+        }
+    }
+
+    /// The same surface moved `distance` along its outward normal.
+    fn offset(&self, distance: f64) -> Option<Self> {
+        let grown = |radius: f64| {
+            let new = radius + distance;
+            (new * radius > 0.0 && new.abs() > POINT_TOLERANCE).then_some(new)
+        };
+        Some(match *self {
+            Self::Plane { point, normal } => Self::Plane {
+                point: point + normal * distance,
+                normal,
+            },
+            Self::Cylinder {
+                point,
+                axis,
+                radius,
+            } => Self::Cylinder {
+                point,
+                axis,
+                radius: grown(radius)?,
+            },
+            Self::Sphere { center, radius } => Self::Sphere {
+                center,
+                radius: grown(radius)?,
+            },
+        })
+    }
+
+    /// The affine map taking this surface to `moved` (its offset).
+    fn map_to(&self, moved: &Self) -> Matrix4 {
+        match (*self, *moved) {
+            (Self::Plane { point, .. }, Self::Plane { point: to, .. }) => {
+                Matrix4::from_translation(to - point)
+            }
+            (
+                Self::Cylinder {
+                    point,
+                    axis,
+                    radius,
+                },
+                Self::Cylinder { radius: to, .. },
+            ) => {
+                let k = to / radius;
+                let along = Matrix3::from_cols(axis * axis.x, axis * axis.y, axis * axis.z);
+                about(point, Matrix3::identity() * k + along * (1.0 - k))
+            }
+            (Self::Sphere { center, radius }, Self::Sphere { radius: to, .. }) => {
+                about(center, Matrix3::identity() * (to / radius))
+            }
+            _ => Matrix4::identity(),
+        }
+    }
+
+    /// Signed distance-like residual of `x` and its gradient.
+    fn residual(&self, x: Point3) -> (f64, Vector3) {
+        match *self {
+            Self::Plane { point, normal } => (normal.dot(x - point), normal),
+            Self::Cylinder {
+                point,
+                axis,
+                radius,
+            } => {
+                let v = x - point;
+                let radial = v - axis * v.dot(axis);
+                (radial.magnitude() - radius.abs(), radial.normalize())
+            }
+            Self::Sphere { center, radius } => {
+                let v = x - center;
+                (v.magnitude() - radius.abs(), v.normalize())
+            }
+        }
+    }
+
+    /// Outward unit normal at a point on the surface.
+    fn normal_at(&self, x: Point3) -> Vector3 {
+        match *self {
+            Self::Plane { normal, .. } => normal,
+            Self::Cylinder { radius, .. } | Self::Sphere { radius, .. } => {
+                self.residual(x).1 * radius.signum()
+            }
+        }
+    }
+
+    fn same(&self, other: &Self) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() < POINT_TOLERANCE;
+        match (*self, *other) {
+            (Self::Plane { point, normal }, Self::Plane { point: q, normal: m }) => {
+                normal.dot(m) > 1.0 - POINT_TOLERANCE && close(normal.dot(q - point), 0.0)
+            }
+            (
+                Self::Cylinder {
+                    point,
+                    axis,
+                    radius,
+                },
+                Self::Cylinder {
+                    point: q,
+                    axis: b,
+                    radius: s,
+                },
+            ) => {
+                let v = q - point;
+                axis.dot(b).abs() > 1.0 - POINT_TOLERANCE
+                    && (v - axis * v.dot(axis)).magnitude() < POINT_TOLERANCE
+                    && close(radius, s)
+            }
+            (Self::Sphere { center, radius }, Self::Sphere { center: c, radius: s }) => {
+                (c - center).magnitude() < POINT_TOLERANCE && close(radius, s)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The affine map `x ↦ center + linear (x − center)`.
+fn about(center: Point3, linear: Matrix3) -> Matrix4 {
+    let v = center.to_vec();
+    Matrix4::from_translation(v) * Matrix4::from(linear) * Matrix4::from_translation(-v)
+}
+
+fn bounded(range: truck_modeling::ParameterRange) -> Option<(f64, f64)> {
+    use std::ops::Bound::{Excluded, Included};
+    match range {
+        (Included(a) | Excluded(a), Included(b) | Excluded(b)) => Some((a, b)),
+        _ => None,
+    }
+}
+
+/// The point near `start` lying on all `surfaces`, by Gauss–Newton with
+/// a minimum-norm step where the surfaces do not pin every direction.
+fn meet(surfaces: &[Carrier], start: Point3) -> Option<Point3> {
+    let mut x = start;
+    for _ in 0..MEET_ITERATIONS {
+        let mut normal_matrix = Matrix3::zero();
+        let mut gradient = Vector3::zero();
+        let mut worst: f64 = 0.0;
+        for surface in surfaces {
+            let (f, g) = surface.residual(x);
+            normal_matrix += Matrix3::from_cols(g * g.x, g * g.y, g * g.z);
+            gradient += g * f;
+            worst = worst.max(f.abs());
+        }
+        if worst < POINT_TOLERANCE * 1e-3 {
+            return Some(x);
+        }
+        let damping = 1e-9 * normal_matrix.trace().max(1.0);
+        let step = (normal_matrix + Matrix3::identity() * damping).invert()? * gradient;
+        x -= step;
+    }
+    surfaces
+        .iter()
+        .all(|s| s.residual(x).0.abs() < POINT_TOLERANCE)
+        .then_some(x)
+}
+
+// ── Rebuilding the shell ────────────────────────────────────────────────
+
+/// A shell with every face moved by its own distance.
+struct Offset {
+    faces: Vec<TruckFace>,
+    carriers: Vec<Carrier>,
+    moved: Vec<Carrier>,
+    /// Faces along each edge.
+    edge_faces: HashMap<EdgeID, Vec<usize>>,
+    /// The original edges, in their absolute orientation.
+    old_edges: HashMap<EdgeID, TruckEdge>,
+    /// The moved edges, in their absolute orientation.
+    edges: HashMap<EdgeID, TruckEdge>,
+}
+
+impl Offset {
+    fn new(shell: &Shell, distance: impl Fn(usize) -> f64) -> GeomResult<Self> {
+        let faces: Vec<TruckFace> = shell.face_iter().cloned().collect();
+        let carriers = faces
+            .iter()
+            .map(Carrier::of_face)
+            .collect::<GeomResult<Vec<_>>>()?;
+        let moved = carriers
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                c.offset(distance(i)).ok_or_else(|| {
+                    invalid(format!(
+                        "offset distance {} collapses a curved face",
+                        distance(i)
+                    ))
+                })
+            })
+            .collect::<GeomResult<Vec<_>>>()?;
+
+        let mut edge_faces: HashMap<EdgeID, Vec<usize>> = HashMap::new();
+        let mut vertex_faces: HashMap<VertexID, Vec<usize>> = HashMap::new();
+        let mut old_edges = HashMap::new();
+        let mut old_vertices = HashMap::new();
+        for (i, face) in faces.iter().enumerate() {
+            for wire in face.absolute_clone().boundaries() {
+                for edge in wire.edge_iter() {
+                    edge_faces.entry(edge.id()).or_default().push(i);
+                    old_edges
+                        .entry(edge.id())
+                        .or_insert_with(|| edge.absolute_clone());
+                    for vertex in [edge.front(), edge.back()] {
+                        let adjacent = vertex_faces.entry(vertex.id()).or_default();
+                        if !adjacent.contains(&i) {
+                            adjacent.push(i);
+                        }
+                        old_vertices.insert(vertex.id(), vertex.point());
+                    }
+                }
+            }
+        }
+
+        let mut offset = Self {
+            faces,
+            carriers,
+            moved,
+            edge_faces,
+            old_edges,
+            edges: HashMap::new(),
+        };
+        let mut vertices: HashMap<VertexID, TruckVertex> = HashMap::new();
+        for (id, point) in old_vertices {
+            let moved = offset.move_point(&vertex_faces[&id], point)?;
+            vertices.insert(id, builder::vertex(moved));
+        }
+        let ids: Vec<EdgeID> = offset.old_edges.keys().copied().collect();
+        for id in ids {
+            let edge = offset.move_edge(id, &vertices)?;
+            offset.edges.insert(id, edge);
+        }
+        Ok(offset)
+    }
+
+    /// The distinct moved surfaces of `faces`, with the face each came from.
+    fn distinct(&self, faces: &[usize]) -> Vec<usize> {
+        let mut distinct: Vec<usize> = Vec::new();
+        for &f in faces {
+            if !distinct.iter().any(|&g| self.moved[g].same(&self.moved[f])) {
+                distinct.push(f);
+            }
+        }
+        distinct
+    }
+
+    /// Where a point on all of `faces` ends up once they have moved.
+    fn move_point(&self, faces: &[usize], point: Point3) -> GeomResult<Point3> {
+        match &self.distinct(faces)[..] {
+            &[f] => Ok(self.map(f).transform_point(point)),
+            distinct => {
+                let surfaces: Vec<Carrier> = distinct.iter().map(|&f| self.moved[f]).collect();
+                meet(&surfaces, point).ok_or_else(|| {
+                    invalid(format!(
+                        "offset faces near {} do not meet; the distance may be too large",
+                        fmt_point(point)
+                    ))
+                })
+            }
+        }
+    }
+
+    fn move_edge(
+        &self,
+        id: EdgeID,
+        vertices: &HashMap<VertexID, TruckVertex>,
+    ) -> GeomResult<TruckEdge> {
+        let old = &self.old_edges[&id];
+        let faces = &self.edge_faces[&id];
+        let front = &vertices[&old.front().id()];
+        let back = &vertices[&old.back().id()];
+        let curve = old.curve();
+        let (t0, t1) = curve.range_tuple();
+        let at = |s: f64| curve.subs(t0 + (t1 - t0) * s);
+        let distinct = self.distinct(faces);
+
+        let chord = old.back().point() - old.front().point();
+        let mid = at(0.5);
+        let straight = chord.magnitude() > POINT_TOLERANCE && {
+            let d = chord.normalize();
+            let v = mid - old.front().point();
+            (v - d * v.dot(d)).magnitude() < POINT_TOLERANCE
+        };
+        if straight {
+            let new = back.point() - front.point();
+            if new.dot(chord) < POINT_TOLERANCE * chord.magnitude() {
+                return Err(self.too_large(mid));
+            }
+            return Ok(builder::line(front, back));
+        }
+
+        let smooth = distinct.len() == 1 || {
+            let normals: Vec<Vector3> = distinct
+                .iter()
+                .map(|&f| self.carriers[f].normal_at(mid))
+                .collect();
+            normals.iter().all(|n| n.dot(normals[0]) > 1.0 - POINT_TOLERANCE)
+        };
+        if smooth {
+            // Both faces move the edge the same way.
+            let moved = curve.transformed(self.map(faces[0]));
+            let (m0, m1) = moved.range_tuple();
+            if (moved.subs(m0) - front.point()).magnitude() > POINT_TOLERANCE
+                || (moved.subs(m1) - back.point()).magnitude() > POINT_TOLERANCE
+            {
+                return Err(self.too_large(mid));
+            }
+            return Ok(TruckEdge::new(front, back, moved));
+        }
+
+        // A sharp curved edge must stay a circular arc.
+        if old.front().id() == old.back().id() {
+            return Err(invalid(format!(
+                "offset does not support the closed edge at {}",
+                fmt_point(old.front().point())
+            )));
+        }
+        let moved = [0.25, 0.5, 0.75]
+            .iter()
+            .map(|&s| self.move_point(faces, at(s)))
+            .collect::<GeomResult<Vec<_>>>()?;
+        let center = circumcenter(front.point(), moved[1], back.point()).ok_or_else(|| {
+            invalid(format!(
+                "offset supports only straight and circular sharp edges, \
+                 but the edge at {} is neither",
+                fmt_point(mid)
+            ))
+        })?;
+        let radius = (front.point() - center).magnitude();
+        for p in [moved[0], moved[2]] {
+            if ((p - center).magnitude() - radius).abs() > POINT_TOLERANCE * radius.max(1.0) {
+                return Err(invalid(format!(
+                    "offset supports only straight and circular sharp edges, \
+                     but the edge at {} is neither",
+                    fmt_point(mid)
+                )));
+            }
+        }
+        Ok(builder::circle_arc(front, back, moved[1]))
+    }
+
+    /// The map moving face `f`'s surface.
+    fn map(&self, f: usize) -> Matrix4 {
+        self.carriers[f].map_to(&self.moved[f])
+    }
+
+    /// The moved boundary wires of face `f`, in the face's absolute sense.
+    fn wires(&self, f: usize) -> Vec<Wire> {
+        self.faces[f]
+            .absolute_clone()
+            .boundaries()
+            .iter()
+            .map(|wire| {
+                wire.edge_iter()
+                    .map(|e| {
+                        let new = &self.edges[&e.id()];
+                        if e.orientation() {
+                            new.clone()
+                        } else {
+                            new.inverse()
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Face `f`, moved.
+    fn face(&self, f: usize) -> GeomResult<TruckFace> {
+        let face = &self.faces[f];
+        let surface = face.surface().transformed(self.map(f));
+        oriented(self.wires(f), surface, face.orientation())
+    }
+
+    fn too_large(&self, at: Point3) -> GeomError {
+        invalid(format!(
+            "offset distance is too large for the faces near {}",
+            fmt_point(at)
+        ))
+    }
+}
+
+/// A face from wires in the surface's own sense, inverted if
+/// `orientation` is false.
+fn oriented(wires: Vec<Wire>, surface: Surface, orientation: bool) -> GeomResult<TruckFace> {
+    TruckFace::try_new(wires, surface)
+        .map(|face| if orientation { face } else { face.inverse() })
+        .map_err(|e| invalid(format!("offset produced an invalid face: {e}")))
+}
+
+/// Centre of the circle through three points.
+fn circumcenter(a: Point3, b: Point3, c: Point3) -> Option<Point3> {
+    let (u, v) = (b - a, c - a);
+    let w = u.cross(v);
+    let w2 = w.magnitude2();
+    if w2 < POINT_TOLERANCE * POINT_TOLERANCE * u.magnitude2() * v.magnitude2() {
+        return None;
+    }
+    let offset = (v * u.magnitude2() - u * v.magnitude2()).cross(w) / (2.0 * w2);
+    Some(a + offset)
+}
+
+fn fmt_point(p: Point3) -> String {
+    format!("({}, {}, {})", p.x, p.y, p.z)
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blend::{solid_fillet, EdgeSelector};
+    use crate::primitives::{make_box, make_cylinder, make_sphere};
+    use std::f64::consts::PI;
+
+    fn mesh_volume(solid: &TruckSolid) -> f64 {
+        let mesh = crate::tessellate::mesh_solid(solid, 0.001);
+        let pos = mesh.positions();
+        mesh.face_iter()
+            .map(|f| {
+                let a = pos[f[0].pos].to_vec();
+                (1..f.len() - 1)
+                    .map(|i| a.dot(pos[f[i].pos].to_vec().cross(pos[f[i + 1].pos].to_vec())))
+                    .sum::<f64>()
+                    / 6.0
+            })
+            .sum()
+    }
+
+    fn assert_volume(solid: &TruckSolid, expected: f64) {
+        let v = mesh_volume(solid);
+        assert!((v - expected).abs() < 0.01 * expected, "volume {v}, expected {expected}");
+    }
+
+    #[test]
+    fn offset_box_keeps_sharp_edges() {
+        let cube = make_box(10.0, 10.0, 10.0);
+        assert_volume(&solid_offset(&cube, 1.0).unwrap(), 12.0_f64.powi(3));
+        assert_volume(&solid_offset(&cube, -1.0).unwrap(), 8.0_f64.powi(3));
+    }
+
+    #[test]
+    fn offset_cylinder_and_sphere() {
+        let grown = solid_offset(&make_cylinder(5.0, 10.0), 1.0).unwrap();
+        assert_volume(&grown, PI * 36.0 * 12.0);
+        let shrunk = solid_offset(&make_sphere(5.0), -1.0).unwrap();
+        assert_volume(&shrunk, 4.0 / 3.0 * PI * 64.0);
+    }
+
+    #[test]
+    fn offset_filleted_box_grows_radius() {
+        let rounded = solid_fillet(
+            &make_box(10.0, 10.0, 10.0),
+            2.0,
+            &EdgeSelector::ParallelTo(Vector3::unit_z()),
+        )
+        .unwrap();
+        let grown = solid_offset(&rounded, 1.0).unwrap();
+        assert_volume(&grown, 1728.0 - 4.0 * (1.0 - PI / 4.0) * 9.0 * 12.0);
+    }
+
+    #[test]
+    fn offset_rejects_collapse_and_unsupported_faces() {
+        let err = solid_offset(&make_box(10.0, 10.0, 10.0), -6.0).unwrap_err();
+        assert!(err.message.contains("too large"), "{}", err.message);
+        let err = solid_offset(&make_sphere(2.0), -3.0).unwrap_err();
+        assert!(err.message.contains("collapses"), "{}", err.message);
+
+        let cone = crate::loft::solid_loft(
+            &[
+                crate::sketch::make_circle(truck_modeling::Point2::new(0.0, 0.0), 2.0).unwrap(),
+                builder::translated(
+                    &crate::sketch::make_circle(truck_modeling::Point2::new(0.0, 0.0), 1.0)
+                        .unwrap(),
+                    Vector3::new(0.0, 0.0, 3.0),
+                ),
+            ],
+            true,
+        )
+        .unwrap();
+        let err = solid_offset(&cone, 0.5).unwrap_err();
+        assert!(err.message.contains("planar, cylindrical and spherical"), "{}", err.message);
+    }
+
+    #[test]
+    fn shell_box_with_open_top() {
+        let cube = make_box(10.0, 10.0, 10.0);
+        let open = solid_shell(&cube, 1.0, &[Vector3::unit_z()]).unwrap();
+        assert_volume(&open, 1000.0 - 8.0 * 8.0 * 9.0);
+        let closed = solid_shell(&cube, 1.0, &[]).unwrap();
+        assert_eq!(closed.boundaries().len(), 2);
+        assert_volume(&closed, 1000.0 - 512.0);
+    }
+
+    #[test]
+    fn shell_cylinder_into_sleeve() {
+        let sleeve = solid_shell(
+            &make_cylinder(5.0, 10.0),
+            1.0,
+            &[Vector3::unit_z(), -Vector3::unit_z()],
+        )
+        .unwrap();
+        assert_volume(&sleeve, PI * (25.0 - 16.0) * 10.0);
+    }
+
+    #[test]
+    fn shell_rejects_bad_input() {
+        let cube = make_box(10.0, 10.0, 10.0);
+        assert!(solid_shell(&cube, 0.0, &[]).is_err());
+        let err = solid_shell(&cube, 1.0, &[Vector3::new(1.0, 1.0, 0.0)]).unwrap_err();
+        assert!(err.message.contains("no planar face"), "{}", err.message);
+        let err = solid_shell(&cube, 1.0, &[Vector3::unit_z(), Vector3::unit_x()]).unwrap_err();
+        assert!(err.message.contains("share an edge"), "{}", err.message);
+        let err = solid_shell(&cube, 6.0, &[]).unwrap_err();
+        assert!(err.message.contains("too large"), "{}", err.message);
+    }
+}
@@ -0,0 +1,221 @@
+//! Polyhedral solids built from closed triangle meshes.
+//!
+//! Neighbouring coplanar triangles are merged into one planar face (with
+//! holes where the merged region has them), and every connected group of
+//! triangles becomes its own shell.
+
+use std::collections::{HashMap, HashSet};
+
+use truck_modeling::{builder, EuclideanSpace, InnerSpace, Plane, Point3, Shell, Surface, Vector3};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::{TruckEdge, TruckFace, TruckSolid, TruckVertex, TruckWire};
+
+/// Normals whose dot product exceeds `1 - COPLANAR_TOLERANCE` are merged
+/// into one face.
+const COPLANAR_TOLERANCE: f64 = 1e-9;
+
+/// Build a solid from a closed, consistently oriented triangle mesh whose
+/// triangles wind counter-clockwise seen from outside.
+///
+/// Every directed edge must be matched by exactly one reversed edge.
+pub(crate) fn solid_from_triangles(
+    points: &[Point3],
+    triangles: &[[usize; 3]],
+) -> GeomResult<TruckSolid> {
+    let normals: Vec<Vector3> = triangles
+        .iter()
+        .map(|t| {
+            let [a, b, c] = t.map(|i| points[i]);
+            (b - a).cross(c - a).normalize()
+        })
+        .collect();
+    let mut owner = HashMap::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            owner.insert((tri[k], tri[(k + 1) % 3]), t);
+        }
+    }
+    let neighbours = |t: usize| {
+        let tri = triangles[t];
+        let owner = &owner;
+        (0..3).filter_map(move |k| owner.get(&(tri[(k + 1) % 3], tri[k])).copied())
+    };
+
+    // Flood-fill groups of triangles sharing the seed triangle's plane, and
+    // the connected bodies those groups belong to.
+    let mut group = vec![usize::MAX; triangles.len()];
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut body = vec![usize::MAX; triangles.len()];
+    let mut bodies = 0;
+    for seed in 0..triangles.len() {
+        if body[seed] == usize::MAX {
+            body[seed] = bodies;
+            let mut stack = vec![seed];
+            while let Some(t) = stack.pop() {
+                for n in neighbours(t) {
+                    if body[n] == usize::MAX {
+                        body[n] = bodies;
+                        stack.push(n);
+                    }
+                }
+            }
+            bodies += 1;
+        }
+        if group[seed] != usize::MAX {
+            continue;
+        }
+        let id = groups.len();
+        group[seed] = id;
+        let mut members = vec![seed];
+        let mut stack = vec![seed];
+        while let Some(t) = stack.pop() {
+            for n in neighbours(t) {
+                if group[n] == usize::MAX
+                    && normals[n].dot(normals[seed]) > 1.0 - COPLANAR_TOLERANCE
+                {
+                    group[n] = id;
+                    members.push(n);
+                    stack.push(n);
+                }
+            }
+        }
+        groups.push(members);
+    }
+
+    let mut vertices: HashMap<usize, TruckVertex> = HashMap::new();
+    let mut edges: HashMap<(usize, usize), TruckEdge> = HashMap::new();
+    let mut shells = vec![Shell::new(); bodies];
+    for members in &groups {
+        let normal = normals[members[0]];
+        let inside: HashSet<(usize, usize)> = members
+            .iter()
+            .flat_map(|&t| {
+                let tri = triangles[t];
+                (0..3).map(move |k| (tri[k], tri[(k + 1) % 3]))
+            })
+            .collect();
+        // The group's boundary: edges whose twin belongs to another face.
+        let mut next: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &(a, b) in &inside {
+            if !inside.contains(&(b, a)) {
+                next.entry(a).or_default().push(b);
+            }
+        }
+        let mut rings = Vec::new();
+        let mut starts: Vec<usize> = next.keys().copied().collect();
+        starts.sort_unstable();
+        for start in starts {
+            while let Some(first) = next.get_mut(&start).and_then(|n| n.pop()) {
+                let mut ring = vec![start];
+                let mut at = first;
+                while at != start {
+                    ring.push(at);
+                    at = next
+                        .get_mut(&at)
+                        .and_then(|n| n.pop())
+                        .ok_or_else(|| invalid("mesh face has a broken boundary".to_string()))?;
+                }
+                rings.push(ring);
+            }
+        }
+        // The outer boundary winds counter-clockwise about the normal and
+        // encloses the most area; the rest are holes.
+        let area = |ring: &[usize]| {
+            let n = ring.len();
+            (0..n)
+                .map(|k| {
+                    points[ring[k]]
+                        .to_vec()
+                        .cross(points[ring[(k + 1) % n]].to_vec())
+                })
+                .fold(Vector3::new(0.0, 0.0, 0.0), |acc, v| acc + v)
+                .dot(normal)
+        };
+        rings.sort_by(|x, y| area(y).total_cmp(&area(x)));
+
+        let mut wire = |ring: &[usize]| -> TruckWire {
+            (0..ring.len())
+                .map(|k| {
+                    let (a, b) = (ring[k], ring[(k + 1) % ring.len()]);
+                    let key = (a.min(b), a.max(b));
+                    let edge = edges.entry(key).or_insert_with(|| {
+                        let mut vertex = |i: usize| {
+                            vertices
+                                .entry(i)
+                                .or_insert_with(|| builder::vertex(points[i]))
+                                .clone()
+                        };
+                        let (v0, v1) = (vertex(key.0), vertex(key.1));
+                        builder::line(&v0, &v1)
+                    });
+                    if a < b {
+                        edge.clone()
+                    } else {
+                        edge.inverse()
+                    }
+                })
+                .collect()
+        };
+        let wires: Vec<_> = rings.iter().map(|r| wire(r)).collect();
+        let origin = points[rings[0][0]];
+        let u = (points[rings[0][1]] - origin).normalize();
+        let plane = Plane::new(origin, origin + u, origin + normal.cross(u));
+        let face = TruckFace::try_new(wires, Surface::Plane(plane))
+            .map_err(|e| invalid(format!("mesh produced an invalid face: {e}")))?;
+        shells[body[members[0]]].push(face);
+    }
+    TruckSolid::try_new(shells).map_err(|e| invalid(format!("mesh produced an invalid solid: {e}")))
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mass::mass_properties;
+
+    /// Unit cube triangles, wound outward, with vertex `i` at the binary
+    /// digits of `i` (x, y, z).
+    fn cube(offset: usize) -> Vec<[usize; 3]> {
+        [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ]
+        .iter()
+        .flat_map(|q| [[q[0], q[1], q[2]], [q[0], q[2], q[3]]])
+        .map(|t| t.map(|i| i + offset))
+        .collect()
+    }
+
+    fn cube_points(at: Vector3) -> Vec<Point3> {
+        (0..8)
+            .map(|i| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64) + at)
+            .collect()
+    }
+
+    #[test]
+    fn merges_coplanar_triangles() {
+        let solid =
+            solid_from_triangles(&cube_points(Vector3::new(0.0, 0.0, 0.0)), &cube(0)).unwrap();
+        assert_eq!(solid.boundaries()[0].len(), 6);
+        assert!((mass_properties(&solid, 1.0, 0.01).unwrap().volume - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn separate_bodies_become_separate_shells() {
+        let mut points = cube_points(Vector3::new(0.0, 0.0, 0.0));
+        points.extend(cube_points(Vector3::new(3.0, 0.0, 0.0)));
+        let mut triangles = cube(0);
+        triangles.extend(cube(8));
+        let solid = solid_from_triangles(&points, &triangles).unwrap();
+        assert_eq!(solid.boundaries().len(), 2);
+        assert!((mass_properties(&solid, 1.0, 0.01).unwrap().volume - 2.0).abs() < 1e-12);
+    }
+}
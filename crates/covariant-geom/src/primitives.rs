@@ -30,27 +30,20 @@ pub fn make_cylinder(radius: f64, height: f64) -> TruckSolid {
 
 /// Create a sphere centered at the origin.
 pub fn make_sphere(radius: f64) -> TruckSolid {
-    // Build a semicircular wire from south pole to north pole, then revolve
-    // it into a face, then revolve that face's wire into a solid.
-    //
-    // Strategy: create a semicircle wire, attach a plane to get a half-disk face,
-    // then rsweep the face by 2π around Z to get a solid.
-    let south = builder::vertex(Point3::new(0.0, 0.0, -radius));
+    // Revolve a meridian from the north pole to the south pole around Z.
+    // `cone` collapses the sweep at the poles, so the shell closes without
+    // degenerate faces, with normals pointing outward.
     let north = builder::vertex(Point3::new(0.0, 0.0, radius));
-    let transit = Point3::new(radius, 0.0, 0.0);
-    let arc = builder::circle_arc(&south, &north, transit);
-    // Close the wire with a straight line from north back to south along Z axis.
-    let line = builder::line(&north, &south);
-    let wire = truck_modeling::Wire::from(vec![arc, line]);
-    let half_disk =
-        builder::try_attach_plane(&[wire]).expect("semicircle+line should form a plane");
-    // Revolve the half-disk face 2π around the Z axis to produce a sphere solid.
-    builder::rsweep(&half_disk, Point3::origin(), Vector3::unit_z(), Rad(2.0 * PI))
+    let south = builder::vertex(Point3::new(0.0, 0.0, -radius));
+    let meridian = builder::circle_arc(&north, &south, Point3::new(radius, 0.0, 0.0));
+    let shell = builder::cone(&vec![meridian].into(), Vector3::unit_z(), Rad(2.0 * PI));
+    TruckSolid::new(vec![shell])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use truck_modeling::{InnerSpace, ParametricSurface, ParametricSurface3D};
 
     #[test]
     fn box_solid_creates_nonempty_boundary() {
@@ -69,4 +62,14 @@ mod tests {
         let solid = make_sphere(8.0);
         assert!(!solid.boundaries().is_empty(), "sphere should have at least one shell");
     }
+
+    #[test]
+    fn sphere_normals_point_outward() {
+        let solid = make_sphere(2.0);
+        for face in solid.boundaries()[0].face_iter() {
+            let surface = face.oriented_surface();
+            let (p, n) = (surface.subs(0.5, 1.0), surface.normal(0.5, 1.0));
+            assert!(p.to_vec().dot(n) > 0.0, "inward normal at {p:?}");
+        }
+    }
 }
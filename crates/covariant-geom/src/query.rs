@@ -0,0 +1,435 @@
+//! Geometric queries: bounding boxes, distances and point containment.
+//!
+//! Queries run on a fine tessellation of the boundary ([`QUERY_TOLERANCE`]),
+//! so results on planar solids are exact and curved extents are within the
+//! tolerance of the true surface.
+
+use truck_meshalgo::analyzers::Collision;
+use truck_modeling::{EuclideanSpace, InnerSpace, MetricSpace, Point3, Vector3};
+use truck_polymesh::PolygonMesh;
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::TruckSolid;
+
+/// Chordal tolerance of the tessellation queries are evaluated on.
+pub const QUERY_TOLERANCE: f64 = 0.01;
+
+/// Axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl BoundingBox {
+    /// Extent along each axis.
+    pub fn size(&self) -> Vector3 {
+        self.max - self.min
+    }
+
+    /// Midpoint of the box.
+    pub fn center(&self) -> Point3 {
+        self.min.midpoint(self.max)
+    }
+
+    /// Distance between two boxes; zero if they touch or overlap.
+    pub fn distance(&self, other: &BoundingBox) -> f64 {
+        let gap = |lo: f64, hi: f64, other_lo: f64, other_hi: f64| {
+            (other_lo - hi).max(lo - other_hi).max(0.0)
+        };
+        Vector3::new(
+            gap(self.min.x, self.max.x, other.min.x, other.max.x),
+            gap(self.min.y, self.max.y, other.min.y, other.max.y),
+            gap(self.min.z, self.max.z, other.min.z, other.max.z),
+        )
+        .magnitude()
+    }
+
+    pub(crate) fn of_points<'a>(points: impl IntoIterator<Item = &'a Point3>) -> Option<BoundingBox> {
+        points.into_iter().fold(None, |acc, p| {
+            Some(match acc {
+                None => BoundingBox { min: *p, max: *p },
+                Some(b) => BoundingBox {
+                    min: Point3::new(b.min.x.min(p.x), b.min.y.min(p.y), b.min.z.min(p.z)),
+                    max: Point3::new(b.max.x.max(p.x), b.max.y.max(p.y), b.max.z.max(p.z)),
+                },
+            })
+        })
+    }
+}
+
+/// Axis-aligned bounding box of a solid.
+pub fn bounding_box(solid: &TruckSolid) -> GeomResult<BoundingBox> {
+    let mesh = query_mesh(solid)?;
+    BoundingBox::of_points(mesh.positions()).ok_or_else(|| invalid("solid is empty".to_string()))
+}
+
+/// Shortest distance between two solids; zero if they touch or overlap.
+pub fn closest_distance(a: &TruckSolid, b: &TruckSolid) -> GeomResult<f64> {
+    let (mesh_a, mesh_b) = (query_mesh(a)?, query_mesh(b)?);
+    let (tris_a, tris_b) = (triangles(&mesh_a), triangles(&mesh_b));
+    if mesh_a.collide_with(&mesh_b).is_some()
+        || winding_number(&tris_b, tris_a[0].points[0]) > 0.5
+        || winding_number(&tris_a, tris_b[0].points[0]) > 0.5
+    {
+        return Ok(0.0);
+    }
+
+    Ok(min_triangle_distance(&tris_a, &tris_b))
+}
+
+/// Whether `point` lies inside the solid or on its boundary.
+pub fn contains_point(solid: &TruckSolid, point: Point3) -> GeomResult<bool> {
+    let mesh = query_mesh(solid)?;
+    let tris = triangles(&mesh);
+    let on_boundary = tris
+        .iter()
+        .any(|t| closest_on_triangle(point, &t.points).distance(point) <= QUERY_TOLERANCE);
+    Ok(on_boundary || winding_number(&tris, point) > 0.5)
+}
+
+fn query_mesh(solid: &TruckSolid) -> GeomResult<PolygonMesh> {
+    let mesh = crate::tessellate::mesh_solid(solid, QUERY_TOLERANCE);
+    if mesh.faces().is_empty() {
+        return Err(invalid("solid has no faces to query".to_string()));
+    }
+    Ok(mesh)
+}
+
+struct Triangle {
+    points: [Point3; 3],
+    bounds: BoundingBox,
+}
+
+fn triangles(mesh: &PolygonMesh) -> Vec<Triangle> {
+    let positions = mesh.positions();
+    mesh.face_iter()
+        .flat_map(|face| {
+            (1..face.len() - 1).map(move |i| {
+                [
+                    positions[face[0].pos],
+                    positions[face[i].pos],
+                    positions[face[i + 1].pos],
+                ]
+            })
+        })
+        .map(|points| Triangle {
+            bounds: BoundingBox::of_points(&points).expect("triangle has points"),
+            points,
+        })
+        .collect()
+}
+
+/// Triangles per leaf of a [`Bvh`].
+const BVH_LEAF_SIZE: usize = 8;
+
+/// Bounding-volume hierarchy over a triangle list, built by median splits
+/// along the longest axis.
+struct Bvh {
+    nodes: Vec<BvhNode>,
+    /// Triangle indices, permuted so each node covers a contiguous range.
+    order: Vec<usize>,
+}
+
+struct BvhNode {
+    bounds: BoundingBox,
+    /// Range of `order` the node covers.
+    range: (usize, usize),
+    /// Child node indices; `None` for a leaf.
+    children: Option<(usize, usize)>,
+}
+
+impl Bvh {
+    fn new(tris: &[Triangle]) -> Bvh {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            order: (0..tris.len()).collect(),
+        };
+        bvh.build(tris, 0, tris.len());
+        bvh
+    }
+
+    fn build(&mut self, tris: &[Triangle], start: usize, end: usize) -> usize {
+        let bounds = BoundingBox::of_points(
+            self.order[start..end]
+                .iter()
+                .flat_map(|&i| [&tris[i].bounds.min, &tris[i].bounds.max]),
+        )
+        .expect("node covers triangles");
+        let id = self.nodes.len();
+        self.nodes.push(BvhNode {
+            bounds,
+            range: (start, end),
+            children: None,
+        });
+        if end - start > BVH_LEAF_SIZE {
+            let size = bounds.size();
+            let axis = if size.x >= size.y && size.x >= size.z {
+                0
+            } else if size.y >= size.z {
+                1
+            } else {
+                2
+            };
+            let mid = (start + end) / 2;
+            self.order[start..end].select_nth_unstable_by(mid - start, |&i, &j| {
+                tris[i].bounds.center()[axis].total_cmp(&tris[j].bounds.center()[axis])
+            });
+            let left = self.build(tris, start, mid);
+            let right = self.build(tris, mid, end);
+            self.nodes[id].children = Some((left, right));
+        }
+        id
+    }
+
+    /// Lowers `best` to the distance from `t` to the nearest indexed
+    /// triangle, skipping subtrees whose bounds are already farther away.
+    fn nearest(&self, tris: &[Triangle], t: &Triangle, best: &mut f64) {
+        let mut stack = vec![0];
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id];
+            if node.bounds.distance(&t.bounds) >= *best {
+                continue;
+            }
+            match node.children {
+                Some((left, right)) => {
+                    // Visit the nearer child first so `best` tightens early.
+                    let near_left = self.nodes[left].bounds.distance(&t.bounds)
+                        <= self.nodes[right].bounds.distance(&t.bounds);
+                    let (near, far) = if near_left {
+                        (left, right)
+                    } else {
+                        (right, left)
+                    };
+                    stack.push(far);
+                    stack.push(near);
+                }
+                None => {
+                    for &i in &self.order[node.range.0..node.range.1] {
+                        if tris[i].bounds.distance(&t.bounds) < *best {
+                            *best = best.min(triangle_distance(t, &tris[i]));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Minimum distance between two triangle sets. Triangles of `a` are visited
+/// in order of their distance to `b`'s bounds, so the search stops once the
+/// remaining ones cannot beat the best pair found.
+fn min_triangle_distance(a: &[Triangle], b: &[Triangle]) -> f64 {
+    let bvh = Bvh::new(b);
+    let root = bvh.nodes[0].bounds;
+    let mut candidates: Vec<(f64, &Triangle)> =
+        a.iter().map(|t| (t.bounds.distance(&root), t)).collect();
+    candidates.sort_by(|x, y| x.0.total_cmp(&y.0));
+    let mut best = f64::INFINITY;
+    for (bound, t) in candidates {
+        if bound >= best {
+            break;
+        }
+        bvh.nearest(b, t, &mut best);
+    }
+    best
+}
+
+/// Generalized winding number of a closed triangle mesh around `point`:
+/// ~1 inside, ~0 outside (Van Oosterom–Strackee solid angles).
+fn winding_number(tris: &[Triangle], point: Point3) -> f64 {
+    let total: f64 = tris
+        .iter()
+        .map(|t| {
+            let [a, b, c] = t.points.map(|p| p - point);
+            let (la, lb, lc) = (a.magnitude(), b.magnitude(), c.magnitude());
+            let numerator = a.dot(b.cross(c));
+            let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+            2.0 * numerator.atan2(denominator)
+        })
+        .sum();
+    total / (4.0 * std::f64::consts::PI)
+}
+
+/// Distance between two non-intersecting triangles: the minimum over
+/// vertex–triangle and edge–edge pairs.
+fn triangle_distance(a: &Triangle, b: &Triangle) -> f64 {
+    let mut best = f64::INFINITY;
+    for (from, to) in [(&a.points, &b.points), (&b.points, &a.points)] {
+        for &p in from {
+            best = best.min(closest_on_triangle(p, to).distance(p));
+        }
+    }
+    for i in 0..3 {
+        for j in 0..3 {
+            let d = segment_distance(
+                a.points[i],
+                a.points[(i + 1) % 3],
+                b.points[j],
+                b.points[(j + 1) % 3],
+            );
+            best = best.min(d);
+        }
+    }
+    best
+}
+
+/// Closest point to `p` on triangle `[a, b, c]` (Ericson, §5.1.5).
+fn closest_on_triangle(p: Point3, [a, b, c]: &[Point3; 3]) -> Point3 {
+    let (ab, ac, ap) = (*b - *a, *c - *a, p - *a);
+    let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return *a;
+    }
+    let bp = p - *b;
+    let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+    if d3 >= 0.0 && d4 <= d3 {
+        return *b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return *a + ab * (d1 / (d1 - d3));
+    }
+    let cp = p - *c;
+    let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+    if d6 >= 0.0 && d5 <= d6 {
+        return *c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return *a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return *b + (*c - *b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denom = va + vb + vc;
+    if denom.abs() < f64::EPSILON {
+        // Degenerate sliver: fall back to its vertices.
+        return [*a, *b, *c]
+            .into_iter()
+            .min_by(|x, y| x.distance2(p).total_cmp(&y.distance2(p)))
+            .expect("three vertices");
+    }
+    *a + ab * (vb / denom) + ac * (vc / denom)
+}
+
+/// Distance between segments `p0 p1` and `q0 q1` (Ericson, §5.1.9).
+fn segment_distance(p0: Point3, p1: Point3, q0: Point3, q1: Point3) -> f64 {
+    let (d1, d2, r) = (p1 - p0, q1 - q0, p0 - q0);
+    let (a, e, f) = (d1.magnitude2(), d2.magnitude2(), d2.dot(r));
+    let (s, t) = if a <= f64::EPSILON && e <= f64::EPSILON {
+        (0.0, 0.0)
+    } else if a <= f64::EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e <= f64::EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            let mut s = if denom > f64::EPSILON {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+    (p0 + d1 * s).distance(q0 + d2 * t)
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder, make_sphere};
+    use truck_modeling::builder;
+
+    fn moved(solid: &TruckSolid, v: Vector3) -> TruckSolid {
+        builder::translated(solid, v)
+    }
+
+    #[test]
+    fn box_bounds_are_exact() {
+        let b = bounding_box(&moved(&make_box(2.0, 4.0, 6.0), Vector3::new(1.0, 0.0, -3.0)))
+            .unwrap();
+        assert_eq!(b.min, Point3::new(1.0, 0.0, -3.0));
+        assert_eq!(b.max, Point3::new(3.0, 4.0, 3.0));
+        assert_eq!(b.size(), Vector3::new(2.0, 4.0, 6.0));
+        assert_eq!(b.center(), Point3::new(2.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn curved_bounds_within_tolerance() {
+        let b = bounding_box(&make_cylinder(5.0, 10.0)).unwrap();
+        assert!((b.min - Point3::new(-5.0, -5.0, 0.0)).magnitude() < QUERY_TOLERANCE * 2.0);
+        assert!((b.max - Point3::new(5.0, 5.0, 10.0)).magnitude() < QUERY_TOLERANCE * 2.0);
+    }
+
+    #[test]
+    fn distance_between_separated_solids() {
+        let a = make_box(10.0, 10.0, 10.0);
+        let b = moved(&make_box(5.0, 5.0, 5.0), Vector3::new(13.0, 2.0, 2.0));
+        assert!((closest_distance(&a, &b).unwrap() - 3.0).abs() < 1e-9);
+        // Edge to edge across a diagonal gap.
+        let c = moved(&make_box(5.0, 5.0, 5.0), Vector3::new(13.0, 14.0, 2.0));
+        assert!((closest_distance(&a, &c).unwrap() - 5.0).abs() < 1e-9);
+        let s = moved(&make_sphere(2.0), Vector3::new(5.0, 5.0, 20.0));
+        assert!((closest_distance(&a, &s).unwrap() - 8.0).abs() < 2.0 * QUERY_TOLERANCE);
+    }
+
+    #[test]
+    fn pruned_search_matches_all_pairs() {
+        let coarse = |solid: &TruckSolid| triangles(&crate::tessellate::mesh_solid(solid, 0.2));
+        let a = coarse(&make_sphere(3.0));
+        let b = coarse(&moved(
+            &make_cylinder(2.0, 5.0),
+            Vector3::new(4.0, 6.0, -1.0),
+        ));
+        let all_pairs = a
+            .iter()
+            .flat_map(|ta| b.iter().map(move |tb| triangle_distance(ta, tb)))
+            .fold(f64::INFINITY, f64::min);
+        assert_eq!(min_triangle_distance(&a, &b), all_pairs);
+        assert_eq!(min_triangle_distance(&b, &a), all_pairs);
+    }
+
+    #[test]
+    fn overlapping_touching_and_nested_solids_have_zero_distance() {
+        let a = make_box(10.0, 10.0, 10.0);
+        let crossing = moved(&make_box(20.0, 2.0, 2.0), Vector3::new(-5.0, 4.0, 4.0));
+        let touching = moved(&make_box(5.0, 5.0, 5.0), Vector3::new(10.0, 0.0, 0.0));
+        let nested = moved(&make_box(2.0, 2.0, 2.0), Vector3::new(4.0, 4.0, 4.0));
+        for b in [crossing, touching, nested] {
+            assert!(closest_distance(&a, &b).unwrap().abs() < 1e-9);
+            assert!(closest_distance(&b, &a).unwrap().abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn point_containment() {
+        let cyl = make_cylinder(5.0, 10.0);
+        assert!(contains_point(&cyl, Point3::new(0.0, 0.0, 5.0)).unwrap());
+        assert!(contains_point(&cyl, Point3::new(4.0, 0.0, 9.0)).unwrap());
+        assert!(contains_point(&cyl, Point3::new(0.0, 0.0, 10.0)).unwrap());
+        assert!(!contains_point(&cyl, Point3::new(4.0, 4.0, 5.0)).unwrap());
+        assert!(!contains_point(&cyl, Point3::new(0.0, 0.0, 10.5)).unwrap());
+
+        let hollow = crate::offset::solid_shell(&make_box(10.0, 10.0, 10.0), 1.0, &[]).unwrap();
+        assert!(!contains_point(&hollow, Point3::new(5.0, 5.0, 5.0)).unwrap());
+        assert!(contains_point(&hollow, Point3::new(0.5, 5.0, 5.0)).unwrap());
+    }
+}
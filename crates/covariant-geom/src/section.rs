@@ -0,0 +1,553 @@
+//! Planar sections and silhouette projections of solids.
+//!
+//! Both run on a welded tessellation of the boundary ([`SECTION_TOLERANCE`])
+//! and return polygonal faces lying in the given plane, in model
+//! coordinates: outer boundaries wind counter-clockwise about the plane
+//! normal and holes clockwise, so the faces can be extruded directly.
+
+use std::collections::{HashMap, HashSet};
+
+use truck_meshalgo::filters::OptimizingFilter;
+use truck_modeling::{builder, InnerSpace, Point2, Point3, Vector2, Vector3};
+use truck_polymesh::PolygonMesh;
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::{TruckFace, TruckSolid, TruckWire};
+
+/// Chordal tolerance of the tessellation sections are cut from.
+pub const SECTION_TOLERANCE: f64 = 0.01;
+
+/// Tolerance, relative to the mesh bounding box, for welding vertices that
+/// adjacent faces of the tessellation share.
+const WELD_TOLERANCE: f64 = 1e-8;
+
+/// Cross-section of a solid by the plane through `origin` with `normal`.
+///
+/// Returns one face per connected region of material in the plane, with
+/// holes; a plane that misses the solid gives no faces.
+pub fn solid_section(
+    solid: &TruckSolid,
+    origin: Point3,
+    normal: Vector3,
+) -> GeomResult<Vec<TruckFace>> {
+    let frame = Frame::new(origin, normal)?;
+    let mesh = welded_mesh(solid)?;
+    let positions = mesh.positions();
+    let above: Vec<bool> = positions
+        .iter()
+        .map(|p| (p - origin).dot(frame.normal) >= 0.0)
+        .collect();
+
+    // Each triangle the plane crosses gives one segment, from where the
+    // plane leaves it to where it enters (walking the triangle
+    // counter-clockwise), which keeps material on the segment's left.
+    // Crossings are keyed by the mesh edge they lie on, so neighbouring
+    // triangles join up exactly.
+    let mut crossings: HashMap<(usize, usize), Point2> = HashMap::new();
+    let mut next: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+    for tri in triangles(&mesh) {
+        let (mut exit, mut entry) = (None, None);
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            if above[a] == above[b] {
+                continue;
+            }
+            let key = (a.min(b), a.max(b));
+            crossings.entry(key).or_insert_with(|| {
+                let (p, q) = (positions[key.0], positions[key.1]);
+                let (dp, dq) = (
+                    (p - origin).dot(frame.normal),
+                    (q - origin).dot(frame.normal),
+                );
+                frame.to_plane(p + (q - p) * (dp / (dp - dq)))
+            });
+            if above[a] {
+                exit = Some(key);
+            } else {
+                entry = Some(key);
+            }
+        }
+        if let (Some(exit), Some(entry)) = (exit, entry) {
+            next.entry(exit).or_default().push(entry);
+        }
+    }
+
+    let loops = chain(next, "section")?
+        .into_iter()
+        .map(|keys| keys.iter().map(|k| crossings[k]).collect())
+        .collect();
+    faces_from_loops(&frame, loops)
+}
+
+/// Outline of a solid's shadow on the plane through `origin` with `normal`,
+/// projecting along the normal.
+///
+/// Returns one face per connected region of the shadow, with holes where
+/// one can see through the solid.
+pub fn solid_project(
+    solid: &TruckSolid,
+    origin: Point3,
+    normal: Vector3,
+) -> GeomResult<Vec<TruckFace>> {
+    let frame = Frame::new(origin, normal)?;
+    let mesh = welded_mesh(solid)?;
+    let positions = mesh.positions();
+    let tris = triangles(&mesh);
+
+    // The shadow is covered by the triangles facing the normal; their
+    // outline is the contour, wound with the shadow on its left.
+    let facing: Vec<bool> = tris
+        .iter()
+        .map(|t| {
+            let [a, b, c] = t.map(|i| positions[i]);
+            let cross = (b - a).cross(c - a);
+            cross.dot(frame.normal) > 1e-9 * cross.magnitude()
+        })
+        .collect();
+    let mut owner = HashMap::new();
+    for (i, t) in tris.iter().enumerate() {
+        for k in 0..3 {
+            owner.insert((t[k], t[(k + 1) % 3]), i);
+        }
+    }
+    let contour: Vec<(usize, usize)> = tris
+        .iter()
+        .zip(&facing)
+        .filter(|&(_, &facing)| facing)
+        .flat_map(|(t, _)| (0..3).map(move |k| (t[k], t[(k + 1) % 3])))
+        .filter(|&(a, b)| !owner.get(&(b, a)).is_some_and(|&i| facing[i]))
+        .collect();
+    if contour.is_empty() {
+        return Err(invalid("solid casts no shadow on the plane".to_string()));
+    }
+
+    let mut points: Vec<Point2> = positions.iter().map(|p| frame.to_plane(*p)).collect();
+    let scale = contour
+        .iter()
+        .flat_map(|&(a, b)| [points[a], points[b]])
+        .map(|p| p.x.abs().max(p.y.abs()))
+        .fold(0.0, f64::max)
+        .max(1.0);
+    let eps = 1e-9 * scale;
+
+    // Split contour edges where they cross or touch each other.
+    let mut splits: Vec<Vec<(f64, usize)>> = contour
+        .iter()
+        .map(|&(a, b)| vec![(0.0, a), (1.0, b)])
+        .collect();
+    let mut order: Vec<usize> = (0..contour.len()).collect();
+    let min_x = |s: usize| points[contour[s].0].x.min(points[contour[s].1].x);
+    let max_x = |s: usize| points[contour[s].0].x.max(points[contour[s].1].x);
+    order.sort_by(|&s, &t| min_x(s).total_cmp(&min_x(t)));
+    let mut crossings = Vec::new();
+    for (k, &s) in order.iter().enumerate() {
+        for &t in &order[k + 1..] {
+            if min_x(t) > max_x(s) + eps {
+                break;
+            }
+            let ((a, b), (c, d)) = (contour[s], contour[t]);
+            for (seg, other, ends) in [(s, (a, b), [c, d]), (t, (c, d), [a, b])] {
+                for end in ends {
+                    if let Some(param) = interior_param(&points, other, points[end], eps) {
+                        splits[seg].push((param, end));
+                    }
+                }
+            }
+            if [a, b].contains(&c) || [a, b].contains(&d) {
+                continue;
+            }
+            if let Some((ps, pt, at)) = proper_crossing(&points, (a, b), (c, d), eps) {
+                crossings.push(at);
+                let id = points.len() + crossings.len() - 1;
+                splits[s].push((ps, id));
+                splits[t].push((pt, id));
+            }
+        }
+    }
+    points.extend(crossings);
+
+    // Keep the pieces with shadow on their left and none on their right.
+    let offset = 1e-6 * scale;
+    let mut kept = HashSet::new();
+    let mut next: HashMap<usize, Vec<usize>> = HashMap::new();
+    for mut cuts in splits {
+        cuts.sort_by(|x, y| x.0.total_cmp(&y.0));
+        cuts.dedup_by_key(|c| c.1);
+        for pair in cuts.windows(2) {
+            let (a, b) = (pair[0].1, pair[1].1);
+            let along = points[b] - points[a];
+            if along.magnitude() <= eps || kept.contains(&(a, b)) {
+                continue;
+            }
+            let mid = points[a] + along / 2.0;
+            let left = Vector2::new(-along.y, along.x).normalize() * offset;
+            let inside = winding_number(&points, &contour, mid + left) > 0;
+            let outside = winding_number(&points, &contour, mid - left) == 0;
+            if inside && outside {
+                kept.insert((a, b));
+                next.entry(a).or_default().push(b);
+            }
+        }
+    }
+
+    let loops = chain(next, "projection")?
+        .into_iter()
+        .map(|ids| ids.iter().map(|&i| points[i]).collect())
+        .collect();
+    faces_from_loops(&frame, loops)
+}
+
+/// An orthonormal frame on a plane; `u × v` is the normal.
+struct Frame {
+    origin: Point3,
+    normal: Vector3,
+    u: Vector3,
+    v: Vector3,
+}
+
+impl Frame {
+    fn new(origin: Point3, normal: Vector3) -> GeomResult<Self> {
+        if normal.magnitude() < 1e-12 {
+            return Err(invalid("plane normal must be non-zero".to_string()));
+        }
+        let normal = normal.normalize();
+        let u = crate::curve::perpendicular(normal);
+        Ok(Self {
+            origin,
+            normal,
+            u,
+            v: normal.cross(u),
+        })
+    }
+
+    fn to_plane(&self, p: Point3) -> Point2 {
+        let d = p - self.origin;
+        Point2::new(d.dot(self.u), d.dot(self.v))
+    }
+
+    fn to_model(&self, p: Point2) -> Point3 {
+        self.origin + self.u * p.x + self.v * p.y
+    }
+}
+
+fn welded_mesh(solid: &TruckSolid) -> GeomResult<PolygonMesh> {
+    let mut mesh = crate::tessellate::mesh_solid(solid, SECTION_TOLERANCE);
+    if mesh.faces().is_empty() {
+        return Err(invalid("solid has no faces".to_string()));
+    }
+    mesh.put_together_same_attrs(WELD_TOLERANCE);
+    Ok(mesh)
+}
+
+/// The mesh's polygons fanned into triangles, dropping those that welding
+/// collapsed.
+fn triangles(mesh: &PolygonMesh) -> Vec<[usize; 3]> {
+    mesh.face_iter()
+        .flat_map(|face| {
+            (1..face.len() - 1).map(move |i| [face[0].pos, face[i].pos, face[i + 1].pos])
+        })
+        .filter(|[a, b, c]| a != b && b != c && c != a)
+        .collect()
+}
+
+/// Follow successor links into closed loops, using every link once.
+fn chain<K>(mut next: HashMap<K, Vec<K>>, what: &str) -> GeomResult<Vec<Vec<K>>>
+where
+    K: Copy + Eq + std::hash::Hash + Ord,
+{
+    let mut starts: Vec<K> = next.keys().copied().collect();
+    starts.sort_unstable();
+    let mut loops = Vec::new();
+    for start in starts {
+        while next.get(&start).is_some_and(|n| !n.is_empty()) {
+            let mut ring = vec![start];
+            let mut at = start;
+            loop {
+                let Some(to) = next.get_mut(&at).and_then(|n| n.pop()) else {
+                    return Err(GeomError::new(
+                        GeomErrorKind::TessellationFailed,
+                        format!("{what} outline does not close; the solid's boundary may be open"),
+                    ));
+                };
+                if to == start {
+                    break;
+                }
+                ring.push(to);
+                at = to;
+            }
+            loops.push(ring);
+        }
+    }
+    Ok(loops)
+}
+
+/// Parameter along segment `(a, b)` of a point strictly inside it, if the
+/// point lies on it.
+fn interior_param(points: &[Point2], (a, b): (usize, usize), p: Point2, eps: f64) -> Option<f64> {
+    let (pa, pb) = (points[a], points[b]);
+    let along = pb - pa;
+    let length2 = along.magnitude2();
+    if length2 <= eps * eps {
+        return None;
+    }
+    let t = (p - pa).dot(along) / length2;
+    let off = (pa + along * t - p).magnitude();
+    let margin = eps / length2.sqrt();
+    (off <= eps && t > margin && t < 1.0 - margin).then_some(t)
+}
+
+/// Where two segments cross at a point inside both, with the parameters
+/// along each.
+fn proper_crossing(
+    points: &[Point2],
+    (a, b): (usize, usize),
+    (c, d): (usize, usize),
+    eps: f64,
+) -> Option<(f64, f64, Point2)> {
+    let (pa, pb, pc, pd) = (points[a], points[b], points[c], points[d]);
+    let cross = |u: Vector2, v: Vector2| u.x * v.y - u.y * v.x;
+    let (r, s) = (pb - pa, pd - pc);
+    let denom = cross(r, s);
+    if denom.abs() <= eps * r.magnitude().max(s.magnitude()) {
+        return None;
+    }
+    let t = cross(pc - pa, s) / denom;
+    let u = cross(pc - pa, r) / denom;
+    let (mt, mu) = (eps / r.magnitude(), eps / s.magnitude());
+    (t > mt && t < 1.0 - mt && u > mu && u < 1.0 - mu).then(|| (t, u, pa + r * t))
+}
+
+/// Winding number of the contour around `p`.
+fn winding_number(points: &[Point2], contour: &[(usize, usize)], p: Point2) -> i32 {
+    contour
+        .iter()
+        .map(|&(a, b)| {
+            let (pa, pb) = (points[a], points[b]);
+            let side = (pb.x - pa.x) * (p.y - pa.y) - (p.x - pa.x) * (pb.y - pa.y);
+            if pa.y <= p.y && pb.y > p.y && side > 0.0 {
+                1
+            } else if pa.y > p.y && pb.y <= p.y && side < 0.0 {
+                -1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Build planar faces from closed loops: counter-clockwise loops bound
+/// regions, clockwise loops are holes in the smallest region around them.
+fn faces_from_loops(frame: &Frame, loops: Vec<Vec<Point2>>) -> GeomResult<Vec<TruckFace>> {
+    let loops: Vec<_> = loops
+        .into_iter()
+        .map(simplify)
+        .filter(|ring| ring.len() >= 3 && signed_area(ring).abs() > 1e-12)
+        .collect();
+    let (outers, holes): (Vec<_>, Vec<_>) = loops.into_iter().partition(|r| signed_area(r) > 0.0);
+    let mut regions: Vec<Vec<Vec<Point2>>> = outers.into_iter().map(|r| vec![r]).collect();
+    for hole in holes {
+        let around = regions
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| contains(&r[0], hole[0]))
+            .min_by(|(_, x), (_, y)| signed_area(&x[0]).total_cmp(&signed_area(&y[0])))
+            .map(|(i, _)| i);
+        if let Some(i) = around {
+            regions[i].push(hole);
+        }
+    }
+    regions
+        .into_iter()
+        .map(|rings| {
+            let wires: Vec<_> = rings.iter().map(|r| wire(frame, r)).collect();
+            builder::try_attach_plane(&wires).map_err(|e| {
+                GeomError::new(
+                    GeomErrorKind::TessellationFailed,
+                    format!("outline does not form a planar face: {e}"),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Drop repeated points and points in the middle of straight runs.
+fn simplify(mut ring: Vec<Point2>) -> Vec<Point2> {
+    let mut changed = true;
+    while changed && ring.len() >= 3 {
+        changed = false;
+        let n = ring.len();
+        for i in 0..n {
+            let (prev, at, next) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+            let (u, v) = (at - prev, next - at);
+            let cross = u.x * v.y - u.y * v.x;
+            if u.magnitude() < 1e-9 || cross.abs() <= 1e-9 * u.magnitude() * v.magnitude() {
+                ring.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    ring
+}
+
+fn signed_area(ring: &[Point2]) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Even-odd point-in-polygon test.
+fn contains(ring: &[Point2], p: Point2) -> bool {
+    let n = ring.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (ring[i], ring[(i + 1) % n]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn wire(frame: &Frame, ring: &[Point2]) -> TruckWire {
+    let vertices: Vec<_> = ring
+        .iter()
+        .map(|p| builder::vertex(frame.to_model(*p)))
+        .collect();
+    (0..vertices.len())
+        .map(|i| builder::line(&vertices[i], &vertices[(i + 1) % vertices.len()]))
+        .collect()
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{make_box, make_cylinder, make_sphere};
+    use std::f64::consts::PI;
+    use truck_modeling::{builder, EuclideanSpace};
+
+    /// Total area, as the volume of a unit extrusion.
+    fn area(faces: &[TruckFace]) -> f64 {
+        faces
+            .iter()
+            .map(|f| {
+                let slab = crate::sweep::solid_extrude(f, 1.0).unwrap();
+                crate::mass::mass_properties(&slab, 1.0, 0.001)
+                    .unwrap()
+                    .volume
+            })
+            .sum()
+    }
+
+    fn pierced_box() -> TruckSolid {
+        let cutter = builder::translated(&make_box(4.0, 4.0, 20.0), Vector3::new(3.0, 3.0, -5.0));
+        crate::boolean::solid_difference(&make_box(10.0, 10.0, 10.0), &cutter, 0.05).unwrap()
+    }
+
+    #[test]
+    fn section_of_a_box_is_a_rectangle() {
+        let faces = solid_section(
+            &make_box(2.0, 3.0, 4.0),
+            Point3::new(0.0, 0.0, 1.0),
+            Vector3::unit_z(),
+        )
+        .unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].boundaries()[0].len(), 4);
+        assert!((area(&faces) - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn section_keeps_holes() {
+        let faces = solid_section(
+            &pierced_box(),
+            Point3::new(0.0, 0.0, 5.0),
+            Vector3::unit_z(),
+        )
+        .unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].boundaries().len(), 2);
+        assert!((area(&faces) - 84.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn section_through_a_vertex_and_across_a_cylinder() {
+        // Oblique plane through three corners of the cube.
+        let normal = Vector3::new(1.0, 1.0, 1.0);
+        let faces =
+            solid_section(&make_box(2.0, 2.0, 2.0), Point3::new(2.0, 2.0, 0.0), normal).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].boundaries()[0].len(), 3);
+        assert!((area(&faces) - 2.0 * 3.0_f64.sqrt()).abs() < 1e-9);
+
+        let faces = solid_section(
+            &make_cylinder(2.0, 5.0),
+            Point3::new(0.0, 0.0, 0.0),
+            Vector3::unit_x(),
+        )
+        .unwrap();
+        let a = area(&faces);
+        assert!(
+            a <= 20.0 + 1e-9 && a > (4.0 - 2.0 * SECTION_TOLERANCE) * 5.0,
+            "{a}"
+        );
+    }
+
+    #[test]
+    fn section_missing_the_solid_is_empty() {
+        let faces = solid_section(
+            &make_sphere(1.0),
+            Point3::new(0.0, 0.0, 5.0),
+            Vector3::unit_z(),
+        )
+        .unwrap();
+        assert!(faces.is_empty());
+    }
+
+    #[test]
+    fn projection_of_a_sphere_is_a_disc() {
+        let faces = solid_project(&make_sphere(2.0), Point3::origin(), Vector3::unit_z()).unwrap();
+        assert_eq!(faces.len(), 1);
+        let a = area(&faces);
+        assert!(a < 4.0 * PI && a > 4.0 * PI * 0.99, "{a}");
+    }
+
+    #[test]
+    fn projection_merges_overlapping_parts_and_keeps_holes() {
+        // Two crossing bars at different heights project to a plus sign.
+        let bar_x = builder::translated(&make_box(10.0, 2.0, 1.0), Vector3::new(-5.0, -1.0, 0.0));
+        let bar_y = builder::translated(&make_box(2.0, 10.0, 1.0), Vector3::new(-1.0, -5.0, 3.0));
+        let bars = crate::boolean::solid_union_many(vec![bar_x, bar_y], 0.05).unwrap();
+        let faces = solid_project(&bars, Point3::origin(), Vector3::unit_z()).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].boundaries()[0].len(), 12);
+        assert!((area(&faces) - 36.0).abs() < 1e-9);
+
+        let faces = solid_project(&pierced_box(), Point3::origin(), Vector3::unit_z()).unwrap();
+        assert_eq!(faces[0].boundaries().len(), 2);
+        assert!((area(&faces) - 84.0).abs() < 1e-9);
+
+        let faces = solid_project(&pierced_box(), Point3::origin(), Vector3::unit_x()).unwrap();
+        assert_eq!(faces[0].boundaries().len(), 1);
+        assert!((area(&faces) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_zero_normal() {
+        assert!(solid_section(
+            &make_box(1.0, 1.0, 1.0),
+            Point3::origin(),
+            Vector3::new(0.0, 0.0, 0.0)
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,446 @@
+//! Planar profile (sketch) construction.
+//!
+//! Profiles live in the XY plane (z = 0). Outer boundaries are wound
+//! counter-clockwise so the face normal points along +Z; hole boundaries
+//! are wound clockwise, as truck's plane attachment expects.
+
+use std::f64::consts::PI;
+use truck_modeling::{
+    builder, BoundedCurve, EuclideanSpace, InnerSpace, ParametricCurve, ParametricSurface3D,
+    Point2, Point3, Rad, Vector3,
+};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::{TruckFace, TruckWire};
+
+/// Number of samples taken along each boundary edge when checking where
+/// holes lie.
+const EDGE_SAMPLES: usize = 32;
+
+/// Create a closed circular wire in the XY plane, wound counter-clockwise.
+pub fn circle_wire(center: Point2, radius: f64) -> GeomResult<TruckWire> {
+    if radius <= 0.0 {
+        return Err(GeomError::new(
+            GeomErrorKind::InvalidInput,
+            format!("circle radius must be positive, got {radius}"),
+        ));
+    }
+    let vertex = builder::vertex(Point3::new(center.x + radius, center.y, 0.0));
+    Ok(builder::rsweep(
+        &vertex,
+        Point3::new(center.x, center.y, 0.0),
+        Vector3::unit_z(),
+        Rad(2.0 * PI),
+    ))
+}
+
+/// Create a closed polygonal wire in the XY plane, wound counter-clockwise.
+///
+/// A trailing point equal to the first one is ignored, so both open and
+/// explicitly closed point loops are accepted. The loop must have at least
+/// three distinct points, non-zero area, and no self-intersections.
+pub fn polygon_wire(points: &[Point2]) -> GeomResult<TruckWire> {
+    let mut pts = points.to_vec();
+    if pts.len() > 1 && pts.first() == pts.last() {
+        pts.pop();
+    }
+    validate_polygon(&pts)?;
+    if signed_area(&pts) < 0.0 {
+        pts.reverse();
+    }
+
+    let vertices: Vec<_> = pts
+        .iter()
+        .map(|p| builder::vertex(Point3::new(p.x, p.y, 0.0)))
+        .collect();
+    let edges: Vec<_> = (0..vertices.len())
+        .map(|i| builder::line(&vertices[i], &vertices[(i + 1) % vertices.len()]))
+        .collect();
+    Ok(TruckWire::from(edges))
+}
+
+/// Attach a planar face to a single closed outer wire.
+pub fn face_from_wire(wire: TruckWire) -> GeomResult<TruckFace> {
+    builder::try_attach_plane(&[wire]).map_err(|e| {
+        GeomError::new(
+            GeomErrorKind::InvalidInput,
+            format!("profile boundary does not form a planar face: {e}"),
+        )
+    })
+}
+
+/// Create a circular face in the XY plane.
+pub fn make_circle(center: Point2, radius: f64) -> GeomResult<TruckFace> {
+    face_from_wire(circle_wire(center, radius)?)
+}
+
+/// Create a rectangular face in the XY plane spanning `origin` to
+/// `origin + (width, height)`.
+pub fn make_rectangle(origin: Point2, width: f64, height: f64) -> GeomResult<TruckFace> {
+    if width <= 0.0 || height <= 0.0 {
+        return Err(GeomError::new(
+            GeomErrorKind::InvalidInput,
+            format!("rectangle size must be positive, got {width} x {height}"),
+        ));
+    }
+    face_from_wire(polygon_wire(&[
+        origin,
+        Point2::new(origin.x + width, origin.y),
+        Point2::new(origin.x + width, origin.y + height),
+        Point2::new(origin.x, origin.y + height),
+    ])?)
+}
+
+/// Create a polygonal face in the XY plane from a closed point loop.
+pub fn make_polygon(points: &[Point2]) -> GeomResult<TruckFace> {
+    face_from_wire(polygon_wire(points)?)
+}
+
+/// Cut the outer boundaries of `holes` out of `outer`.
+///
+/// All faces must be coplanar and the holes must lie inside the outer
+/// boundary without overlapping each other or the holes already present in
+/// `outer`, which are kept. Hole faces with holes of their own are
+/// rejected: their inner boundaries would become islands, which a single
+/// face cannot hold.
+pub fn face_with_holes(outer: &TruckFace, holes: &[TruckFace]) -> GeomResult<TruckFace> {
+    let invalid = |msg: String| GeomError::new(GeomErrorKind::InvalidInput, msg);
+    let mut wires = outer.boundaries();
+    let existing = wires.len() - 1;
+    for (i, hole) in holes.iter().enumerate() {
+        let mut boundaries = hole.boundaries().into_iter();
+        let boundary = boundaries
+            .next()
+            .ok_or_else(|| invalid(format!("hole {i} has no boundary")))?;
+        if boundaries.next().is_some() {
+            return Err(invalid(format!(
+                "hole {i} has holes of its own; nested profiles are not supported"
+            )));
+        }
+        wires.push(boundary.inverse());
+    }
+    let face = builder::try_attach_plane(&wires).map_err(|e| {
+        invalid(format!(
+            "holes must be coplanar with the outer profile: {e}"
+        ))
+    })?;
+    check_hole_layout(&face, existing)?;
+    Ok(face)
+}
+
+/// Reject inner boundaries that leave the outer boundary or overlap one
+/// another.
+///
+/// The first `existing` inner boundaries came with the outer face and are
+/// only checked against the new holes that follow them.
+fn check_hole_layout(face: &TruckFace, existing: usize) -> GeomResult<()> {
+    let polygons = boundary_polygons(face);
+    let (outer, inner) = polygons
+        .split_first()
+        .expect("an attached face has an outer boundary");
+    let name = |k: usize| {
+        if k < existing {
+            format!("existing hole {k}")
+        } else {
+            format!("hole {}", k - existing)
+        }
+    };
+    let invalid = |msg: String| Err(GeomError::new(GeomErrorKind::InvalidInput, msg));
+    for (k, hole) in inner.iter().enumerate().skip(existing) {
+        if polylines_cross(outer, hole) || !hole.iter().all(|&p| point_in_polygon(p, outer)) {
+            return invalid(format!("{} is not inside the outer profile", name(k)));
+        }
+        for (j, other) in inner[..k].iter().enumerate() {
+            if polylines_cross(hole, other)
+                || point_in_polygon(hole[0], other)
+                || point_in_polygon(other[0], hole)
+            {
+                return invalid(format!("{} overlaps {}", name(k), name(j)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sample every boundary wire of a planar face into a closed polyline in
+/// 2D coordinates of the face's plane.
+fn boundary_polygons(face: &TruckFace) -> Vec<Vec<Point2>> {
+    let normal = face.oriented_surface().normal(0.0, 0.0).normalize();
+    let seed = if normal.x.abs() < 0.9 {
+        Vector3::unit_x()
+    } else {
+        Vector3::unit_y()
+    };
+    let u = normal.cross(seed).normalize();
+    let v = normal.cross(u);
+    face.boundaries()
+        .iter()
+        .map(|wire| {
+            wire.edge_iter()
+                .flat_map(|edge| {
+                    let curve = edge.oriented_curve();
+                    let (t0, t1) = curve.range_tuple();
+                    (0..EDGE_SAMPLES).map(move |i| {
+                        let p = curve
+                            .subs(t0 + (t1 - t0) * i as f64 / EDGE_SAMPLES as f64)
+                            .to_vec();
+                        Point2::new(p.dot(u), p.dot(v))
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns `true` if any edge of closed polyline `a` touches or crosses any
+/// edge of closed polyline `b`.
+fn polylines_cross(a: &[Point2], b: &[Point2]) -> bool {
+    let (n, m) = (a.len(), b.len());
+    (0..n).any(|i| (0..m).any(|j| segments_intersect(a[i], a[(i + 1) % n], b[j], b[(j + 1) % m])))
+}
+
+/// Even-odd point-in-polygon test.
+fn point_in_polygon(p: Point2, pts: &[Point2]) -> bool {
+    let n = pts.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (pts[i], pts[(i + 1) % n]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Signed area of a closed polygon (positive when counter-clockwise).
+fn signed_area(pts: &[Point2]) -> f64 {
+    let n = pts.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (pts[i], pts[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Reject polygons that cannot bound a face.
+fn validate_polygon(pts: &[Point2]) -> GeomResult<()> {
+    let invalid = |msg: String| Err(GeomError::new(GeomErrorKind::InvalidInput, msg));
+    let n = pts.len();
+    if n < 3 {
+        return invalid(format!("polygon needs at least 3 points, got {n}"));
+    }
+    for i in 0..n {
+        if pts[i] == pts[(i + 1) % n] {
+            return invalid(format!("polygon has repeated consecutive point {i}"));
+        }
+    }
+    if signed_area(pts).abs() < 1e-12 {
+        return invalid("polygon has zero area".to_string());
+    }
+    for i in 0..n {
+        for j in (i + 1)..n {
+            // Adjacent edges share an endpoint; skip them.
+            if j == i + 1 || (i == 0 && j == n - 1) {
+                continue;
+            }
+            let (a0, a1) = (pts[i], pts[(i + 1) % n]);
+            let (b0, b1) = (pts[j], pts[(j + 1) % n]);
+            if segments_intersect(a0, a1, b0, b1) {
+                return invalid(format!("polygon edges {i} and {j} intersect"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if the closed segments `a0-a1` and `b0-b1` touch or cross.
+fn segments_intersect(a0: Point2, a1: Point2, b0: Point2, b1: Point2) -> bool {
+    let cross =
+        |o: Point2, p: Point2, q: Point2| (p.x - o.x) * (q.y - o.y) - (p.y - o.y) * (q.x - o.x);
+    let on_segment = |o: Point2, p: Point2, q: Point2| {
+        q.x >= o.x.min(p.x) && q.x <= o.x.max(p.x) && q.y >= o.y.min(p.y) && q.y <= o.y.max(p.y)
+    };
+    let d1 = cross(b0, b1, a0);
+    let d2 = cross(b0, b1, a1);
+    let d3 = cross(a0, a1, b0);
+    let d4 = cross(a0, a1, b1);
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+    (d1 == 0.0 && on_segment(b0, b1, a0))
+        || (d2 == 0.0 && on_segment(b0, b1, a1))
+        || (d3 == 0.0 && on_segment(a0, a1, b0))
+        || (d4 == 0.0 && on_segment(a0, a1, b1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use truck_modeling::{InnerSpace, ParametricSurface3D};
+
+    fn normal_of(face: &TruckFace) -> Vector3 {
+        let n = face.oriented_surface().normal(0.5, 0.5);
+        n.normalize()
+    }
+
+    #[test]
+    fn circle_face_points_up() {
+        let face = make_circle(Point2::new(1.0, 2.0), 3.0).unwrap();
+        assert!((normal_of(&face) - Vector3::unit_z()).magnitude() < 1e-9);
+    }
+
+    #[test]
+    fn circle_rejects_non_positive_radius() {
+        let err = make_circle(Point2::new(0.0, 0.0), 0.0).unwrap_err();
+        assert_eq!(err.kind, GeomErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn rectangle_face_has_four_edges() {
+        let face = make_rectangle(Point2::new(0.0, 0.0), 4.0, 2.0).unwrap();
+        assert_eq!(face.boundaries()[0].len(), 4);
+    }
+
+    #[test]
+    fn clockwise_polygon_is_reoriented() {
+        let pts = [
+            Point2::new(0.0, 0.0),
+            Point2::new(0.0, 1.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(1.0, 0.0),
+        ];
+        let face = make_polygon(&pts).unwrap();
+        assert!((normal_of(&face) - Vector3::unit_z()).magnitude() < 1e-9);
+    }
+
+    #[test]
+    fn explicitly_closed_polygon_accepted() {
+        let pts = [
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(0.0, 0.0),
+        ];
+        let face = make_polygon(&pts).unwrap();
+        assert_eq!(face.boundaries()[0].len(), 3);
+    }
+
+    #[test]
+    fn degenerate_polygons_rejected() {
+        let too_few = [Point2::new(0.0, 0.0), Point2::new(1.0, 0.0)];
+        assert!(make_polygon(&too_few).is_err());
+
+        let collinear = [
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(2.0, 0.0),
+        ];
+        assert!(make_polygon(&collinear).is_err());
+
+        let bow_tie = [
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(0.0, 1.0),
+        ];
+        assert!(make_polygon(&bow_tie).is_err());
+    }
+
+    #[test]
+    fn face_with_circular_hole() {
+        let outer = make_rectangle(Point2::new(0.0, 0.0), 10.0, 10.0).unwrap();
+        let hole = make_circle(Point2::new(5.0, 5.0), 2.0).unwrap();
+        let face = face_with_holes(&outer, &[hole]).unwrap();
+        assert_eq!(face.boundaries().len(), 2);
+        assert!((normal_of(&face) - Vector3::unit_z()).magnitude() < 1e-9);
+    }
+
+    fn holes_error(outer: &TruckFace, holes: &[TruckFace]) -> String {
+        let err = face_with_holes(outer, holes).unwrap_err();
+        assert_eq!(err.kind, GeomErrorKind::InvalidInput);
+        err.message
+    }
+
+    #[test]
+    fn face_with_several_holes_and_an_existing_one() {
+        let outer = make_rectangle(Point2::new(0.0, 0.0), 20.0, 10.0).unwrap();
+        let holed =
+            face_with_holes(&outer, &[make_circle(Point2::new(5.0, 5.0), 2.0).unwrap()]).unwrap();
+        let face = face_with_holes(
+            &holed,
+            &[
+                make_circle(Point2::new(11.0, 5.0), 2.0).unwrap(),
+                make_rectangle(Point2::new(15.0, 3.0), 3.0, 4.0).unwrap(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(face.boundaries().len(), 4);
+    }
+
+    #[test]
+    fn hole_outside_outer_profile_rejected() {
+        let outer = make_rectangle(Point2::new(0.0, 0.0), 10.0, 10.0).unwrap();
+        let outside = make_circle(Point2::new(20.0, 5.0), 2.0).unwrap();
+        assert_eq!(
+            holes_error(&outer, &[outside]),
+            "hole 0 is not inside the outer profile"
+        );
+    }
+
+    #[test]
+    fn hole_crossing_outer_boundary_rejected() {
+        let outer = make_rectangle(Point2::new(0.0, 0.0), 10.0, 10.0).unwrap();
+        let inside = make_circle(Point2::new(5.0, 5.0), 1.0).unwrap();
+        let crossing = make_circle(Point2::new(9.0, 5.0), 2.0).unwrap();
+        assert_eq!(
+            holes_error(&outer, &[inside, crossing]),
+            "hole 1 is not inside the outer profile"
+        );
+    }
+
+    #[test]
+    fn overlapping_holes_rejected() {
+        let outer = make_rectangle(Point2::new(0.0, 0.0), 20.0, 10.0).unwrap();
+        let a = make_circle(Point2::new(8.0, 5.0), 2.0).unwrap();
+        let b = make_circle(Point2::new(11.0, 5.0), 2.0).unwrap();
+        assert_eq!(holes_error(&outer, &[a, b]), "hole 1 overlaps hole 0");
+    }
+
+    #[test]
+    fn hole_inside_another_hole_rejected() {
+        let outer = make_rectangle(Point2::new(0.0, 0.0), 20.0, 10.0).unwrap();
+        let big = make_circle(Point2::new(10.0, 5.0), 4.0).unwrap();
+        let small = make_circle(Point2::new(10.0, 5.0), 1.0).unwrap();
+        assert_eq!(holes_error(&outer, &[small, big]), "hole 1 overlaps hole 0");
+    }
+
+    #[test]
+    fn hole_overlapping_existing_hole_rejected() {
+        let outer = make_rectangle(Point2::new(0.0, 0.0), 20.0, 10.0).unwrap();
+        let holed =
+            face_with_holes(&outer, &[make_circle(Point2::new(5.0, 5.0), 2.0).unwrap()]).unwrap();
+        let hole = make_rectangle(Point2::new(6.0, 4.0), 4.0, 2.0).unwrap();
+        assert_eq!(
+            holes_error(&holed, &[hole]),
+            "hole 0 overlaps existing hole 0"
+        );
+    }
+
+    #[test]
+    fn hole_with_its_own_holes_rejected() {
+        let outer = make_rectangle(Point2::new(0.0, 0.0), 20.0, 10.0).unwrap();
+        let ring = face_with_holes(
+            &make_circle(Point2::new(10.0, 5.0), 4.0).unwrap(),
+            &[make_circle(Point2::new(10.0, 5.0), 2.0).unwrap()],
+        )
+        .unwrap();
+        assert_eq!(
+            holes_error(&outer, &[ring]),
+            "hole 0 has holes of its own; nested profiles are not supported"
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! STEP AP214 export of exact B-rep geometry.
+//!
+//! Faces keep their exact surfaces (planes, B-spline and NURBS surfaces,
+//! revolved curves) and edges their curves, so the receiving CAD system
+//! gets the same model rather than a mesh. Lengths are written in
+//! millimetres.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use truck_modeling::{EuclideanSpace, InnerSpace};
+use truck_stepio::out::{StepHeaderDescriptor, StepModels};
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::query::BoundingBox;
+use crate::types::TruckSolid;
+
+/// Tessellation tolerance used to tell outer shells from voids.
+const SHELL_TOLERANCE: f64 = 0.1;
+
+/// The AP214 (automotive design) schema identifier.
+const AP214_SCHEMA: &str = "AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }";
+
+/// Split a solid into its disconnected bodies: each outer shell together
+/// with the voids inside it.
+///
+/// A void is assigned to the smallest outer shell whose bounding box
+/// contains it.
+pub fn solid_bodies(solid: &TruckSolid) -> GeomResult<Vec<TruckSolid>> {
+    let mut outers = Vec::new();
+    let mut voids = Vec::new();
+    for shell in solid.boundaries() {
+        let single = TruckSolid::new_unchecked(vec![shell.clone()]);
+        let mesh = crate::tessellate::mesh_solid(&single, SHELL_TOLERANCE);
+        let positions = mesh.positions();
+        let volume: f64 = mesh
+            .face_iter()
+            .flat_map(|face| {
+                (1..face.len() - 1).map(move |i| {
+                    let [a, b, c] =
+                        [face[0], face[i], face[i + 1]].map(|v| positions[v.pos].to_vec());
+                    a.dot(b.cross(c)) / 6.0
+                })
+            })
+            .sum();
+        let bounds = BoundingBox::of_points(positions)
+            .ok_or_else(|| invalid("solid has a shell with no faces".to_string()))?;
+        if volume >= 0.0 {
+            outers.push((vec![shell.clone()], bounds, volume));
+        } else {
+            voids.push((shell.clone(), bounds));
+        }
+    }
+    for (void, bounds) in voids {
+        let contains = |outer: &BoundingBox| {
+            (0..3).all(|k| outer.min[k] <= bounds.min[k] && bounds.max[k] <= outer.max[k])
+        };
+        let (shells, _, _) = outers
+            .iter_mut()
+            .filter(|(_, outer, _)| contains(outer))
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .ok_or_else(|| invalid("solid has a void outside every body".to_string()))?;
+        shells.push(void);
+    }
+    Ok(outers
+        .into_iter()
+        .map(|(shells, _, _)| TruckSolid::new_unchecked(shells))
+        .collect())
+}
+
+/// Write named bodies as one AP214 product to a STEP file.
+///
+/// Each body must be a single connected solid (see [`solid_bodies`]); a
+/// body with several shells is written with the extra shells as voids.
+pub fn write_step(product: &str, bodies: &[(String, TruckSolid)], path: &Path) -> GeomResult<()> {
+    let text = step_string(product, bodies, path)?;
+    std::fs::write(path, text).map_err(|e| {
+        GeomError::new(
+            GeomErrorKind::IoError,
+            format!("failed to write STEP file {}: {e}", path.display()),
+        )
+    })
+}
+
+fn step_string(product: &str, bodies: &[(String, TruckSolid)], path: &Path) -> GeomResult<String> {
+    if bodies.is_empty() {
+        return Err(invalid("STEP export needs at least one body".to_string()));
+    }
+    let compressed: Vec<_> = bodies.iter().map(|(_, solid)| solid.compress()).collect();
+    if compressed.iter().any(|c| c.boundaries.is_empty()) {
+        return Err(invalid("cannot export an empty solid to STEP".to_string()));
+    }
+    let models: StepModels<_, _, _> = compressed.iter().collect();
+
+    // The header is written here rather than by truck, which declares the
+    // AP203 schema and a fixed description.
+    let product = step_text(product);
+    let file_name = path
+        .file_name()
+        .map(|name| step_text(&name.to_string_lossy()))
+        .unwrap_or_default();
+    // ISO 8601 without fractional seconds, as Part 21 expects.
+    let stamp = StepHeaderDescriptor::default().time_stamp;
+    let stamp = stamp
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .replacen(' ', "T", 1);
+    let mut out = format!(
+        "ISO-10303-21;\nHEADER;\n\
+         FILE_DESCRIPTION(('{product}'), '2;1');\n\
+         FILE_NAME('{file_name}', '{stamp}', (''), (''), 'truck', 'covariant', '');\n\
+         FILE_SCHEMA(('{AP214_SCHEMA}'));\n\
+         ENDSEC;\nDATA;\n"
+    );
+
+    // The data section has AP214 entities with every name left empty;
+    // name the product, its shape representation and the bodies. This
+    // relies on the exact output of the pinned truck-stepio version,
+    // which `truck_stepio_output_has_the_records_we_name` checks.
+    let mut data = String::new();
+    write!(data, "{models}").map_err(|_| invalid("failed to format STEP data".to_string()))?;
+    let mut names = bodies.iter().map(|(name, _)| step_text(name));
+    let (mut products, mut representations, mut solids) = (0, 0, 0);
+    for line in data.lines() {
+        let named = match entity(line) {
+            Some((id, "PRODUCT", args)) => {
+                products += 1;
+                let rest = args.strip_prefix("'','',").ok_or_else(unexpected)?;
+                format!("{id} = PRODUCT('{product}','{product}',{rest}")
+            }
+            Some((id, keyword @ "ADVANCED_BREP_SHAPE_REPRESENTATION", args)) => {
+                representations += 1;
+                let rest = args.strip_prefix("'',").ok_or_else(unexpected)?;
+                format!("{id} = {keyword}('{product}',{rest}")
+            }
+            Some((id, keyword @ ("MANIFOLD_SOLID_BREP" | "BREP_WITH_VOIDS"), args)) => {
+                solids += 1;
+                let rest = args.strip_prefix("'',").ok_or_else(unexpected)?;
+                let name = names.next().ok_or_else(unexpected)?;
+                format!("{id} = {keyword}('{name}',{rest}")
+            }
+            _ => line.to_string(),
+        };
+        out.push_str(&named);
+        out.push('\n');
+    }
+    if products != 1 || representations != 1 || solids != bodies.len() {
+        return Err(unexpected());
+    }
+    out.push_str("ENDSEC;\nEND-ISO-10303-21;\n");
+    Ok(out)
+}
+
+/// Split a one-line entity instance `#<id> = <KEYWORD>(<args>` into its
+/// id, keyword and the text after the opening parenthesis.
+fn entity(line: &str) -> Option<(&str, &str, &str)> {
+    let (id, record) = line.split_once(" = ")?;
+    if !id.starts_with('#') {
+        return None;
+    }
+    let (keyword, args) = record.split_once('(')?;
+    Some((id, keyword, args))
+}
+
+fn unexpected() -> GeomError {
+    invalid("unexpected STEP writer output".to_string())
+}
+
+/// Escape text for a STEP string literal: quotes and backslashes are
+/// doubled, and characters outside printable ASCII use `\X2\` hex.
+fn step_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\'' => out.push_str("''"),
+            '\\' => out.push_str("\\\\"),
+            ' '..='~' => out.push(c),
+            _ => {
+                out.push_str("\\X2\\");
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    let _ = write!(out, "{unit:04X}");
+                }
+                out.push_str("\\X0\\");
+            }
+        }
+    }
+    out
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean::{solid_difference, solid_union};
+    use crate::primitives::{make_box, make_cylinder, make_sphere};
+    use crate::types::DEFAULT_TOLERANCE;
+    use truck_modeling::{builder, Vector3};
+
+    fn moved(solid: &TruckSolid, v: Vector3) -> TruckSolid {
+        builder::translated(solid, v)
+    }
+
+    #[test]
+    fn writes_ap214_with_names_and_exact_surfaces() {
+        let hole = moved(&make_cylinder(3.0, 20.0), Vector3::new(10.0, 10.0, -5.0));
+        let block =
+            solid_difference(&make_box(20.0, 20.0, 10.0), &hole, DEFAULT_TOLERANCE).unwrap();
+        let bodies = vec![
+            ("block".to_string(), block),
+            (
+                "ball".to_string(),
+                moved(&make_sphere(4.0), Vector3::new(40.0, 0.0, 0.0)),
+            ),
+        ];
+        let text = step_string("Bracket Ø6", &bodies, Path::new("/tmp/part.step")).unwrap();
+        assert!(text.starts_with("ISO-10303-21;"));
+        assert!(text.contains("FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));"));
+        assert!(text.contains("FILE_DESCRIPTION(('Bracket \\X2\\00D8\\X0\\6'), '2;1');"));
+        assert!(text.contains("FILE_NAME('part.step', '"));
+        assert_eq!(text.matches("HEADER;").count(), 1);
+        assert!(text.contains("(''), (''), 'truck', 'covariant', '');"));
+        assert!(text.contains("PRODUCT('Bracket \\X2\\00D8\\X0\\6','Bracket \\X2\\00D8\\X0\\6',"));
+        assert!(text.contains("ADVANCED_BREP_SHAPE_REPRESENTATION('Bracket \\X2\\00D8\\X0\\6',"));
+        assert!(text.contains("MANIFOLD_SOLID_BREP('block',"));
+        assert!(text.contains("MANIFOLD_SOLID_BREP('ball',"));
+        assert!(text.contains("SI_UNIT(.MILLI.,.METRE.)"));
+        assert!(text.contains("PLANE("));
+        assert!(text.contains("SURFACE_OF_REVOLUTION(") || text.contains("B_SPLINE_SURFACE"));
+        assert!(text.trim_end().ends_with("END-ISO-10303-21;"));
+
+        let table = truck_stepio::r#in::Table::from_step(&text).expect("output parses as STEP");
+        assert_eq!(table.shell.len(), 2);
+    }
+
+    #[test]
+    fn splits_disjoint_shells_and_keeps_voids() {
+        let pair = solid_union(
+            &make_box(1.0, 1.0, 1.0),
+            &moved(&make_box(1.0, 1.0, 1.0), Vector3::new(3.0, 0.0, 0.0)),
+            DEFAULT_TOLERANCE,
+        )
+        .unwrap();
+        assert_eq!(pair.boundaries().len(), 2);
+        assert_eq!(solid_bodies(&pair).unwrap().len(), 2);
+
+        let hollow = crate::offset::solid_shell(&make_box(4.0, 4.0, 4.0), 1.0, &[]).unwrap();
+        let bodies = solid_bodies(&hollow).unwrap();
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0].boundaries().len(), 2);
+        let text = step_string(
+            "hollow",
+            &[("hollow".to_string(), bodies[0].clone())],
+            Path::new("h.stp"),
+        )
+        .unwrap();
+        assert!(text.contains("BREP_WITH_VOIDS('hollow',"));
+    }
+
+    /// Guards the truck-stepio pin: if an upgrade changes how the records
+    /// `step_string` names are written, this fails before exports do.
+    #[test]
+    fn truck_stepio_output_has_the_records_we_name() {
+        let cube = make_box(1.0, 1.0, 1.0).compress();
+        let hollow = crate::offset::solid_shell(&make_box(4.0, 4.0, 4.0), 1.0, &[])
+            .unwrap()
+            .compress();
+        let models: StepModels<_, _, _> = [&cube, &hollow].into_iter().collect();
+        let data = models.to_string();
+        let lines: Vec<&str> = data.lines().collect();
+        assert!(lines.contains(&"#7 = PRODUCT('','','', (#8));"), "{data}");
+        assert!(
+            lines.iter().any(|l| {
+                l.starts_with("#10 = ADVANCED_BREP_SHAPE_REPRESENTATION('', (#16, #")
+                    && l.ends_with("), #11);")
+            }),
+            "{data}"
+        );
+        assert!(
+            lines.contains(&"#16 = MANIFOLD_SOLID_BREP('', #17);"),
+            "{data}"
+        );
+        let voids: Vec<_> = lines
+            .iter()
+            .filter_map(|l| entity(l))
+            .filter(|(_, keyword, _)| *keyword == "BREP_WITH_VOIDS")
+            .collect();
+        assert_eq!(voids.len(), 1, "{data}");
+        assert!(
+            voids[0].2.starts_with("'', #") && voids[0].2.ends_with(");"),
+            "{data}"
+        );
+    }
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(step_text("a'b\\c"), "a''b\\\\c");
+        assert_eq!(step_text("Ø6"), "\\X2\\00D8\\X0\\6");
+    }
+}
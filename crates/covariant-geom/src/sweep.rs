@@ -1,29 +1,369 @@
 //! Sweep and revolve operations.
+//!
+//! Profiles are planar faces. The resulting solids are always oriented
+//! outward: truck keeps the profile's orientation for the start cap, so a
+//! profile swept against its normal is inverted before sweeping.
 
-use truck_modeling::{builder, Point3, Rad, Vector3};
+use truck_modeling::{
+    builder, BoundedCurve, Curve, EuclideanSpace, InnerSpace, Matrix4, ParameterDivision1D,
+    ParametricCurve, ParametricSurface3D, Point3, Rad, Shell, Surface, Vector3,
+};
 
-use crate::types::{TruckFace, TruckSolid};
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::{TruckFace, TruckSolid, TruckWire};
+
+/// Distance below which a profile point is considered to lie on an axis
+/// or plane.
+const AXIS_TOLERANCE: f64 = 1e-6;
+
+/// Number of samples taken along each boundary edge when checking a
+/// profile against a revolve axis.
+const EDGE_SAMPLES: usize = 16;
+
+/// Maximum deviation between a curved sweep path and the polyline of
+/// stations the profile is placed at.
+const PATH_TOLERANCE: f64 = 0.01;
+
+/// How the profile is oriented as it travels along a sweep path.
+///
+/// In both modes the profile's normal follows the path tangent; the modes
+/// differ in how the profile is allowed to roll about the tangent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SweepFrame {
+    /// The profile's X axis follows the path's principal normal (towards
+    /// the centre of curvature). Straight stretches carry the previous
+    /// frame forward. Suits helices and planar bends.
+    Frenet,
+    /// The profile's Y axis stays as close as possible to a fixed up
+    /// vector. Fails where the path runs parallel to it.
+    FixedUp(Vector3),
+}
 
 /// Translational sweep: extrude a face along a direction vector.
-pub fn solid_sweep(face: &TruckFace, direction: Vector3) -> TruckSolid {
-    builder::tsweep(face, direction)
+///
+/// The direction may point to either side of the profile but must not be
+/// parallel to its plane.
+pub fn solid_sweep(face: &TruckFace, direction: Vector3) -> GeomResult<TruckSolid> {
+    let normal = profile_normal(face)?;
+    let length = direction.magnitude();
+    if length < AXIS_TOLERANCE {
+        return Err(invalid("extrude direction must be non-zero".to_string()));
+    }
+    let along = direction.dot(normal);
+    if along.abs() < AXIS_TOLERANCE * length {
+        return Err(invalid(
+            "extrude direction is parallel to the profile plane".to_string(),
+        ));
+    }
+    let face = if along < 0.0 { face.inverse() } else { face.clone() };
+    Ok(builder::tsweep(&face, direction))
+}
+
+/// Extrude a face along its own normal by `distance`.
+///
+/// A negative distance extrudes to the back side of the profile.
+pub fn solid_extrude(face: &TruckFace, distance: f64) -> GeomResult<TruckSolid> {
+    if distance.abs() < AXIS_TOLERANCE {
+        return Err(invalid(format!("extrude distance must be non-zero, got {distance}")));
+    }
+    solid_sweep(face, profile_normal(face)? * distance)
 }
 
 /// Rotational sweep (revolve): sweep a face around an axis.
+///
+/// `angle_rad` may be negative to revolve clockwise; angles of a full turn
+/// or more produce a closed solid of revolution. The profile may touch the
+/// axis but must not cross it.
 pub fn solid_revolve(
     face: &TruckFace,
     origin: Point3,
     axis: Vector3,
     angle_rad: f64,
-) -> TruckSolid {
-    builder::rsweep(face, origin, axis, Rad(angle_rad))
+) -> GeomResult<TruckSolid> {
+    let normal = profile_normal(face)?;
+    if axis.magnitude() < AXIS_TOLERANCE {
+        return Err(invalid("revolve axis must be non-zero".to_string()));
+    }
+    if angle_rad.abs() < AXIS_TOLERANCE {
+        return Err(invalid(format!("revolve angle must be non-zero, got {angle_rad}")));
+    }
+    let axis = axis.normalize();
+    let samples = boundary_samples(face);
+    check_axis_clearance(&samples, normal, origin, axis)?;
+
+    // The farthest boundary point from the axis moves in the sweep
+    // direction; flip the profile if that is against its normal.
+    let radial = |p: &Point3| {
+        let d = p - origin;
+        d - axis * d.dot(axis)
+    };
+    let far = samples
+        .iter()
+        .flatten()
+        .max_by(|a, b| radial(a).magnitude2().total_cmp(&radial(b).magnitude2()))
+        .ok_or_else(|| invalid("profile has no boundary".to_string()))?;
+    let velocity = axis.cross(far - origin) * angle_rad.signum();
+    let face = if velocity.dot(normal) < 0.0 { face.inverse() } else { face.clone() };
+    Ok(builder::rsweep(&face, origin, axis, Rad(angle_rad)))
+}
+
+/// Sweep a face along a path wire.
+///
+/// The profile is taken in its own sketch coordinates: its origin follows
+/// the path, its normal follows the path tangent and its X and Y axes are
+/// carried by `frame`. The lateral surface is ruled between profile copies
+/// placed at most [`PATH_TOLERANCE`] away from curved stretches of the
+/// path. Closed paths give a closed ring when the frame returns to its
+/// starting orientation.
+pub fn solid_sweep_along(
+    face: &TruckFace,
+    path: &TruckWire,
+    frame: SweepFrame,
+) -> GeomResult<TruckSolid> {
+    let normal = profile_normal(face)?;
+    if normal.z.abs() < 1.0 - AXIS_TOLERANCE {
+        return Err(invalid(
+            "sweep profile must lie in a plane parallel to XY".to_string(),
+        ));
+    }
+    let face = if normal.z < 0.0 { face.inverse() } else { face.clone() };
+
+    let stations = path_stations(path)?;
+    let mut frames = station_frames(&stations, frame)?;
+    let closed = path.is_closed();
+    if closed {
+        let (first, last) = (frames[0], frames[frames.len() - 1]);
+        if (first.x - last.x).magnitude() > AXIS_TOLERANCE {
+            return Err(invalid(
+                "sweep frame does not return to its start on this closed path; \
+                 use a fixed up vector"
+                    .to_string(),
+            ));
+        }
+        frames.pop();
+    }
+
+    let placed: Vec<TruckFace> = frames
+        .iter()
+        .map(|m| builder::transformed(&face, *m))
+        .collect();
+    let mut rings: Vec<Vec<TruckWire>> = placed.iter().map(|f| f.boundaries()).collect();
+    if closed {
+        rings.push(rings[0].clone());
+    }
+
+    let mut shell = Shell::new();
+    for pair in rings.windows(2) {
+        for (w0, w1) in pair[0].iter().zip(&pair[1]) {
+            let strip = builder::try_wire_homotopy(w0, w1)
+                .map_err(|e| invalid(format!("failed to build sweep surface: {e}")))?;
+            shell.extend(strip);
+        }
+    }
+    if !closed {
+        shell.push(placed[0].inverse());
+        shell.push(placed[placed.len() - 1].clone());
+    }
+    TruckSolid::try_new(vec![shell])
+        .map_err(|e| invalid(format!("sweep did not produce a closed solid: {e}")))
+}
+
+/// A point on a sweep path with its first and second derivatives.
+struct Station {
+    point: Point3,
+    tangent: Vector3,
+    curvature: Vector3,
+}
+
+/// Sample the path at the parameters the profile will be placed at.
+///
+/// Straight edges contribute their end points; curved edges are divided
+/// until the polyline is within [`PATH_TOLERANCE`]. Shared end points
+/// between consecutive edges are kept once.
+fn path_stations(path: &TruckWire) -> GeomResult<Vec<Station>> {
+    let mut stations: Vec<Station> = Vec::new();
+    for edge in path.edge_iter() {
+        let curve = edge.oriented_curve();
+        let range = curve.range_tuple();
+        let params = match curve {
+            Curve::Line(_) => vec![range.0, range.1],
+            _ => curve.parameter_division(range, PATH_TOLERANCE).0,
+        };
+        let skip = usize::from(!stations.is_empty());
+        for &t in &params[skip..] {
+            let tangent = curve.der(t);
+            if tangent.magnitude() < AXIS_TOLERANCE {
+                return Err(invalid("sweep path has a degenerate tangent".to_string()));
+            }
+            stations.push(Station {
+                point: curve.subs(t),
+                tangent,
+                curvature: curve.der2(t),
+            });
+        }
+    }
+    if stations.len() < 2 {
+        return Err(invalid("sweep path is empty".to_string()));
+    }
+    Ok(stations)
+}
+
+/// Local-to-world placement of the profile at every station.
+fn station_frames(stations: &[Station], frame: SweepFrame) -> GeomResult<Vec<Matrix4>> {
+    let mut previous: Option<Vector3> = None;
+    stations
+        .iter()
+        .map(|station| {
+            let t = station.tangent.normalize();
+            let x = match frame {
+                SweepFrame::Frenet => {
+                    // Principal normal, independent of the path's speed.
+                    let k = station.curvature - t * station.curvature.dot(t);
+                    let x = if k.magnitude() > AXIS_TOLERANCE * station.tangent.magnitude2() {
+                        k
+                    } else {
+                        let carried = previous.unwrap_or_else(|| crate::curve::perpendicular(t));
+                        carried - t * carried.dot(t)
+                    };
+                    x.normalize()
+                }
+                SweepFrame::FixedUp(up) => {
+                    let y = up - t * up.dot(t);
+                    if y.magnitude() < AXIS_TOLERANCE * up.magnitude().max(1.0) {
+                        return Err(invalid(
+                            "sweep path runs parallel to the up vector".to_string(),
+                        ));
+                    }
+                    y.normalize().cross(t)
+                }
+            };
+            previous = Some(x);
+            let y = t.cross(x);
+            Ok(Matrix4::from_cols(
+                x.extend(0.0),
+                y.extend(0.0),
+                t.extend(0.0),
+                station.point.to_vec().extend(1.0),
+            ))
+        })
+        .collect()
+}
+
+/// Unit normal of a planar profile face.
+pub(crate) fn profile_normal(face: &TruckFace) -> GeomResult<Vector3> {
+    match face.surface() {
+        Surface::Plane(_) => Ok(face.oriented_surface().normal(0.0, 0.0).normalize()),
+        _ => Err(invalid("profile must be a planar face".to_string())),
+    }
+}
+
+/// Sample points along every boundary wire of a face.
+fn boundary_samples(face: &TruckFace) -> Vec<Vec<Point3>> {
+    face.boundaries()
+        .iter()
+        .map(|wire| {
+            wire.edge_iter()
+                .flat_map(|edge| {
+                    let curve = edge.oriented_curve();
+                    let (t0, t1) = curve.range_tuple();
+                    (0..EDGE_SAMPLES).map(move |i| {
+                        curve.subs(t0 + (t1 - t0) * i as f64 / EDGE_SAMPLES as f64)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Reject revolve axes that pass through the interior of a profile.
+///
+/// An axis lying in the profile plane must keep the whole profile on one
+/// side; an axis piercing the plane must do so outside the profile.
+fn check_axis_clearance(
+    samples: &[Vec<Point3>],
+    normal: Vector3,
+    origin: Point3,
+    axis: Vector3,
+) -> GeomResult<()> {
+    let Some(&on_plane) = samples.first().and_then(|w| w.first()) else {
+        return Ok(());
+    };
+    let along = axis.dot(normal);
+    if along.abs() < AXIS_TOLERANCE {
+        if (origin - on_plane).dot(normal).abs() > AXIS_TOLERANCE {
+            return Ok(());
+        }
+        let side = normal.cross(axis);
+        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for p in samples.iter().flatten() {
+            let d = (p - origin).dot(side);
+            min = min.min(d);
+            max = max.max(d);
+        }
+        if min < -AXIS_TOLERANCE && max > AXIS_TOLERANCE {
+            return Err(invalid("profile crosses the revolve axis".to_string()));
+        }
+        return Ok(());
+    }
+
+    let pierce = origin + axis * ((on_plane - origin).dot(normal) / along);
+    if point_in_loops(samples, normal, pierce) {
+        return Err(invalid(
+            "revolve axis passes through the profile".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Even-odd test of a point against sampled boundary loops in their plane.
+fn point_in_loops(samples: &[Vec<Point3>], normal: Vector3, point: Point3) -> bool {
+    let u = if normal.x.abs() < 0.9 { Vector3::unit_x() } else { Vector3::unit_y() };
+    let u = (u - normal * u.dot(normal)).normalize();
+    let v = normal.cross(u);
+    let to_2d = |p: &Point3| ((p - point).dot(u), (p - point).dot(v));
+
+    let mut inside = false;
+    for wire in samples {
+        for (i, a) in wire.iter().enumerate() {
+            let (ax, ay) = to_2d(a);
+            let (bx, by) = to_2d(&wire[(i + 1) % wire.len()]);
+            if (ay > 0.0) != (by > 0.0) && ax + (bx - ax) * (-ay) / (by - ay) > 0.0 {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::f64::consts::PI;
-    use truck_modeling::{builder, EuclideanSpace, Wire};
+    use truck_modeling::{builder, EuclideanSpace, Point2, Wire};
+
+    /// Signed volume of a tessellated solid (negative if inside out).
+    fn mesh_volume(solid: &TruckSolid) -> f64 {
+        let mesh = crate::tessellate::mesh_solid(solid, 0.01);
+        let pos = mesh.positions();
+        mesh.face_iter()
+            .map(|f| {
+                let a = pos[f[0].pos].to_vec();
+                (1..f.len() - 1)
+                    .map(|i| a.dot(pos[f[i].pos].to_vec().cross(pos[f[i + 1].pos].to_vec())))
+                    .sum::<f64>()
+                    / 6.0
+            })
+            .sum()
+    }
+
+    /// A 2 x 1 rectangle in the XY plane, offset 1 unit from the Y axis.
+    fn offset_rectangle() -> TruckFace {
+        crate::sketch::make_rectangle(Point2::new(1.0, 0.0), 2.0, 1.0).unwrap()
+    }
 
     /// Helper: create a square face in the XY plane.
     fn square_face(size: f64) -> TruckFace {
@@ -43,7 +383,7 @@ mod tests {
     #[test]
     fn sweep_face_into_solid() {
         let face = square_face(5.0);
-        let solid = solid_sweep(&face, Vector3::new(0.0, 0.0, 10.0));
+        let solid = solid_sweep(&face, Vector3::new(0.0, 0.0, 10.0)).unwrap();
         assert!(!solid.boundaries().is_empty(), "sweep should produce a solid");
     }
 
@@ -62,10 +402,120 @@ mod tests {
         ]);
         let face =
             builder::try_attach_plane(&[wire]).expect("rectangular wire should form a plane");
-        let solid = solid_revolve(&face, Point3::origin(), Vector3::unit_z(), 2.0 * PI);
+        let solid = solid_revolve(&face, Point3::origin(), Vector3::unit_z(), 2.0 * PI).unwrap();
         assert!(
             !solid.boundaries().is_empty(),
             "revolve should produce a solid"
         );
     }
+
+    #[test]
+    fn sweep_against_normal_is_outward() {
+        let face = square_face(2.0);
+        let up = solid_sweep(&face, Vector3::new(0.0, 0.0, 3.0)).unwrap();
+        let down = solid_sweep(&face, Vector3::new(0.0, 0.0, -3.0)).unwrap();
+        assert!((mesh_volume(&up) - 12.0).abs() < 1e-6);
+        assert!((mesh_volume(&down) - 12.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn extrude_along_normal() {
+        let solid = solid_extrude(&square_face(2.0), -1.5).unwrap();
+        assert!((mesh_volume(&solid) - 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sweep_rejects_degenerate_directions() {
+        let face = square_face(2.0);
+        assert!(solid_sweep(&face, Vector3::new(0.0, 0.0, 0.0)).is_err());
+        let err = solid_sweep(&face, Vector3::new(1.0, 0.0, 0.0)).unwrap_err();
+        assert!(err.message.contains("parallel"), "{}", err.message);
+        assert!(solid_extrude(&face, 0.0).is_err());
+    }
+
+    #[test]
+    fn partial_revolve_in_either_direction_is_outward() {
+        // Quarter turn of the 2 x 1 rectangle: (π/4)(3² − 1²) · 1 = 2π.
+        let face = offset_rectangle();
+        for angle in [PI / 2.0, -PI / 2.0] {
+            let solid = solid_revolve(&face, Point3::origin(), Vector3::unit_y(), angle).unwrap();
+            let v = mesh_volume(&solid);
+            assert!((v - 2.0 * PI).abs() < 0.05, "angle {angle}: volume {v}");
+        }
+    }
+
+    #[test]
+    fn revolve_profile_touching_axis() {
+        let face = crate::sketch::make_rectangle(Point2::new(0.0, 0.0), 2.0, 1.0).unwrap();
+        let solid = solid_revolve(&face, Point3::origin(), Vector3::unit_y(), 2.0 * PI).unwrap();
+        assert!((mesh_volume(&solid) - 4.0 * PI).abs() < 0.1);
+    }
+
+    #[test]
+    fn revolve_rejects_profile_crossing_axis() {
+        let face = crate::sketch::make_rectangle(Point2::new(-1.0, 0.0), 2.0, 1.0).unwrap();
+        let err = solid_revolve(&face, Point3::origin(), Vector3::unit_y(), PI).unwrap_err();
+        assert_eq!(err.kind, GeomErrorKind::InvalidInput);
+        assert!(err.message.contains("crosses the revolve axis"), "{}", err.message);
+    }
+
+    #[test]
+    fn revolve_rejects_axis_through_profile() {
+        let face = offset_rectangle();
+        let err = solid_revolve(&face, Point3::new(2.0, 0.5, 0.0), Vector3::unit_z(), PI)
+            .unwrap_err();
+        assert!(err.message.contains("passes through"), "{}", err.message);
+        // Piercing the plane outside the profile is fine.
+        assert!(solid_revolve(&face, Point3::origin(), Vector3::unit_z(), PI).is_ok());
+    }
+
+    #[test]
+    fn revolve_rejects_zero_axis_and_angle() {
+        let face = offset_rectangle();
+        assert!(solid_revolve(&face, Point3::origin(), Vector3::new(0.0, 0.0, 0.0), PI).is_err());
+        assert!(solid_revolve(&face, Point3::origin(), Vector3::unit_y(), 0.0).is_err());
+    }
+
+    #[test]
+    fn sweep_along_straight_path_matches_extrusion() {
+        let face = crate::sketch::make_rectangle(Point2::new(-1.0, -1.0), 2.0, 2.0).unwrap();
+        let path = crate::curve::line_curve(Point3::origin(), Point3::new(0.0, 5.0, 0.0)).unwrap();
+        for frame in [SweepFrame::Frenet, SweepFrame::FixedUp(Vector3::unit_z())] {
+            let solid = solid_sweep_along(&face, &path, frame).unwrap();
+            assert!((mesh_volume(&solid) - 20.0).abs() < 1e-6, "{frame:?}");
+        }
+    }
+
+    #[test]
+    fn sweep_circle_along_helix() {
+        // Spring: wire radius 0.5 on a 5 mm helix, 2 mm pitch, 2 turns.
+        let face = crate::sketch::make_circle(Point2::new(0.0, 0.0), 0.5).unwrap();
+        let path = crate::curve::helix_curve(5.0, 2.0, 4.0, false).unwrap();
+        let solid = solid_sweep_along(&face, &path, SweepFrame::Frenet).unwrap();
+        let length = 2.0 * (4.0 + (2.0 * PI * 5.0).powi(2)).sqrt();
+        let expected = PI * 0.25 * length;
+        let v = mesh_volume(&solid);
+        assert!((v - expected).abs() / expected < 0.02, "volume {v}, expected {expected}");
+    }
+
+    #[test]
+    fn sweep_along_closed_path_gives_ring() {
+        // Torus: 2π² R r².
+        let face = crate::sketch::make_circle(Point2::new(0.0, 0.0), 1.0).unwrap();
+        let path = crate::curve::circle_curve(Point3::origin(), 4.0, Vector3::unit_z()).unwrap();
+        let solid = solid_sweep_along(&face, &path, SweepFrame::Frenet).unwrap();
+        assert_eq!(solid.boundaries().len(), 1);
+        let expected = 2.0 * PI * PI * 4.0;
+        let v = mesh_volume(&solid);
+        assert!((v - expected).abs() / expected < 0.02, "volume {v}, expected {expected}");
+    }
+
+    #[test]
+    fn sweep_fixed_up_rejects_parallel_path() {
+        let face = crate::sketch::make_circle(Point2::new(0.0, 0.0), 1.0).unwrap();
+        let path = crate::curve::line_curve(Point3::origin(), Point3::new(0.0, 0.0, 5.0)).unwrap();
+        let err = solid_sweep_along(&face, &path, SweepFrame::FixedUp(Vector3::unit_z()))
+            .unwrap_err();
+        assert!(err.message.contains("parallel to the up vector"), "{}", err.message);
+    }
 }
@@ -0,0 +1,260 @@
+//! Helical thread solids.
+//!
+//! A thread is the ISO 68-1 / UTS basic profile — a flat crest of `P/8` on
+//! the major diameter, a flat root of `P/4` on the minor diameter and 60°
+//! flanks — swept along a right- or left-hand helix. It is built as a
+//! polyhedron: the profile corners are sampled at evenly spaced angles per
+//! turn, crests and roots become planar strips, each flank strip is split
+//! into two triangles, and both ends are capped flat.
+//!
+//! Internal and external threads share the basic profile, so one solid
+//! serves both: on its own it is the external thread, and subtracted from a
+//! part it cuts the matching internal thread (including the hole at the
+//! minor diameter).
+
+use std::f64::consts::{PI, TAU};
+
+use covariant_thread::HelixParams;
+use truck_modeling::Point3;
+
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::polyhedron::solid_from_triangles;
+use crate::types::TruckSolid;
+
+/// Largest gap between the sampled crests and the major cylinder.
+pub const THREAD_TOLERANCE: f64 = 0.02;
+
+/// Fewest angular samples per turn; also keeps a sample step well below
+/// the narrowest part of the profile.
+const MIN_SEGMENTS: usize = 16;
+const MAX_SEGMENTS: usize = 180;
+
+/// Axial positions of the profile corners within one turn, as fractions
+/// of the pitch: crest start, crest end, root start and root end.
+const CORNERS: [f64; 4] = [-1.0 / 16.0, 1.0 / 16.0, 3.0 / 8.0, 5.0 / 8.0];
+
+/// Offset of the sampled angles, as a fraction of one step. An irrational
+/// fraction keeps the profile corners off round heights, where they would
+/// land exactly on the faces of a part the thread is cut from — a case the
+/// boolean operations cannot handle.
+const PHASE: f64 = 0.618_033_988_749_895;
+
+/// Threaded rod along +Z from `z = 0` to `z = helix.depth`.
+pub fn solid_thread(helix: &HelixParams) -> GeomResult<TruckSolid> {
+    let HelixParams {
+        major_diameter,
+        minor_diameter,
+        pitch,
+        depth,
+        left_hand,
+    } = *helix;
+    if !(pitch > 0.0 && depth > 0.0 && minor_diameter > 0.0 && minor_diameter < major_diameter) {
+        return Err(invalid(format!(
+            "thread needs 0 < minor diameter < major diameter and a positive pitch and length \
+             (got major {major_diameter}, minor {minor_diameter}, pitch {pitch}, length {depth})"
+        )));
+    }
+    let (major, minor) = (major_diameter / 2.0, minor_diameter / 2.0);
+    let segments = ((PI / (1.0 - THREAD_TOLERANCE / major).acos()).ceil() as usize)
+        .clamp(MIN_SEGMENTS, MAX_SEGMENTS);
+
+    // Radius of the profile at axial offset `u` along a generator line.
+    let radius = |u: f64| {
+        let w = (u / pitch + 1.0 / 16.0).rem_euclid(1.0);
+        let flank = 5.0 / 16.0;
+        if w < 1.0 / 8.0 {
+            major
+        } else if w < 7.0 / 16.0 {
+            major - (major - minor) * (w - 1.0 / 8.0) / flank
+        } else if w < 11.0 / 16.0 {
+            minor
+        } else {
+            minor + (major - minor) * (w - 11.0 / 16.0) / flank
+        }
+    };
+
+    // Each generator line holds its bottom point, the profile corners
+    // between the ends (keyed by corner number, counted up the helix) and
+    // its top point.
+    let eps = 1e-6 * pitch;
+    let mut points = Vec::new();
+    let mut lines: Vec<Vec<(i64, usize)>> = Vec::with_capacity(segments + 1);
+    for k in 0..segments {
+        let step = (k as f64 + PHASE) / segments as f64;
+        let (theta, shift) = (TAU * step, pitch * step);
+        let (cos, sin) = (theta.cos(), theta.sin());
+        let mut at = |r: f64, z: f64| {
+            points.push(Point3::new(r * cos, r * sin, z));
+            points.len() - 1
+        };
+        let mut line = vec![(i64::MIN, at(radius(-shift), 0.0))];
+        let mut corner = 4 * ((-shift / pitch).floor() as i64 - 1);
+        loop {
+            let (turn, j) = (corner.div_euclid(4), corner.rem_euclid(4) as usize);
+            let z = (CORNERS[j] + turn as f64) * pitch + shift;
+            if z >= depth - eps {
+                break;
+            }
+            if z > eps {
+                line.push((corner, at(if j < 2 { major } else { minor }, z)));
+            }
+            corner += 1;
+        }
+        line.push((i64::MAX, at(radius(depth - shift), depth)));
+        lines.push(line);
+    }
+    // After a full turn the helix has risen one pitch: corner `c` there is
+    // corner `c + 4` of the first line.
+    let wrapped = lines[0]
+        .iter()
+        .map(|&(key, i)| match key {
+            i64::MIN | i64::MAX => (key, i),
+            _ => (key - 4, i),
+        })
+        .collect();
+    lines.push(wrapped);
+
+    // Zip neighbouring lines together, pairing corners with the same key;
+    // corners entering or leaving through the ends get a single triangle.
+    let mut triangles = Vec::new();
+    for pair in lines.windows(2) {
+        let (left, right) = (&pair[0], &pair[1]);
+        let (mut i, mut j) = (0, 0);
+        while i + 1 < left.len() || j + 1 < right.len() {
+            let advance_left =
+                j + 1 == right.len() || (i + 1 < left.len() && left[i + 1].0 <= right[j + 1].0);
+            if advance_left {
+                triangles.push([left[i].1, right[j].1, left[i + 1].1]);
+                i += 1;
+            } else {
+                triangles.push([left[i].1, right[j].1, right[j + 1].1]);
+                j += 1;
+            }
+        }
+    }
+
+    let bottom = points.len();
+    points.push(Point3::new(0.0, 0.0, 0.0));
+    let top = points.len();
+    points.push(Point3::new(0.0, 0.0, depth));
+    for pair in lines.windows(2) {
+        let (left, right) = (&pair[0], &pair[1]);
+        triangles.push([bottom, right[0].1, left[0].1]);
+        triangles.push([top, left[left.len() - 1].1, right[right.len() - 1].1]);
+    }
+
+    if left_hand {
+        // The mirror image in the XZ plane turns the other way; its faces
+        // must be flipped to keep facing outwards.
+        for p in &mut points {
+            p.y = -p.y;
+        }
+        for triangle in &mut triangles {
+            triangle.swap(1, 2);
+        }
+    }
+
+    solid_from_triangles(&points, &triangles)
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean::solid_difference;
+    use crate::mass::mass_properties;
+    use crate::primitives::make_box;
+    use crate::query::contains_point;
+    use crate::transform::solid_translate;
+    use crate::DEFAULT_TOLERANCE;
+    use truck_modeling::Vector3;
+
+    /// M6 × 1 basic profile.
+    fn m6(depth: f64) -> HelixParams {
+        HelixParams {
+            major_diameter: 6.0,
+            minor_diameter: 4.917,
+            pitch: 1.0,
+            depth,
+            left_hand: false,
+        }
+    }
+
+    #[test]
+    fn volume_lies_between_minor_and_major_cylinders() {
+        let solid = solid_thread(&m6(5.0)).unwrap();
+        let volume = mass_properties(&solid, 1.0, 0.01).unwrap().volume;
+        // Mean squared radius over one pitch: crest, root and two flanks
+        // along which the radius changes linearly.
+        let (r, rr) = (4.917_f64 / 2.0, 3.0_f64);
+        let flank = |a: f64, b: f64| (a * a + a * b + b * b) / 3.0;
+        let mean = (rr * rr / 8.0 + r * r / 4.0 + 2.0 * 5.0 / 16.0 * flank(r, rr)) * PI;
+        let expected = mean * 5.0;
+        assert!(
+            (volume - expected).abs() / expected < 0.01,
+            "volume {volume}, expected {expected}"
+        );
+        assert!(volume > PI * r * r * 5.0 && volume < PI * rr * rr * 5.0);
+    }
+
+    #[test]
+    fn thread_is_right_handed() {
+        let solid = solid_thread(&m6(3.0)).unwrap();
+        let probe = |theta: f64, z: f64| {
+            let p = Point3::new(2.9 * theta.cos(), 2.9 * theta.sin(), z);
+            contains_point(&solid, p).unwrap()
+        };
+        // The crest at z = 1 on +X rises a quarter pitch a quarter turn
+        // counter-clockwise.
+        assert!(probe(0.0, 1.0));
+        assert!(!probe(0.0, 1.5));
+        assert!(probe(PI / 2.0, 1.25));
+        assert!(!probe(PI / 2.0, 1.0));
+    }
+
+    #[test]
+    fn left_hand_thread_turns_the_other_way() {
+        let solid = solid_thread(&HelixParams {
+            left_hand: true,
+            ..m6(3.0)
+        })
+        .unwrap();
+        let probe = |theta: f64, z: f64| {
+            let p = Point3::new(2.9 * theta.cos(), 2.9 * theta.sin(), z);
+            contains_point(&solid, p).unwrap()
+        };
+        assert!(probe(0.0, 1.0));
+        assert!(probe(-PI / 2.0, 1.25));
+        assert!(!probe(PI / 2.0, 1.25));
+        let volume = mass_properties(&solid, 1.0, 0.01).unwrap().volume;
+        assert!(volume > 0.0, "{volume}");
+    }
+
+    #[test]
+    fn cuts_an_internal_thread() {
+        // The thread runs through the block so no faces are coplanar.
+        let block = solid_translate(&make_box(10.0, 10.0, 4.0), Vector3::new(-5.0, -5.0, 0.0));
+        let thread = solid_translate(
+            &solid_thread(&m6(6.0)).unwrap(),
+            Vector3::new(0.0, 0.0, -1.0),
+        );
+        let nut = solid_difference(&block, &thread, DEFAULT_TOLERANCE)
+            .expect("thread should cut the block");
+        let volume = mass_properties(&nut, 1.0, 0.01).unwrap().volume;
+        let cut = mass_properties(&solid_thread(&m6(4.0)).unwrap(), 1.0, 0.01)
+            .unwrap()
+            .volume;
+        assert!((volume - (400.0 - cut)).abs() < 0.5, "{volume}");
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let mut helix = m6(5.0);
+        helix.minor_diameter = 7.0;
+        assert!(solid_thread(&helix).is_err());
+        assert!(solid_thread(&m6(0.0)).is_err());
+    }
+}
@@ -1,8 +1,11 @@
 //! Transformation operations on solids.
 
-use truck_modeling::{builder, Matrix4, Point3, Rad, Vector3};
+use truck_modeling::{
+    builder, EuclideanSpace, InnerSpace, Matrix3, Matrix4, Point3, Rad, SquareMatrix, Vector3,
+};
 
-use crate::types::TruckSolid;
+use crate::error::{GeomError, GeomErrorKind, GeomResult};
+use crate::types::{TruckFace, TruckSolid};
 
 /// Translate a solid by a vector.
 pub fn solid_translate(solid: &TruckSolid, v: Vector3) -> TruckSolid {
@@ -19,48 +22,94 @@ pub fn solid_rotate(
     builder::rotated(solid, origin, axis, Rad(angle_rad))
 }
 
-/// Uniformly scale a solid about `center`.
-pub fn solid_scale(solid: &TruckSolid, center: Point3, factor: f64) -> TruckSolid {
-    builder::scaled(solid, center, Vector3::new(factor, factor, factor))
+/// Translate a planar profile by a vector.
+pub fn face_translate(face: &TruckFace, v: Vector3) -> TruckFace {
+    builder::translated(face, v)
+}
+
+/// Rotate a planar profile around an axis through `origin`.
+pub fn face_rotate(face: &TruckFace, origin: Point3, axis: Vector3, angle_rad: f64) -> TruckFace {
+    builder::rotated(face, origin, axis, Rad(angle_rad))
+}
+
+/// Scale a solid about `center` by per-axis `factors`.
+///
+/// Negative factors mirror the solid; a zero factor is rejected.
+pub fn solid_scale(solid: &TruckSolid, center: Point3, factors: Vector3) -> GeomResult<TruckSolid> {
+    let offset = center.to_vec();
+    let mat = Matrix4::from_translation(offset)
+        * Matrix4::from_nonuniform_scale(factors.x, factors.y, factors.z)
+        * Matrix4::from_translation(-offset);
+    solid_transform(solid, mat)
+}
+
+/// Apply a 4×4 affine matrix (column vectors, translation in the last
+/// column) to a solid.
+///
+/// Fails unless the bottom row is `[0, 0, 0, 1]` and the linear part is
+/// invertible. Orientation-reversing matrices keep the boundary facing
+/// outward.
+pub fn solid_transform(solid: &TruckSolid, mat: Matrix4) -> GeomResult<TruckSolid> {
+    if [mat.x.w, mat.y.w, mat.z.w, mat.w.w] != [0.0, 0.0, 0.0, 1.0] {
+        return Err(invalid(
+            "transform matrix must be affine (bottom row 0, 0, 0, 1)".to_string(),
+        ));
+    }
+    let det = linear_part(mat).determinant();
+    let scale = [mat.x, mat.y, mat.z]
+        .iter()
+        .map(|c| c.truncate().magnitude())
+        .fold(0.0, f64::max);
+    if !det.is_finite() || det.abs() <= 1e-9 * scale.powi(3) {
+        return Err(invalid(format!(
+            "transform matrix is singular (determinant {det})"
+        )));
+    }
+    Ok(transformed(solid, mat))
 }
 
 /// Mirror a solid across a plane defined by `origin` and `normal`.
 ///
-/// Uses a reflection matrix: I - 2*n*nᵀ (Householder reflection),
-/// then translates to account for the plane offset.
+/// Uses a Householder reflection `I - 2 n nᵀ` about a plane through the
+/// world origin, conjugated by translations so the plane passes through
+/// `origin`. A reflection reverses handedness, so every face is inverted
+/// afterwards to keep the boundary facing outward.
 pub fn solid_mirror(solid: &TruckSolid, origin: Point3, normal: Vector3) -> TruckSolid {
-    use truck_modeling::InnerSpace;
     let n = normal.normalize();
-    // Householder reflection matrix: R = I - 2*n*nᵀ
-    #[rustfmt::skip]
-    let reflection = Matrix4::new(
-        1.0 - 2.0 * n.x * n.x, -2.0 * n.x * n.y,       -2.0 * n.x * n.z,       0.0,
-        -2.0 * n.y * n.x,       1.0 - 2.0 * n.y * n.y,  -2.0 * n.y * n.z,       0.0,
-        -2.0 * n.z * n.x,       -2.0 * n.z * n.y,        1.0 - 2.0 * n.z * n.z, 0.0,
-        0.0,                     0.0,                      0.0,                    1.0,
-    );
-    // Translate so that origin maps to itself under reflection:
-    // T = 2 * dot(origin - O, n) * n  where O is world origin
-    let d = origin.x * n.x + origin.y * n.y + origin.z * n.z;
-    let tx = 2.0 * d * n.x;
-    let ty = 2.0 * d * n.y;
-    let tz = 2.0 * d * n.z;
-    #[rustfmt::skip]
-    let translate = Matrix4::new(
-        1.0, 0.0, 0.0, tx,
-        0.0, 1.0, 0.0, ty,
-        0.0, 0.0, 1.0, tz,
-        0.0, 0.0, 0.0, 1.0,
-    );
-    let mat = translate * reflection;
-    builder::transformed(solid, mat)
+    let reflection = Matrix4::from(Matrix3::identity() - outer(n, n) * 2.0);
+    let offset = origin.to_vec();
+    let mat = Matrix4::from_translation(offset) * reflection * Matrix4::from_translation(-offset);
+    transformed(solid, mat)
+}
+
+/// Apply an invertible affine matrix, inverting the faces when it
+/// reverses handedness so the boundary keeps facing outward.
+fn transformed(solid: &TruckSolid, mat: Matrix4) -> TruckSolid {
+    let mut result = builder::transformed(solid, mat);
+    if linear_part(mat).determinant() < 0.0 {
+        result.not();
+    }
+    result
+}
+
+fn linear_part(mat: Matrix4) -> Matrix3 {
+    Matrix3::from_cols(mat.x.truncate(), mat.y.truncate(), mat.z.truncate())
+}
+
+/// `u vᵀ` as a matrix.
+fn outer(u: Vector3, v: Vector3) -> Matrix3 {
+    Matrix3::from_cols(u * v.x, u * v.y, u * v.z)
+}
+
+fn invalid(message: String) -> GeomError {
+    GeomError::new(GeomErrorKind::InvalidInput, message)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitives::make_box;
-    use std::f64::consts::FRAC_PI_2;
+    use crate::primitives::{make_box, make_cylinder};
+    use std::f64::consts::{FRAC_PI_2, PI};
     use truck_modeling::EuclideanSpace;
 
     #[test]
@@ -84,14 +133,89 @@ mod tests {
     #[test]
     fn scale_changes_size() {
         let solid = make_box(1.0, 1.0, 1.0);
-        let scaled = solid_scale(&solid, Point3::origin(), 2.0);
+        let scaled = solid_scale(&solid, Point3::origin(), Vector3::new(2.0, 2.0, 2.0)).unwrap();
         assert!(!scaled.boundaries().is_empty());
     }
 
+    #[test]
+    fn per_axis_scale_about_center() {
+        let solid = make_box(2.0, 2.0, 2.0);
+        let scaled = solid_scale(&solid, Point3::new(1.0, 1.0, 1.0), Vector3::new(2.0, 1.0, 0.5))
+            .unwrap();
+        let bounds = crate::query::bounding_box(&scaled).unwrap();
+        assert!((bounds.min - Point3::new(-1.0, 0.0, 0.5)).magnitude() < 1e-9);
+        assert!((bounds.max - Point3::new(3.0, 2.0, 1.5)).magnitude() < 1e-9);
+    }
+
+    #[test]
+    fn negative_scale_keeps_outward_normals() {
+        let solid = make_box(1.0, 2.0, 3.0);
+        let flipped = solid_scale(&solid, Point3::origin(), Vector3::new(-1.0, 1.0, 2.0)).unwrap();
+        let props = crate::mass::mass_properties(&flipped, 1.0, 0.01).unwrap();
+        assert!((props.volume - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn transform_applies_affine_matrix() {
+        // Shear x by z, then translate.
+        #[rustfmt::skip]
+        let mat = Matrix4::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.5, 0.0, 1.0, 0.0,
+            3.0, 0.0, 0.0, 1.0,
+        );
+        let sheared = solid_transform(&make_box(1.0, 1.0, 2.0), mat).unwrap();
+        let bounds = crate::query::bounding_box(&sheared).unwrap();
+        assert!((bounds.min - Point3::new(3.0, 0.0, 0.0)).magnitude() < 1e-9);
+        assert!((bounds.max - Point3::new(5.0, 1.0, 2.0)).magnitude() < 1e-9);
+        // Shear preserves volume.
+        let props = crate::mass::mass_properties(&sheared, 1.0, 0.01).unwrap();
+        assert!((props.volume - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn transform_rejects_singular_and_projective_matrices() {
+        let solid = make_box(1.0, 1.0, 1.0);
+        let flat = Matrix4::from_nonuniform_scale(1.0, 1.0, 0.0);
+        let err = solid_transform(&solid, flat).unwrap_err();
+        assert!(err.message.contains("singular"), "{}", err.message);
+        assert!(solid_scale(&solid, Point3::origin(), Vector3::new(1.0, 0.0, 1.0)).is_err());
+
+        let mut projective = Matrix4::identity();
+        projective.x.w = 0.1;
+        let err = solid_transform(&solid, projective).unwrap_err();
+        assert!(err.message.contains("affine"), "{}", err.message);
+    }
+
     #[test]
     fn mirror_produces_valid_solid() {
         let solid = make_box(1.0, 1.0, 1.0);
         let mirrored = solid_mirror(&solid, Point3::origin(), Vector3::unit_x());
         assert!(!mirrored.boundaries().is_empty());
     }
+
+    #[test]
+    fn mirror_across_offset_plane_keeps_outward_normals() {
+        let solid = make_box(1.0, 2.0, 3.0);
+        let mirrored = solid_mirror(&solid, Point3::new(5.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0));
+        let bounds = crate::query::bounding_box(&mirrored).unwrap();
+        assert!((bounds.min - Point3::new(9.0, 0.0, 0.0)).magnitude() < 1e-9);
+        assert!((bounds.max - Point3::new(10.0, 2.0, 3.0)).magnitude() < 1e-9);
+        // Inside-out solids would measure a negative volume and be rejected.
+        let props = crate::mass::mass_properties(&mirrored, 1.0, 0.01).unwrap();
+        assert!((props.volume - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mirrored_tool_cuts_like_the_original() {
+        let drill = solid_translate(&make_cylinder(0.5, 6.0), Vector3::new(1.0, 2.0, -1.0));
+        let mirrored = solid_mirror(&drill, Point3::new(2.0, 0.0, 0.0), Vector3::unit_x());
+        let cut = crate::boolean::solid_difference(&make_box(4.0, 4.0, 4.0), &mirrored, 0.05)
+            .unwrap();
+        let props = crate::mass::mass_properties(&cut, 1.0, 0.001).unwrap();
+        let expected = 64.0 - PI;
+        assert!((props.volume - expected).abs() < 0.01 * expected, "{}", props.volume);
+        assert!(props.centroid.x < 2.0);
+    }
 }
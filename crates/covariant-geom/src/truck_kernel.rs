@@ -1,8 +1,11 @@
 //! `TruckKernel` — the truck-backed implementation of `GeomKernel`.
 
 use crate::kernel::GeomKernel;
-use crate::{Face, GeomError, GeomErrorKind, GeomResult, Mesh, Point3, Solid, Vector3,
-            DEFAULT_TOLERANCE};
+use crate::{
+    BoundingBox, EdgeSelector, Face, GeomError, GeomErrorKind, GeomResult, MassProperties,
+    Matrix4, Mesh, Point2, Point3, Solid, SweepFrame, Vector3, Wire, DEFAULT_TOLERANCE,
+};
+use covariant_thread::HelixParams;
 use std::path::Path;
 
 /// Stateless geometry kernel backed by the **truck** B-rep library.
@@ -22,6 +25,55 @@ impl GeomKernel for TruckKernel {
         Solid::from_truck(crate::primitives::make_sphere(radius))
     }
 
+    fn circle_profile(&self, center: Point2, radius: f64) -> GeomResult<Face> {
+        crate::sketch::make_circle(center, radius).map(Face::from_truck)
+    }
+
+    fn rectangle_profile(&self, origin: Point2, width: f64, height: f64) -> GeomResult<Face> {
+        crate::sketch::make_rectangle(origin, width, height).map(Face::from_truck)
+    }
+
+    fn polygon_profile(&self, points: &[Point2]) -> GeomResult<Face> {
+        crate::sketch::make_polygon(points).map(Face::from_truck)
+    }
+
+    fn profile_with_holes(&self, outer: &Face, holes: &[Face]) -> GeomResult<Face> {
+        let holes: Vec<_> = holes.iter().map(|h| h.inner().clone()).collect();
+        crate::sketch::face_with_holes(outer.inner(), &holes).map(Face::from_truck)
+    }
+
+    fn line_curve(&self, p0: Point3, p1: Point3) -> GeomResult<Wire> {
+        crate::curve::line_curve(p0, p1).map(Wire::from_truck)
+    }
+
+    fn arc_curve(
+        &self,
+        center: Point3,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    ) -> GeomResult<Wire> {
+        crate::curve::arc_curve(center, radius, start_angle, end_angle).map(Wire::from_truck)
+    }
+
+    fn circle_curve(&self, center: Point3, radius: f64, normal: Vector3) -> GeomResult<Wire> {
+        crate::curve::circle_curve(center, radius, normal).map(Wire::from_truck)
+    }
+
+    fn helix_curve(
+        &self,
+        radius: f64,
+        pitch: f64,
+        height: f64,
+        left_handed: bool,
+    ) -> GeomResult<Wire> {
+        crate::curve::helix_curve(radius, pitch, height, left_handed).map(Wire::from_truck)
+    }
+
+    fn interpolated_curve(&self, points: &[Point3]) -> GeomResult<Wire> {
+        crate::curve::interpolated_curve(points).map(Wire::from_truck)
+    }
+
     fn union(&self, a: &Solid, b: &Solid) -> GeomResult<Solid> {
         crate::boolean::solid_union(a.inner(), b.inner(), DEFAULT_TOLERANCE)
             .map(Solid::from_truck)
@@ -40,6 +92,23 @@ impl GeomKernel for TruckKernel {
             .ok_or_else(|| GeomError::new(GeomErrorKind::BooleanFailed, "intersection failed"))
     }
 
+    fn union_many(&self, solids: &[Solid]) -> GeomResult<Solid> {
+        if solids.is_empty() {
+            return Err(GeomError::new(
+                GeomErrorKind::InvalidInput,
+                "union_many requires at least one solid",
+            ));
+        }
+        let solids: Vec<_> = solids.iter().map(|s| s.inner().clone()).collect();
+        crate::boolean::solid_union_many(solids, DEFAULT_TOLERANCE).map(Solid::from_truck)
+    }
+
+    fn difference_many(&self, base: &Solid, cutters: &[Solid]) -> GeomResult<Solid> {
+        let cutters: Vec<_> = cutters.iter().map(|s| s.inner().clone()).collect();
+        crate::boolean::solid_difference_many(base.inner(), cutters, DEFAULT_TOLERANCE)
+            .map(Solid::from_truck)
+    }
+
     fn translate(&self, solid: &Solid, v: Vector3) -> Solid {
         Solid::from_truck(crate::transform::solid_translate(solid.inner(), v))
     }
@@ -53,8 +122,12 @@ impl GeomKernel for TruckKernel {
         ))
     }
 
-    fn scale(&self, solid: &Solid, center: Point3, factor: f64) -> Solid {
-        Solid::from_truck(crate::transform::solid_scale(solid.inner(), center, factor))
+    fn scale(&self, solid: &Solid, center: Point3, factors: Vector3) -> GeomResult<Solid> {
+        crate::transform::solid_scale(solid.inner(), center, factors).map(Solid::from_truck)
+    }
+
+    fn transform(&self, solid: &Solid, matrix: Matrix4) -> GeomResult<Solid> {
+        crate::transform::solid_transform(solid.inner(), matrix).map(Solid::from_truck)
     }
 
     fn mirror(&self, solid: &Solid, origin: Point3, normal: Vector3) -> Solid {
@@ -65,17 +138,106 @@ impl GeomKernel for TruckKernel {
         ))
     }
 
-    fn sweep(&self, profile: &Face, direction: Vector3) -> Solid {
-        Solid::from_truck(crate::sweep::solid_sweep(profile.inner(), direction))
+    fn translate_profile(&self, profile: &Face, v: Vector3) -> Face {
+        Face::from_truck(crate::transform::face_translate(profile.inner(), v))
     }
 
-    fn revolve(&self, profile: &Face, origin: Point3, axis: Vector3, angle_rad: f64) -> Solid {
-        Solid::from_truck(crate::sweep::solid_revolve(
-            profile.inner(),
-            origin,
-            axis,
-            angle_rad,
-        ))
+    fn rotate_profile(
+        &self,
+        profile: &Face,
+        origin: Point3,
+        axis: Vector3,
+        angle_rad: f64,
+    ) -> Face {
+        Face::from_truck(crate::transform::face_rotate(profile.inner(), origin, axis, angle_rad))
+    }
+
+    fn sweep(&self, profile: &Face, direction: Vector3) -> GeomResult<Solid> {
+        crate::sweep::solid_sweep(profile.inner(), direction).map(Solid::from_truck)
+    }
+
+    fn extrude(&self, profile: &Face, distance: f64) -> GeomResult<Solid> {
+        crate::sweep::solid_extrude(profile.inner(), distance).map(Solid::from_truck)
+    }
+
+    fn revolve(
+        &self,
+        profile: &Face,
+        origin: Point3,
+        axis: Vector3,
+        angle_rad: f64,
+    ) -> GeomResult<Solid> {
+        crate::sweep::solid_revolve(profile.inner(), origin, axis, angle_rad)
+            .map(Solid::from_truck)
+    }
+
+    fn sweep_along(&self, profile: &Face, path: &Wire, frame: SweepFrame) -> GeomResult<Solid> {
+        crate::sweep::solid_sweep_along(profile.inner(), path.inner(), frame).map(Solid::from_truck)
+    }
+
+    fn loft(&self, sections: &[Face], ruled: bool) -> GeomResult<Solid> {
+        let sections: Vec<_> = sections.iter().map(|f| f.inner().clone()).collect();
+        crate::loft::solid_loft(&sections, ruled).map(Solid::from_truck)
+    }
+
+    fn fillet(&self, solid: &Solid, radius: f64, edges: &EdgeSelector) -> GeomResult<Solid> {
+        crate::blend::solid_fillet(solid.inner(), radius, edges).map(Solid::from_truck)
+    }
+
+    fn chamfer(&self, solid: &Solid, distance: f64, edges: &EdgeSelector) -> GeomResult<Solid> {
+        crate::blend::solid_chamfer(solid.inner(), distance, edges).map(Solid::from_truck)
+    }
+
+    fn shell(&self, solid: &Solid, thickness: f64, open_faces: &[Vector3]) -> GeomResult<Solid> {
+        crate::offset::solid_shell(solid.inner(), thickness, open_faces).map(Solid::from_truck)
+    }
+
+    fn offset(&self, solid: &Solid, distance: f64) -> GeomResult<Solid> {
+        crate::offset::solid_offset(solid.inner(), distance).map(Solid::from_truck)
+    }
+
+    fn hull(&self, solids: &[Solid]) -> GeomResult<Solid> {
+        let solids: Vec<_> = solids.iter().map(|s| s.inner().clone()).collect();
+        crate::hull::solid_hull(&solids).map(Solid::from_truck)
+    }
+
+    fn minkowski(&self, a: &Solid, b: &Solid) -> GeomResult<Solid> {
+        crate::hull::solid_minkowski(a.inner(), b.inner()).map(Solid::from_truck)
+    }
+
+    fn thread(&self, helix: &HelixParams) -> GeomResult<Solid> {
+        crate::thread::solid_thread(helix).map(Solid::from_truck)
+    }
+
+    fn section(&self, solid: &Solid, origin: Point3, normal: Vector3) -> GeomResult<Vec<Face>> {
+        let faces = crate::section::solid_section(solid.inner(), origin, normal)?;
+        Ok(faces.into_iter().map(Face::from_truck).collect())
+    }
+
+    fn project(&self, solid: &Solid, origin: Point3, normal: Vector3) -> GeomResult<Vec<Face>> {
+        let faces = crate::section::solid_project(solid.inner(), origin, normal)?;
+        Ok(faces.into_iter().map(Face::from_truck).collect())
+    }
+
+    fn mass_properties(
+        &self,
+        solid: &Solid,
+        density: f64,
+        tolerance: f64,
+    ) -> GeomResult<MassProperties> {
+        crate::mass::mass_properties(solid.inner(), density, tolerance)
+    }
+
+    fn bounding_box(&self, solid: &Solid) -> GeomResult<BoundingBox> {
+        crate::query::bounding_box(solid.inner())
+    }
+
+    fn closest_distance(&self, a: &Solid, b: &Solid) -> GeomResult<f64> {
+        crate::query::closest_distance(a.inner(), b.inner())
+    }
+
+    fn contains_point(&self, solid: &Solid, point: Point3) -> GeomResult<bool> {
+        crate::query::contains_point(solid.inner(), point)
     }
 
     fn tessellate(&self, solid: &Solid, tolerance: f64) -> Mesh {
@@ -89,4 +251,42 @@ impl GeomKernel for TruckKernel {
     fn export_stl_ascii(&self, mesh: &Mesh, path: &Path) -> GeomResult<()> {
         crate::tessellate::write_stl_ascii(mesh.inner(), path)
     }
+
+    fn bodies(&self, solid: &Solid) -> GeomResult<Vec<Solid>> {
+        crate::step::solid_bodies(solid.inner())
+            .map(|bodies| bodies.into_iter().map(Solid::from_truck).collect())
+    }
+
+    fn export_step(
+        &self,
+        product: &str,
+        bodies: &[(String, Solid)],
+        path: &Path,
+    ) -> GeomResult<()> {
+        let bodies: Vec<_> = bodies
+            .iter()
+            .map(|(name, solid)| (name.clone(), solid.inner().clone()))
+            .collect();
+        crate::step::write_step(product, &bodies, path)
+    }
+
+    fn import_stl(&self, path: &Path) -> GeomResult<Mesh> {
+        crate::import::read_stl(path).map(Mesh::from_polygon)
+    }
+
+    fn import_obj(&self, path: &Path) -> GeomResult<Mesh> {
+        crate::import::read_obj(path).map(Mesh::from_polygon)
+    }
+
+    fn mesh_to_solid(&self, mesh: &Mesh) -> GeomResult<Solid> {
+        crate::import::mesh_to_solid(mesh.inner()).map(Solid::from_truck)
+    }
+
+    fn save_solid(&self, solid: &Solid, path: &Path) -> GeomResult<()> {
+        crate::native::write_solid(solid.inner(), path)
+    }
+
+    fn load_solid(&self, path: &Path) -> GeomResult<Solid> {
+        crate::native::read_solid(path).map(Solid::from_truck)
+    }
 }
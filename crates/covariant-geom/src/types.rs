@@ -2,12 +2,18 @@
 //!
 //! Public API never exposes truck generics directly.
 
+use truck_modeling::InnerSpace;
 use truck_polymesh::PolygonMesh;
 
+/// A 2D point in a sketch plane (re-exported from truck's cgmath-based types).
+pub type Point2 = truck_modeling::Point2;
 /// A 3D point (re-exported from truck's cgmath-based types).
 pub type Point3 = truck_modeling::Point3;
 /// A 3D vector (re-exported from truck's cgmath-based types).
 pub type Vector3 = truck_modeling::Vector3;
+/// A 4×4 homogeneous transform, column-major (re-exported from truck's
+/// cgmath-based types).
+pub type Matrix4 = truck_modeling::Matrix4;
 
 /// Default tolerance for boolean operations and tessellation.
 ///
@@ -55,10 +61,19 @@ impl Wire {
         Self(inner)
     }
 
-    #[allow(dead_code)]
     pub(crate) fn inner(&self) -> &TruckWire {
         &self.0
     }
+
+    /// The edges of the wire, in order.
+    pub fn edges(&self) -> Vec<Edge> {
+        self.0.edge_iter().cloned().map(Edge::from_truck).collect()
+    }
+
+    /// Returns `true` if the wire ends where it starts.
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
 }
 
 /// An opaque face (bounded surface).
@@ -142,6 +157,30 @@ impl Mesh {
             .map(|tri| [tri[0].pos, tri[1].pos, tri[2].pos])
             .collect()
     }
+
+    /// Unit normals at the three corners of each triangle, in the order of
+    /// [`tri_faces()`](Self::tri_faces).
+    ///
+    /// Corners carry the normal of the surface they were sampled from, so
+    /// smooth surfaces shade smoothly and edges stay sharp. Corners without
+    /// a normal take the triangle's flat normal.
+    pub fn corner_normals(&self) -> Vec<[[f64; 3]; 3]> {
+        let positions = self.0.positions();
+        let normals = self.0.normals();
+        self.0
+            .tri_faces()
+            .iter()
+            .map(|tri| {
+                let [a, b, c] = [tri[0], tri[1], tri[2]].map(|v| positions[v.pos]);
+                let flat = (b - a).cross(c - a);
+                [tri[0], tri[1], tri[2]].map(|v| {
+                    let n = v.nor.map_or(flat, |i| normals[i]);
+                    let n = if n.magnitude2() > 0.0 { n.normalize() } else { n };
+                    [n.x, n.y, n.z]
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -214,4 +253,20 @@ mod tests {
             assert!(tri[2] < pos.len());
         }
     }
+
+    #[test]
+    fn corner_normals_point_out_of_a_box() {
+        let solid = crate::primitives::make_box(10.0, 10.0, 10.0);
+        let mesh = Mesh::from_polygon(crate::tessellate::mesh_solid(&solid, 0.1));
+        let pos = mesh.positions();
+        let normals = mesh.corner_normals();
+        assert_eq!(normals.len(), mesh.triangle_count());
+        for (tri, corners) in mesh.tri_faces().iter().zip(&normals) {
+            for (&v, n) in tri.iter().zip(corners) {
+                let outward: f64 = (0..3).map(|k| (pos[v][k] - 5.0) * n[k]).sum();
+                assert!(outward > 0.0);
+                assert!((n.iter().map(|c| c * c).sum::<f64>() - 1.0).abs() < 1e-9);
+            }
+        }
+    }
 }
@@ -1,6 +1,6 @@
 //! End-to-end integration tests for covariant-geom.
 
-use covariant_geom::{GeomKernel, Point3, TruckKernel, Vector3};
+use covariant_geom::{EdgeSelector, GeomKernel, Point2, Point3, SweepFrame, TruckKernel, Vector3};
 
 fn kernel() -> TruckKernel {
     TruckKernel
@@ -82,6 +82,115 @@ fn transform_chain() {
         Vector3::new(0.0, 0.0, 1.0),
         std::f64::consts::FRAC_PI_4,
     );
-    let scaled = k.scale(&rotated, Point3::new(0.0, 0.0, 0.0), 2.0);
+    let scaled = k
+        .scale(&rotated, Point3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 2.0, 2.0))
+        .unwrap();
     let _mirrored = k.mirror(&scaled, Point3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0));
 }
+
+#[test]
+fn extruded_profile_with_hole_cuts_plate() {
+    let k = kernel();
+
+    let outer = k.rectangle_profile(Point2::new(0.0, 0.0), 30.0, 20.0).unwrap();
+    let hole = k.circle_profile(Point2::new(15.0, 10.0), 4.0).unwrap();
+    let profile = k.profile_with_holes(&outer, &[hole]).unwrap();
+    let bracket = k.extrude(&profile, 5.0).expect("extrude should succeed");
+
+    let pin = k.translate(&k.cylinder(2.0, 10.0), Vector3::new(5.0, 5.0, -2.5));
+    let result = k.difference(&bracket, &pin);
+    assert!(result.is_ok(), "extruded bracket - pin should succeed");
+}
+
+#[test]
+fn partial_revolve_tessellates() {
+    let k = kernel();
+    let profile = k.rectangle_profile(Point2::new(5.0, 0.0), 2.0, 10.0).unwrap();
+    let solid = k
+        .revolve(
+            &profile,
+            Point3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            std::f64::consts::FRAC_PI_2,
+        )
+        .expect("quarter revolve should succeed");
+    let mesh = k.tessellate(&solid, 0.1);
+    assert!(mesh.triangle_count() > 0);
+}
+
+#[test]
+fn pipe_swept_along_arc_joins_plate() {
+    let k = kernel();
+
+    // Quarter-bend pipe rising from the plate's top face.
+    let path = k
+        .arc_curve(Point3::new(0.0, 0.0, 0.0), 20.0, 0.0, std::f64::consts::FRAC_PI_2)
+        .unwrap();
+    assert_eq!(path.edges().len(), 1);
+    assert!(!path.is_closed());
+    let profile = k.circle_profile(Point2::new(0.0, 0.0), 3.0).unwrap();
+    let pipe = k
+        .sweep_along(&profile, &path, SweepFrame::FixedUp(Vector3::new(0.0, 0.0, 1.0)))
+        .expect("sweep along arc should succeed");
+
+    let mesh = k.tessellate(&pipe, 0.1);
+    assert!(mesh.triangle_count() > 0);
+}
+
+#[test]
+fn lofted_nozzle_can_be_hollowed() {
+    let k = kernel();
+
+    let inlet = k.circle_profile(Point2::new(0.0, 0.0), 10.0).unwrap();
+    let waist = k.translate_profile(
+        &k.circle_profile(Point2::new(0.0, 0.0), 6.0).unwrap(),
+        Vector3::new(0.0, 0.0, 15.0),
+    );
+    let outlet = k.translate_profile(
+        &k.rectangle_profile(Point2::new(-4.0, -4.0), 8.0, 8.0).unwrap(),
+        Vector3::new(0.0, 0.0, 30.0),
+    );
+    let body = k
+        .loft(&[inlet, waist, outlet], false)
+        .expect("smooth loft should succeed");
+
+    let bore = k.translate(&k.cylinder(2.0, 40.0), Vector3::new(0.0, 0.0, -5.0));
+    let result = k.difference(&body, &bore);
+    assert!(result.is_ok(), "nozzle - bore should succeed");
+}
+
+#[test]
+fn filleted_plate_can_be_drilled() {
+    let k = kernel();
+
+    let plate = k.box_solid(40.0, 30.0, 5.0);
+    let rounded = k
+        .fillet(&plate, 4.0, &EdgeSelector::ParallelTo(Vector3::new(0.0, 0.0, 1.0)))
+        .expect("fillet of vertical edges should succeed");
+
+    let hole = k.translate(&k.cylinder(3.0, 7.0), Vector3::new(20.0, 15.0, -1.0));
+    let drilled = k.difference(&rounded, &hole);
+    assert!(drilled.is_ok(), "filleted plate - hole should succeed");
+    let mesh = k.tessellate(&drilled.unwrap(), 0.1);
+    assert!(mesh.triangle_count() > 0);
+}
+
+#[test]
+fn shelled_enclosure_takes_a_cable_hole() {
+    let k = kernel();
+
+    let body = k.box_solid(60.0, 40.0, 20.0);
+    let enclosure = k
+        .shell(&body, 2.0, &[Vector3::new(0.0, 0.0, 1.0)])
+        .expect("open-top shell should succeed");
+
+    let hole = k.rotate(
+        &k.cylinder(4.0, 10.0),
+        Point3::new(0.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0),
+        std::f64::consts::FRAC_PI_2,
+    );
+    let hole = k.translate(&hole, Vector3::new(-5.0, 20.0, 10.0));
+    let result = k.difference(&enclosure, &hole);
+    assert!(result.is_ok(), "enclosure - cable hole should succeed");
+}
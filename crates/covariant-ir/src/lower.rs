@@ -402,12 +402,12 @@ mod tests {
 
     #[test]
     fn lower_float_lit() {
-        let (dag, errors) = parse_and_lower("let x = 3.14");
+        let (dag, errors) = parse_and_lower("let x = 2.5");
         assert!(errors.is_empty());
         let root = dag.roots()[0];
         match dag.node(root) {
             IrNode::Let { value, .. } => {
-                assert_eq!(*dag.node(*value), IrNode::FloatLit(3.14));
+                assert_eq!(*dag.node(*value), IrNode::FloatLit(2.5));
             }
             other => panic!("expected Let, got {other:?}"),
         }
@@ -0,0 +1,130 @@
+use crate::{ThreadDimensions, ThreadSize};
+
+/// Depth of the Whitworth form as a fraction of the pitch: the 55° flanks
+/// meet at `0.960491 P`, and a sixth of that is rounded off at both crest
+/// and root.
+const THREAD_DEPTH: f64 = 0.640327;
+
+/// Look up British Standard Whitworth (BSW), British Standard Fine (BSF)
+/// and British Standard Pipe parallel (BSPP, "G") dimensions by size.
+///
+/// Returns `None` for other sizes.
+/// Major diameters and threads per inch per BS 84 and ISO 228-1, converted
+/// to mm; minor diameters follow from the Whitworth form.
+pub fn lookup(size: ThreadSize) -> Option<ThreadDimensions> {
+    //           major    TPI   tap     cl_c   cl_m   cl_f   insert
+    let data: (f64, f64, f64, f64, f64, f64, f64) = match size {
+        // BSW (BS 84 coarse)
+        ThreadSize::Bsw1_8   => (3.175,  40.0, 2.55,  3.4,   3.6,   3.9,   4.2),
+        ThreadSize::Bsw3_16  => (4.763,  24.0, 3.6,   5.0,   5.3,   5.6,   6.1),
+        ThreadSize::Bsw1_4   => (6.350,  20.0, 5.1,   6.6,   7.0,   7.4,   8.0),
+        ThreadSize::Bsw5_16  => (7.938,  18.0, 6.5,   8.3,   8.7,   9.1,   10.0),
+        ThreadSize::Bsw3_8   => (9.525,  16.0, 7.9,   9.9,  10.3,  10.7,   12.0),
+        ThreadSize::Bsw7_16  => (11.113, 14.0, 9.3,  11.5,  11.9,  12.3,   14.0),
+        ThreadSize::Bsw1_2   => (12.700, 12.0, 10.5, 13.0,  13.5,  14.0,   16.0),
+        ThreadSize::Bsw5_8   => (15.875, 11.0, 13.5, 16.3,  16.7,  17.5,   20.0),
+        ThreadSize::Bsw3_4   => (19.050, 10.0, 16.25,19.5,  20.0,  21.0,   24.0),
+        ThreadSize::Bsw7_8   => (22.225,  9.0, 19.25,22.8,  23.5,  24.5,   28.0),
+        ThreadSize::Bsw1     => (25.400,  8.0, 22.0, 26.0,  26.5,  28.0,   32.0),
+        // BSF (BS 84 fine)
+        ThreadSize::Bsf1_4   => (6.350,  26.0, 5.3,   6.6,   7.0,   7.4,   8.0),
+        ThreadSize::Bsf5_16  => (7.938,  22.0, 6.8,   8.3,   8.7,   9.1,   10.0),
+        ThreadSize::Bsf3_8   => (9.525,  20.0, 8.3,   9.9,  10.3,  10.7,   12.0),
+        ThreadSize::Bsf7_16  => (11.113, 18.0, 9.7,  11.5,  11.9,  12.3,   14.0),
+        ThreadSize::Bsf1_2   => (12.700, 16.0, 11.1, 13.0,  13.5,  14.0,   16.0),
+        ThreadSize::Bsf9_16  => (14.288, 16.0, 12.7, 14.7,  15.2,  15.8,   18.0),
+        ThreadSize::Bsf5_8   => (15.875, 14.0, 14.0, 16.3,  16.7,  17.5,   20.0),
+        ThreadSize::Bsf3_4   => (19.050, 12.0, 16.75,19.5,  20.0,  21.0,   24.0),
+        ThreadSize::Bsf7_8   => (22.225, 11.0, 19.75,22.8,  23.5,  24.5,   28.0),
+        ThreadSize::Bsf1     => (25.400, 10.0, 22.75,26.0,  26.5,  28.0,   32.0),
+        // BSPP (ISO 228-1 parallel pipe)
+        ThreadSize::G1_8     => (9.728,  28.0, 8.8,  10.0,  10.5,  11.0,   11.2),
+        ThreadSize::G1_4     => (13.157, 19.0, 11.8, 13.5,  14.0,  14.5,   15.2),
+        ThreadSize::G3_8     => (16.662, 19.0, 15.25,17.0,  17.5,  18.0,   18.7),
+        ThreadSize::G1_2     => (20.955, 14.0, 19.0, 21.5,  22.0,  23.0,   23.5),
+        ThreadSize::G5_8     => (22.911, 14.0, 21.0, 23.5,  24.0,  25.0,   25.5),
+        ThreadSize::G3_4     => (26.441, 14.0, 24.5, 27.0,  27.5,  28.5,   29.0),
+        ThreadSize::G1       => (33.249, 11.0, 30.75,34.0,  34.5,  36.0,   36.5),
+        ThreadSize::G1_1_4   => (41.910, 11.0, 39.5, 42.5,  43.5,  45.0,   45.5),
+        ThreadSize::G1_1_2   => (47.803, 11.0, 45.25,48.5,  49.5,  51.0,   51.5),
+        ThreadSize::G2       => (59.614, 11.0, 57.0, 60.5,  61.5,  63.0,   63.5),
+        _ => return None,
+    };
+
+    let pitch = 25.4 / data.1;
+    Some(ThreadDimensions {
+        nominal: data.0,
+        pitch,
+        major_diameter: data.0,
+        minor_diameter: data.0 - 2.0 * THREAD_DEPTH * pitch,
+        tap_drill: data.2,
+        clearance_close: data.3,
+        clearance_medium: data.4,
+        clearance_free: data.5,
+        insert_hole: data.6,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ThreadStandard;
+
+    fn british_sizes() -> impl Iterator<Item = ThreadSize> {
+        ThreadSize::INCH.iter().copied().filter(|size| {
+            matches!(size.standard(), ThreadStandard::Bsw | ThreadStandard::Bsp)
+        })
+    }
+
+    #[test]
+    fn bsw_1_4_dimensions() {
+        let d = lookup(ThreadSize::Bsw1_4).unwrap();
+        assert!((d.nominal - 6.350).abs() < 0.001);
+        assert!((d.pitch - 1.270).abs() < 0.001);
+        assert!((d.minor_diameter - 4.724).abs() < 0.001);
+        assert_eq!(d.tap_drill, 5.1);
+    }
+
+    #[test]
+    fn bsf_1_2_dimensions() {
+        let d = lookup(ThreadSize::Bsf1_2).unwrap();
+        assert!((d.pitch - 1.5875).abs() < 0.0001);
+        assert!((d.minor_diameter - 10.667).abs() < 0.001);
+        assert_eq!(d.tap_drill, 11.1);
+    }
+
+    #[test]
+    fn g_1_2_dimensions() {
+        let d = lookup(ThreadSize::G1_2).unwrap();
+        assert!((d.major_diameter - 20.955).abs() < 0.001);
+        assert!((d.pitch - 1.814).abs() < 0.001);
+        assert!((d.minor_diameter - 18.631).abs() < 0.001);
+    }
+
+    #[test]
+    fn all_british_sizes_present() {
+        for size in british_sizes() {
+            assert!(lookup(size).is_some(), "missing British data for {size}");
+        }
+    }
+
+    #[test]
+    fn diameter_ordering() {
+        for size in british_sizes() {
+            let d = lookup(size).unwrap();
+            assert!(d.minor_diameter < d.major_diameter, "{size}: minor < major");
+            assert!(d.tap_drill > d.minor_diameter, "{size}: tap > minor");
+            assert!(d.tap_drill < d.major_diameter, "{size}: tap < major");
+            assert!(d.clearance_close > d.major_diameter, "{size}: cl_close > major");
+            assert!(d.clearance_medium >= d.clearance_close, "{size}: cl_med >= cl_close");
+            assert!(d.clearance_free >= d.clearance_medium, "{size}: cl_free >= cl_med");
+            assert!(d.insert_hole >= d.clearance_free, "{size}: insert >= cl_free");
+        }
+    }
+
+    #[test]
+    fn non_british_returns_none() {
+        assert!(lookup(ThreadSize::M5).is_none());
+        assert!(lookup(ThreadSize::Uts1_4_20).is_none());
+    }
+}
@@ -0,0 +1,385 @@
+//! Thread callouts as written on drawings.
+//!
+//! A callout gives the size and, optionally, the thread series, tolerance
+//! class, hand and length:
+//!
+//! - ISO metric: `M6`, `M6x1-6H`, `M8x1.25-6g-LH x 20`
+//! - UTS: `1/4-20 UNC-2B`, `#10-32 UNF-2A x 1/2`
+//! - Whitworth: `1/4 BSW`, `3/8-20 BSF`
+//! - BSPP: `G1/2`, `G 1-1/4 A`
+//!
+//! The tolerance class tells internal threads from external ones: upper
+//! case metric classes (`6H`) and UTS class `B` are internal, lower case
+//! ones (`6g`) and class `A` external, and so are BSPP threads with class
+//! `A` or `B`. A callout without a class describes a tapped hole. The
+//! length follows a free-standing `x`, in mm for metric threads and in
+//! inches otherwise unless it carries a `mm`, `in` or `"` unit.
+
+use std::str::FromStr;
+
+use crate::{bsw, ThreadKind, ThreadSeries, ThreadSize, ThreadSpec};
+
+/// A parsed thread callout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadCallout {
+    pub size: ThreadSize,
+    /// Series of the size, e.g. `UNC` or `BSF`; a series written in the
+    /// callout must match it.
+    pub series: ThreadSeries,
+    /// `Internal` or `External`, from the tolerance class.
+    pub kind: ThreadKind,
+    /// Tolerance class as written, e.g. `6H` or `2B`.
+    pub tolerance: Option<String>,
+    pub left_hand: bool,
+    /// Thread length in mm, if the callout gives one.
+    pub length: Option<f64>,
+}
+
+impl ThreadCallout {
+    /// The thread spec for this callout, `depth` long with a `chamfer` at
+    /// its entrance.
+    pub fn spec(&self, depth: f64, chamfer: f64) -> ThreadSpec {
+        ThreadSpec {
+            series: self.series,
+            left_hand: self.left_hand,
+            ..ThreadSpec::new(self.size, self.kind, depth, chamfer)
+        }
+    }
+}
+
+impl FromStr for ThreadCallout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s).map_err(|why| format!("invalid thread callout '{s}': {why}"))
+    }
+}
+
+/// Thread series of the Unified standard (ASME B1.1) by name; the
+/// rounded-root `UNR` series share the dimensions of their `UN` ones.
+fn unified_series(name: &str) -> Option<ThreadSeries> {
+    match name {
+        "UNC" | "UNRC" => Some(ThreadSeries::Unc),
+        "UNF" | "UNRF" => Some(ThreadSeries::Unf),
+        "UNEF" | "UNREF" => Some(ThreadSeries::Unef),
+        "UN" | "UNR" => Some(ThreadSeries::Un),
+        "UNS" => Some(ThreadSeries::Uns),
+        _ => None,
+    }
+}
+
+/// How the size, class and length of a callout read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Family {
+    Metric,
+    Unified,
+    Whitworth,
+    Pipe,
+}
+
+fn parse(s: &str) -> Result<ThreadCallout, String> {
+    let text = s.trim().replace('×', "x");
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    let length = match words.iter().position(|w| w.eq_ignore_ascii_case("x")) {
+        Some(i) => {
+            let length = words.split_off(i + 1).concat();
+            words.truncate(i);
+            Some(length)
+        }
+        None => None,
+    };
+    let mut parts = words
+        .iter()
+        .flat_map(|w| w.split('-'))
+        .filter(|p| !p.is_empty())
+        .peekable();
+    let first = parts.next().ok_or("no thread size")?;
+
+    let (family, size, mut tolerance) = if is_metric(first) {
+        (Family::Metric, first.parse()?, None)
+    } else if let Some(rest) = first.strip_prefix('G') {
+        // `G1/2`, `G 1/2`, `G1-1/4` or `G1 1/4`, maybe with the class glued on.
+        let mut size = match rest {
+            "" => parts.next().ok_or("no pipe size after 'G'")?.to_string(),
+            _ => rest.to_string(),
+        };
+        if let Some(fraction) = parts.next_if(|p| p.contains('/') && !size.contains('/')) {
+            size = format!("{size}-{fraction}");
+        }
+        let class = size
+            .strip_suffix(['A', 'B'])
+            .map(|s| (s.len(), size[s.len()..].to_string()));
+        let tolerance = class.map(|(len, class)| {
+            size.truncate(len);
+            class
+        });
+        (Family::Pipe, parse_size(&format!("G{size}"))?, tolerance)
+    } else if first.starts_with(|c: char| c.is_ascii_digit() || c == '#') {
+        let diameter = first.trim_end_matches('"');
+        let tpi = parts.next_if(|p| p.bytes().all(|b| b.is_ascii_digit()));
+        let series = parts.next_if(|p| unified_series(p).is_some() || *p == "BSW" || *p == "BSF");
+        match (series, tpi) {
+            (Some(series @ ("BSW" | "BSF")), tpi) => {
+                let size = parse_size(&format!("{diameter}\" {series}"))?;
+                if let Some(tpi) = tpi {
+                    check_tpi(size, tpi)?;
+                }
+                (Family::Whitworth, size, None)
+            }
+            (_, Some(tpi)) => {
+                let name = match diameter.parse::<u32>() {
+                    Ok(n @ 2..=12) => format!("#{n}-{tpi}"),
+                    _ if diameter.starts_with('#') => format!("{diameter}-{tpi}"),
+                    _ => format!("{diameter}\"-{tpi}"),
+                };
+                let size = parse_size(&name)?;
+                if let Some(written) = series.filter(|s| unified_series(s) != Some(size.series())) {
+                    return Err(format!("{size} is {}, not {written}", size.series()));
+                }
+                (Family::Unified, size, None)
+            }
+            (_, None) => return Err("no threads per inch".to_string()),
+        }
+    } else {
+        return Err(format!("unknown thread size '{first}'"));
+    };
+
+    let mut left_hand = false;
+    for part in parts {
+        let kind_of_class = match family {
+            Family::Metric => metric_class_kind(part),
+            Family::Unified => unified_class_kind(part),
+            Family::Whitworth => None,
+            Family::Pipe => matches!(part, "A" | "B").then_some(ThreadKind::External),
+        };
+        if part.eq_ignore_ascii_case("LH") || part.eq_ignore_ascii_case("RH") {
+            left_hand = part.eq_ignore_ascii_case("LH");
+        } else if kind_of_class.is_some() && tolerance.is_none() {
+            tolerance = Some(part.to_string());
+        } else {
+            return Err(format!("unexpected '{part}'"));
+        }
+    }
+
+    let kind = match (&tolerance, family) {
+        (Some(_), Family::Pipe) => ThreadKind::External,
+        (Some(class), Family::Metric) => metric_class_kind(class).unwrap_or(ThreadKind::Internal),
+        (Some(class), _) => unified_class_kind(class).unwrap_or(ThreadKind::Internal),
+        (None, _) => ThreadKind::Internal,
+    };
+    let length = length
+        .map(|text| parse_length(&text, family == Family::Metric))
+        .transpose()?;
+    Ok(ThreadCallout {
+        size,
+        series: size.series(),
+        kind,
+        tolerance,
+        left_hand,
+        length,
+    })
+}
+
+fn is_metric(part: &str) -> bool {
+    part.strip_prefix('M')
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+fn parse_size(name: &str) -> Result<ThreadSize, String> {
+    name.parse().map_err(|_| format!("no thread size {name}"))
+}
+
+/// Whitworth sizes have one pitch each; a stated one must match it.
+fn check_tpi(size: ThreadSize, tpi: &str) -> Result<(), String> {
+    let pitch = bsw::lookup(size).map(|d| d.pitch).unwrap_or_default();
+    let stated: f64 = tpi
+        .parse()
+        .map_err(|_| format!("bad threads per inch '{tpi}'"))?;
+    if (25.4 / stated - pitch).abs() > 1e-6 {
+        return Err(format!(
+            "{size} has {} threads per inch, not {tpi}",
+            (25.4 / pitch).round()
+        ));
+    }
+    Ok(())
+}
+
+/// Kind of thread an ISO 965 tolerance class such as `6H` or `5g6g`
+/// belongs to: grade digits with upper case positions for internal threads
+/// and lower case for external ones.
+fn metric_class_kind(class: &str) -> Option<ThreadKind> {
+    let bytes = class.as_bytes();
+    if !matches!(bytes.len(), 2 | 4) {
+        return None;
+    }
+    let mut kind = None;
+    for pair in bytes.chunks(2) {
+        if !(b'3'..=b'9').contains(&pair[0]) {
+            return None;
+        }
+        let this = match pair[1] {
+            b'G' | b'H' => ThreadKind::Internal,
+            b'e' | b'f' | b'g' | b'h' => ThreadKind::External,
+            _ => return None,
+        };
+        if kind.is_some_and(|k| k != this) {
+            return None;
+        }
+        kind = Some(this);
+    }
+    kind
+}
+
+/// Kind of thread a UTS class (`1A`–`3A`, `1B`–`3B`) belongs to.
+fn unified_class_kind(class: &str) -> Option<ThreadKind> {
+    match class.as_bytes() {
+        [b'1'..=b'3', b'A'] => Some(ThreadKind::External),
+        [b'1'..=b'3', b'B'] => Some(ThreadKind::Internal),
+        _ => None,
+    }
+}
+
+/// Length in mm from `20`, `20mm`, `0.5in` or `1/2"`; bare numbers are in
+/// mm for metric threads and in inches otherwise.
+fn parse_length(text: &str, metric: bool) -> Result<f64, String> {
+    let (number, scale) = if let Some(n) = text.strip_suffix("mm") {
+        (n, 1.0)
+    } else if let Some(n) = text.strip_suffix("in").or_else(|| text.strip_suffix('"')) {
+        (n, 25.4)
+    } else {
+        (text, if metric { 1.0 } else { 25.4 })
+    };
+    let value = match number.split_once('/') {
+        Some((n, d)) => n
+            .parse::<f64>()
+            .ok()
+            .zip(d.parse::<f64>().ok())
+            .map(|(n, d)| n / d),
+        None => number.parse().ok(),
+    };
+    match value {
+        Some(v) if v.is_finite() && v > 0.0 => Ok(v * scale),
+        _ => Err(format!("bad length '{text}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn callout(text: &str) -> ThreadCallout {
+        text.parse().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    #[test]
+    fn metric_callouts() {
+        let c = callout("M6x1-6H");
+        assert_eq!(c.size, ThreadSize::M6);
+        assert_eq!(c.kind, ThreadKind::Internal);
+        assert_eq!(c.tolerance.as_deref(), Some("6H"));
+        assert!(!c.left_hand);
+        assert_eq!(c.length, None);
+
+        let c = callout("M8x1-5g6g-LH x 20");
+        assert_eq!(c.size, ThreadSize::metric(8.0, 1.0));
+        assert_eq!(c.series, ThreadSeries::MetricFine);
+        assert_eq!(c.kind, ThreadKind::External);
+        assert!(c.left_hand);
+        assert_eq!(c.length, Some(20.0));
+
+        assert_eq!(callout("M3").size, ThreadSize::M3);
+        assert_eq!(callout("M8×1.25-6g × 20mm").length, Some(20.0));
+    }
+
+    #[test]
+    fn unified_callouts() {
+        let c = callout("1/4-20 UNC-2B");
+        assert_eq!(c.size, ThreadSize::Uts1_4_20);
+        assert_eq!(c.series, ThreadSeries::Unc);
+        assert_eq!(c.kind, ThreadKind::Internal);
+        assert_eq!(c.tolerance.as_deref(), Some("2B"));
+
+        let c = callout("#10-32 UNF-2A x 1/2");
+        assert_eq!(c.size, ThreadSize::Uts10_32);
+        assert_eq!(c.series, ThreadSeries::Unf);
+        assert_eq!(c.kind, ThreadKind::External);
+        assert_eq!(c.length, Some(12.7));
+
+        assert_eq!(callout("10-24 UNC").size, ThreadSize::Uts10_24);
+        assert_eq!(callout("3/8\"-16 UNC-2A-LH x 20mm").length, Some(20.0));
+        assert!(callout("1/2-13").tolerance.is_none());
+        assert_eq!(callout("1/4-20 UNRC-2A").series, ThreadSeries::Unc);
+    }
+
+    #[test]
+    fn rejects_mismatched_series() {
+        for text in [
+            "1/4-20 UNF",
+            "#10-32 UNC",
+            "#10-24 UNEF-2B",
+            "3/8-16 UN",
+            "1/2-13 UNS",
+        ] {
+            assert!(text.parse::<ThreadCallout>().is_err(), "{text:?}");
+        }
+        let err = "1/4-20 UNF".parse::<ThreadCallout>().unwrap_err();
+        assert!(err.contains("1/4\"-20 is UNC, not UNF"), "{err}");
+    }
+
+    #[test]
+    fn rejects_untabulated_metric_pitch() {
+        let err = "M8x0.9-6H".parse::<ThreadCallout>().unwrap_err();
+        assert!(err.contains("M8 comes in pitches 1.25, 1, 0.75"), "{err}");
+    }
+
+    #[test]
+    fn british_callouts() {
+        assert_eq!(callout("1/4 BSW").size, ThreadSize::Bsw1_4);
+        assert_eq!(callout("3/8-20 BSF").size, ThreadSize::Bsf3_8);
+        assert_eq!(callout("3/8-20 BSF").series, ThreadSeries::Bsf);
+
+        let c = callout("G1/2");
+        assert_eq!(c.size, ThreadSize::G1_2);
+        assert_eq!(c.series, ThreadSeries::Bspp);
+        assert_eq!(c.kind, ThreadKind::Internal);
+
+        let c = callout("G 1-1/4 A");
+        assert_eq!(c.size, ThreadSize::G1_1_4);
+        assert_eq!(c.kind, ThreadKind::External);
+        assert_eq!(c.tolerance.as_deref(), Some("A"));
+
+        assert_eq!(callout("G1 1/2").size, ThreadSize::G1_1_2);
+        assert_eq!(callout("G3/4B").tolerance.as_deref(), Some("B"));
+    }
+
+    #[test]
+    fn spec_keeps_the_hand() {
+        let spec = callout("M10x1.25-6H-LH").spec(12.0, 0.5);
+        assert_eq!(spec.size, ThreadSize::metric(10.0, 1.25));
+        assert_eq!(spec.series, ThreadSeries::MetricFine);
+        assert_eq!(spec.kind, ThreadKind::Internal);
+        assert_eq!(spec.depth, 12.0);
+        assert!(spec.left_hand);
+    }
+
+    #[test]
+    fn rejects_bad_callouts() {
+        for text in [
+            "",
+            "M6x1-6Z",
+            "M6x1-6H-6g",
+            "1/4-20 UNC-2C",
+            "1/4 UNC",
+            "1/4-28 UNF",
+            "3/8-16 BSF",
+            "1/4 BSW-2A",
+            "M6 x",
+            "M6 x -3",
+            "Q6",
+        ] {
+            assert!(text.parse::<ThreadCallout>().is_err(), "{text:?}");
+        }
+        let err = "3/8-16 BSF".parse::<ThreadCallout>().unwrap_err();
+        assert!(err.contains("20 threads per inch, not 16"), "{err}");
+    }
+}
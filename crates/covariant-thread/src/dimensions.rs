@@ -1,14 +1,13 @@
-use crate::{iso_metric, uts, ClearanceFit, ThreadKind, ThreadSpec, ThreadStandard};
+use crate::{bsw, iso_metric, uts, ClearanceFit, ThreadKind, ThreadSpec, ThreadStandard};
 
 /// Look up thread dimensions for a given spec.
 ///
-/// Panics for `ThreadStandard::Bsw` (not yet implemented).
 /// Returns `None` if the size is not found in the standard's database.
 pub fn get_dimensions(spec: &ThreadSpec) -> Option<ThreadDimensions> {
     match spec.standard {
         ThreadStandard::IsoMetric => iso_metric::lookup(spec.size),
         ThreadStandard::Uts => uts::lookup(spec.size),
-        ThreadStandard::Bsw => unimplemented!("BSW thread data not yet available"),
+        ThreadStandard::Bsw | ThreadStandard::Bsp => bsw::lookup(spec.size),
     }
 }
 
@@ -99,16 +98,19 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "BSW")]
-    fn get_dimensions_bsw_panics() {
+    fn get_dimensions_bsw() {
+        let spec = ThreadSpec::new(ThreadSize::Bsw1_4, ThreadKind::Internal, 10.0, 0.0);
+        let dims = get_dimensions(&spec).unwrap();
+        assert!((dims.nominal - 6.350).abs() < 0.001);
+    }
+
+    #[test]
+    fn get_dimensions_size_outside_standard() {
         let spec = ThreadSpec {
             standard: ThreadStandard::Bsw,
-            size: ThreadSize::M5,
-            kind: ThreadKind::Internal,
-            depth: 10.0,
-            chamfer: 0.0,
+            ..ThreadSpec::new(ThreadSize::M5, ThreadKind::Internal, 10.0, 0.0)
         };
-        get_dimensions(&spec);
+        assert!(get_dimensions(&spec).is_none());
     }
 
     #[test]
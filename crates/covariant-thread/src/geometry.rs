@@ -24,6 +24,8 @@ pub struct HelixParams {
     pub minor_diameter: f64,
     pub pitch: f64,
     pub depth: f64,
+    /// Left-hand thread; `false` for the usual right hand.
+    pub left_hand: bool,
 }
 
 /// Cosmetic annotation data (lightweight visual indicator).
@@ -98,6 +100,7 @@ pub fn generate_thread_geometry(spec: &ThreadSpec, mode: ThreadMode) -> Option<T
                 minor_diameter: dims.minor_diameter,
                 pitch: dims.pitch,
                 depth: spec.depth,
+                left_hand: spec.left_hand,
             },
         },
     })
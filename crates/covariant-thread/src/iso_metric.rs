@@ -1,43 +1,145 @@
 use crate::{ThreadDimensions, ThreadSize};
 
+/// Coarse series (ISO 261), with the hole sizes every pitch of the
+/// diameter shares.
+//              size    minor   tap    cl_c   cl_m   cl_f   insert
+const COARSE: [(ThreadSize, f64, f64, f64, f64, f64, f64); 29] = [
+    (ThreadSize::M1_6,  1.221,  1.25,  1.7,   1.8,   2.0,   2.1),
+    (ThreadSize::M2,    1.567,  1.6,   2.2,   2.4,   2.6,   2.7),
+    (ThreadSize::M2_5,  2.013,  2.05,  2.7,   2.9,   3.1,   3.3),
+    (ThreadSize::M3,    2.459,  2.5,   3.2,   3.4,   3.6,   4.0),
+    (ThreadSize::M3_5,  2.85,   2.9,   3.7,   3.9,   4.2,   4.6),
+    (ThreadSize::M4,    3.242,  3.3,   4.3,   4.5,   4.8,   5.2),
+    (ThreadSize::M5,    4.134,  4.2,   5.3,   5.5,   5.8,   6.4),
+    (ThreadSize::M6,    4.917,  5.0,   6.4,   6.6,   7.0,   7.6),
+    (ThreadSize::M8,    6.647,  6.8,   8.4,   9.0,   10.0,  10.2),
+    (ThreadSize::M10,   8.376,  8.5,   10.5,  11.0,  12.0,  12.7),
+    (ThreadSize::M12,   10.106, 10.2,  13.0,  13.5,  14.5,  15.2),
+    (ThreadSize::M14,   11.835, 12.0,  15.0,  15.5,  16.5,  17.7),
+    (ThreadSize::M16,   13.835, 14.0,  17.0,  17.5,  18.5,  20.2),
+    (ThreadSize::M18,   15.294, 15.5,  19.0,  20.0,  21.0,  22.7),
+    (ThreadSize::M20,   17.294, 17.5,  21.0,  22.0,  24.0,  25.2),
+    (ThreadSize::M22,   19.294, 19.5,  23.0,  24.0,  26.0,  27.7),
+    (ThreadSize::M24,   20.752, 21.0,  25.0,  26.0,  28.0,  30.2),
+    (ThreadSize::M27,   23.752, 24.0,  28.0,  30.0,  32.0,  34.0),
+    (ThreadSize::M30,   26.211, 26.5,  31.0,  33.0,  35.0,  37.7),
+    (ThreadSize::M33,   29.211, 29.5,  34.0,  36.0,  38.0,  41.5),
+    (ThreadSize::M36,   31.67,  32.0,  37.0,  39.0,  42.0,  45.2),
+    (ThreadSize::M39,   34.67,  35.0,  40.0,  42.0,  45.0,  49.0),
+    (ThreadSize::M42,   37.129, 37.5,  43.0,  45.0,  48.0,  52.7),
+    (ThreadSize::M45,   40.129, 40.5,  46.0,  48.0,  52.0,  56.5),
+    (ThreadSize::M48,   42.587, 43.0,  50.0,  52.0,  56.0,  60.2),
+    (ThreadSize::M52,   46.587, 47.0,  54.0,  56.0,  62.0,  65.2),
+    (ThreadSize::M56,   50.046, 50.5,  58.0,  62.0,  66.0,  70.2),
+    (ThreadSize::M60,   54.046, 54.5,  62.0,  66.0,  70.0,  75.2),
+    (ThreadSize::M64,   57.505, 58.0,  66.0,  70.0,  74.0,  80.2),
+];
+
+/// Fine series (ISO 261): diameter, pitch, minor diameter and tap drill.
+/// Clearance and insert holes are those of the coarse size.
+//   d     pitch minor   tap
+const FINE: [(f64, f64, f64, f64); 35] = [
+    (3.0,  0.35, 2.621,  2.65),
+    (4.0,  0.5,  3.459,  3.5),
+    (5.0,  0.5,  4.459,  4.5),
+    (6.0,  0.75, 5.188,  5.25),
+    (8.0,  1.0,  6.917,  7.0),
+    (8.0,  0.75, 7.188,  7.25),
+    (10.0, 1.25, 8.647,  8.75),
+    (10.0, 1.0,  8.917,  9.0),
+    (10.0, 0.75, 9.188,  9.25),
+    (12.0, 1.5,  10.376, 10.5),
+    (12.0, 1.25, 10.647, 10.75),
+    (12.0, 1.0,  10.917, 11.0),
+    (14.0, 1.5,  12.376, 12.5),
+    (16.0, 1.5,  14.376, 14.5),
+    (16.0, 1.0,  14.917, 15.0),
+    (18.0, 2.0,  15.835, 16.0),
+    (18.0, 1.5,  16.376, 16.5),
+    (20.0, 2.0,  17.835, 18.0),
+    (20.0, 1.5,  18.376, 18.5),
+    (22.0, 2.0,  19.835, 20.0),
+    (22.0, 1.5,  20.376, 20.5),
+    (24.0, 2.0,  21.835, 22.0),
+    (24.0, 1.5,  22.376, 22.5),
+    (27.0, 2.0,  24.835, 25.0),
+    (30.0, 2.0,  27.835, 28.0),
+    (33.0, 2.0,  30.835, 31.0),
+    (36.0, 3.0,  32.752, 33.0),
+    (39.0, 3.0,  35.752, 36.0),
+    (42.0, 3.0,  38.752, 39.0),
+    (45.0, 3.0,  41.752, 42.0),
+    (48.0, 3.0,  44.752, 45.0),
+    (52.0, 4.0,  47.67,  48.0),
+    (56.0, 4.0,  51.67,  52.0),
+    (60.0, 4.0,  55.67,  56.0),
+    (64.0, 4.0,  59.67,  60.0),
+];
+
 /// Look up ISO 261 metric thread dimensions by size.
 ///
-/// Returns `None` for non-ISO sizes.
-/// All values in mm per ISO 261 / ISO 262 / ISO 273.
+/// Returns `None` for non-ISO sizes and pitches outside the coarse and fine
+/// series. All values in mm per ISO 261 / ISO 262 / ISO 273.
 pub fn lookup(size: ThreadSize) -> Option<ThreadDimensions> {
-    //          pitch  major  minor   tap    cl_c   cl_m   cl_f   insert
-    let data: (f64, f64, f64, f64, f64, f64, f64, f64) = match size {
-        ThreadSize::M1_6 => (0.35, 1.6,  1.221, 1.25,  1.7,   1.8,   2.0,   2.1),
-        ThreadSize::M2   => (0.4,  2.0,  1.567, 1.6,   2.2,   2.4,   2.6,   2.7),
-        ThreadSize::M2_5 => (0.45, 2.5,  2.013, 2.05,  2.7,   2.9,   3.1,   3.3),
-        ThreadSize::M3   => (0.5,  3.0,  2.459, 2.5,   3.2,   3.4,   3.6,   4.0),
-        ThreadSize::M4   => (0.7,  4.0,  3.242, 3.3,   4.3,   4.5,   4.8,   5.2),
-        ThreadSize::M5   => (0.8,  5.0,  4.134, 4.2,   5.3,   5.5,   5.8,   6.4),
-        ThreadSize::M6   => (1.0,  6.0,  4.917, 5.0,   6.4,   6.6,   7.0,   7.6),
-        ThreadSize::M8   => (1.25, 8.0,  6.647, 6.8,   8.4,   9.0,   10.0,  10.2),
-        ThreadSize::M10  => (1.5,  10.0, 8.376, 8.5,   10.5,  11.0,  12.0,  12.7),
-        ThreadSize::M12  => (1.75, 12.0, 10.106,10.2,  13.0,  13.5,  14.5,  15.2),
-        ThreadSize::M14  => (2.0,  14.0, 11.835,12.0,  15.0,  15.5,  16.5,  17.7),
-        ThreadSize::M16  => (2.0,  16.0, 13.835,14.0,  17.0,  17.5,  18.5,  20.2),
-        ThreadSize::M20  => (2.5,  20.0, 17.294,17.5,  21.0,  22.0,  24.0,  25.2),
-        ThreadSize::M24  => (3.0,  24.0, 20.752,21.0,  25.0,  26.0,  28.0,  30.2),
-        ThreadSize::M30  => (3.5,  30.0, 26.211,26.5,  31.0,  33.0,  35.0,  37.7),
-        _ => return None,
+    let ThreadSize::Metric { diameter, pitch } = size else {
+        return None;
+    };
+    let coarse = COARSE.iter().find(
+        |row| matches!(row.0, ThreadSize::Metric { diameter: d, .. } if d == diameter),
+    )?;
+    let (minor, tap) = if coarse.0 == size {
+        (coarse.1, coarse.2)
+    } else {
+        let row = FINE
+            .iter()
+            .find(|row| ThreadSize::metric(row.0, row.1) == size)?;
+        (row.2, row.3)
     };
 
     Some(ThreadDimensions {
-        nominal: data.1,
-        pitch: data.0,
-        major_diameter: data.1,
-        minor_diameter: data.2,
-        tap_drill: data.3,
-        clearance_close: data.4,
-        clearance_medium: data.5,
-        clearance_free: data.6,
-        insert_hole: data.7,
+        nominal: mm(diameter),
+        pitch: mm(pitch),
+        major_diameter: mm(diameter),
+        minor_diameter: minor,
+        tap_drill: tap,
+        clearance_close: coarse.3,
+        clearance_medium: coarse.4,
+        clearance_free: coarse.5,
+        insert_hole: coarse.6,
     })
 }
 
+/// Coarse pitch of a nominal diameter in mm, if it is in the coarse series.
+pub fn coarse_pitch(diameter: f64) -> Option<f64> {
+    let key = (diameter * 1000.0).round() as u32;
+    COARSE.iter().find_map(|row| match row.0 {
+        ThreadSize::Metric { diameter, pitch } if diameter == key => Some(mm(pitch)),
+        _ => None,
+    })
+}
+
+/// Tabulated pitches of a nominal diameter in mm, coarse first. Empty for
+/// diameters outside the coarse series.
+pub fn pitches(diameter: f64) -> Vec<f64> {
+    let key = (diameter * 1000.0).round() as u32;
+    sizes()
+        .filter_map(|size| match size {
+            ThreadSize::Metric { diameter, pitch } if diameter == key => Some(mm(pitch)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Every tabulated size: the coarse series, then the fine series.
+pub fn sizes() -> impl Iterator<Item = ThreadSize> {
+    let fine = FINE.iter().map(|row| ThreadSize::metric(row.0, row.1));
+    COARSE.iter().map(|row| row.0).chain(fine)
+}
+
+fn mm(micrometres: u32) -> f64 {
+    f64::from(micrometres) / 1000.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,20 +202,62 @@ mod tests {
 
     #[test]
     fn all_iso_sizes_present() {
-        let iso_sizes = &ThreadSize::ALL[..15];
-        for size in iso_sizes {
+        assert_eq!(sizes().count(), 64); // 29 coarse + 35 fine
+        for size in sizes() {
+            assert_eq!(size.standard(), crate::ThreadStandard::IsoMetric, "{size}");
             assert!(
-                lookup(*size).is_some(),
+                lookup(size).is_some(),
                 "missing ISO metric data for {size}"
             );
         }
     }
 
+    #[test]
+    fn intermediate_and_large_sizes() {
+        let d = lookup(ThreadSize::M3_5).unwrap();
+        assert_eq!(d.pitch, 0.6);
+        assert_eq!(d.tap_drill, 2.9);
+        let d = lookup(ThreadSize::M27).unwrap();
+        assert_eq!(d.pitch, 3.0);
+        assert_eq!(d.minor_diameter, 23.752);
+        let d = lookup(ThreadSize::M64).unwrap();
+        assert_eq!(d.pitch, 6.0);
+        assert_eq!(d.clearance_medium, 70.0);
+    }
+
+    #[test]
+    fn fine_pitch_dimensions() {
+        let d = lookup(ThreadSize::metric(8.0, 1.0)).unwrap();
+        assert_eq!(d.nominal, 8.0);
+        assert_eq!(d.pitch, 1.0);
+        assert_eq!(d.minor_diameter, 6.917);
+        assert_eq!(d.tap_drill, 7.0);
+        // Clearance and insert holes follow the diameter, not the pitch.
+        let coarse = lookup(ThreadSize::M8).unwrap();
+        assert_eq!(d.clearance_medium, coarse.clearance_medium);
+        assert_eq!(d.insert_hole, coarse.insert_hole);
+
+        let d = lookup(ThreadSize::metric(10.0, 1.25)).unwrap();
+        assert_eq!(d.tap_drill, 8.75);
+    }
+
+    #[test]
+    fn untabulated_pitches_return_none() {
+        assert!(lookup(ThreadSize::metric(8.0, 0.9)).is_none());
+        assert!(lookup(ThreadSize::metric(7.0, 1.0)).is_none());
+    }
+
+    #[test]
+    fn coarse_pitches() {
+        assert_eq!(coarse_pitch(1.6), Some(0.35));
+        assert_eq!(coarse_pitch(12.0), Some(1.75));
+        assert_eq!(coarse_pitch(7.0), None);
+    }
+
     #[test]
     fn all_dimensions_positive() {
-        let iso_sizes = &ThreadSize::ALL[..15];
-        for size in iso_sizes {
-            let d = lookup(*size).unwrap();
+        for size in sizes() {
+            let d = lookup(size).unwrap();
             assert!(d.nominal > 0.0, "{size}: nominal");
             assert!(d.pitch > 0.0, "{size}: pitch");
             assert!(d.major_diameter > 0.0, "{size}: major");
@@ -128,9 +272,8 @@ mod tests {
 
     #[test]
     fn diameter_ordering() {
-        let iso_sizes = &ThreadSize::ALL[..15];
-        for size in iso_sizes {
-            let d = lookup(*size).unwrap();
+        for size in sizes() {
+            let d = lookup(size).unwrap();
             assert!(d.minor_diameter < d.major_diameter, "{size}: minor < major");
             assert!(d.tap_drill > d.minor_diameter, "{size}: tap > minor");
             assert!(d.clearance_close > d.major_diameter, "{size}: cl_close > major");
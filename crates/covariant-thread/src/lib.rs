@@ -1,8 +1,12 @@
 //! Thread standards database and hole geometry for COVARIANT.
 //!
-//! This crate provides thread dimension data for ISO Metric and UTS standards,
-//! hole diameter calculations, and geometry parameters for thread generation.
+//! This crate provides thread dimension data for ISO Metric, UTS, British
+//! Standard Whitworth and British Standard Pipe threads, thread callout
+//! parsing, hole diameter calculations, and geometry parameters for thread
+//! generation.
 
+pub mod bsw;
+pub mod callout;
 pub mod dimensions;
 pub mod geometry;
 pub mod iso_metric;
@@ -10,6 +14,7 @@ pub mod spec;
 pub mod standard;
 pub mod uts;
 
+pub use callout::ThreadCallout;
 pub use dimensions::{
     chamfer_dimensions, clearance_hole_diameter, get_dimensions, hole_diameter,
     ChamferDimensions, ThreadDimensions,
@@ -19,4 +24,4 @@ pub use geometry::{
     ThreadGeometry,
 };
 pub use spec::{ThreadMode, ThreadSpec};
-pub use standard::{ClearanceFit, ThreadKind, ThreadSize, ThreadStandard};
+pub use standard::{ClearanceFit, ThreadKind, ThreadSeries, ThreadSize, ThreadStandard};
@@ -1,4 +1,4 @@
-use crate::{ThreadKind, ThreadSize, ThreadStandard};
+use crate::{ThreadKind, ThreadSeries, ThreadSize, ThreadStandard};
 
 /// How thread geometry should be rendered.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -15,12 +15,15 @@ pub enum ThreadMode {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ThreadSpec {
     pub standard: ThreadStandard,
+    pub series: ThreadSeries,
     pub size: ThreadSize,
     pub kind: ThreadKind,
     /// Hole depth in mm.
     pub depth: f64,
     /// Chamfer depth in mm (0.0 = no chamfer).
     pub chamfer: f64,
+    /// Left-hand thread; `false` for the usual right hand.
+    pub left_hand: bool,
 }
 
 impl ThreadSpec {
@@ -28,10 +31,12 @@ impl ThreadSpec {
     pub fn new(size: ThreadSize, kind: ThreadKind, depth: f64, chamfer: f64) -> Self {
         Self {
             standard: size.standard(),
+            series: size.series(),
             size,
             kind,
             depth,
             chamfer,
+            left_hand: false,
         }
     }
 }
@@ -44,6 +49,7 @@ mod tests {
     fn spec_new_derives_standard() {
         let spec = ThreadSpec::new(ThreadSize::M5, ThreadKind::Internal, 10.0, 0.5);
         assert_eq!(spec.standard, ThreadStandard::IsoMetric);
+        assert_eq!(spec.series, ThreadSeries::MetricCoarse);
         assert_eq!(spec.size, ThreadSize::M5);
         assert_eq!(spec.depth, 10.0);
         assert_eq!(spec.chamfer, 0.5);
@@ -53,6 +59,7 @@ mod tests {
     fn spec_new_uts() {
         let spec = ThreadSpec::new(ThreadSize::Uts1_4_20, ThreadKind::External, 15.0, 0.0);
         assert_eq!(spec.standard, ThreadStandard::Uts);
+        assert_eq!(spec.series, ThreadSeries::Unc);
     }
 
     #[test]
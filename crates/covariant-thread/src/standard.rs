@@ -44,6 +44,35 @@ macro_rules! string_enum {
     };
 }
 
+/// Generates `ThreadSize`: an open ISO metric variant plus the named inch
+/// sizes, with their names and an `INCH` constant.
+macro_rules! inch_sizes {
+    (
+        $( $(#[$vmeta:meta])* $variant:ident => $str:literal ),* $(,)?
+    ) => {
+        /// Individual thread sizes across all standards.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ThreadSize {
+            /// ISO metric `M<diameter>x<pitch>`, both in micrometres so that
+            /// sizes compare and hash exactly.
+            Metric { diameter: u32, pitch: u32 },
+            $( $(#[$vmeta])* $variant ),*
+        }
+
+        impl ThreadSize {
+            /// All named inch sizes (UTS, BSW/BSF and BSPP).
+            pub const INCH: &[Self] = &[ $( Self::$variant ),* ];
+
+            fn inch_name(self) -> Option<&'static str> {
+                match self {
+                    Self::Metric { .. } => None,
+                    $( Self::$variant => Some($str), )*
+                }
+            }
+        }
+    };
+}
+
 string_enum! {
     /// Thread standard families.
     pub enum ThreadStandard {
@@ -51,76 +80,254 @@ string_enum! {
         IsoMetric => "ISO",
         /// ASME B1.1 Unified Thread Standard.
         Uts => "UTS",
-        /// BS 84 British Standard Whitworth (future).
+        /// BS 84 British Standard Whitworth, coarse (BSW) and fine (BSF).
         Bsw => "BSW",
+        /// ISO 228-1 British Standard Pipe parallel threads (BSPP, "G").
+        Bsp => "BSP",
     }
 }
 
 string_enum! {
-    /// Individual thread sizes across all standards.
-    pub enum ThreadSize {
-        // ISO Metric sizes (15 total)
-        M1_6  => "M1.6",
-        M2    => "M2",
-        M2_5  => "M2.5",
-        M3    => "M3",
-        M4    => "M4",
-        M5    => "M5",
-        M6    => "M6",
-        M8    => "M8",
-        M10   => "M10",
-        M12   => "M12",
-        M14   => "M14",
-        M16   => "M16",
-        M20   => "M20",
-        M24   => "M24",
-        M30   => "M30",
-        // UTS sizes (13 total)
-        /// #2-56 UNC
-        Uts2_56    => "#2-56",
-        /// #4-40 UNC
-        Uts4_40    => "#4-40",
-        /// #6-32 UNC
-        Uts6_32    => "#6-32",
-        /// #8-32 UNC
-        Uts8_32    => "#8-32",
-        /// #10-24 UNC
-        Uts10_24   => "#10-24",
-        /// #10-32 UNF
-        Uts10_32   => "#10-32",
-        /// 1/4"-20 UNC
-        Uts1_4_20  => "1/4\"-20",
-        /// 5/16"-18 UNC
-        Uts5_16_18 => "5/16\"-18",
-        /// 3/8"-16 UNC
-        Uts3_8_16  => "3/8\"-16",
-        /// 7/16"-14 UNC
-        Uts7_16_14 => "7/16\"-14",
-        /// 1/2"-13 UNC
-        Uts1_2_13  => "1/2\"-13",
-        /// 5/8"-11 UNC
-        Uts5_8_11  => "5/8\"-11",
-        /// 3/4"-10 UNC
-        Uts3_4_10  => "3/4\"-10",
+    /// Thread series within a standard: the pitch family of a size.
+    pub enum ThreadSeries {
+        /// ISO 261 coarse pitch.
+        MetricCoarse => "coarse",
+        /// ISO 261 fine pitch.
+        MetricFine   => "fine",
+        /// UTS coarse.
+        Unc          => "UNC",
+        /// UTS fine.
+        Unf          => "UNF",
+        /// UTS extra fine.
+        Unef         => "UNEF",
+        /// UTS constant pitch.
+        Un           => "UN",
+        /// UTS special.
+        Uns          => "UNS",
+        /// Whitworth coarse.
+        Bsw          => "BSW",
+        /// Whitworth fine.
+        Bsf          => "BSF",
+        /// Parallel pipe.
+        Bspp         => "G",
     }
 }
 
+inch_sizes! {
+    // UTS sizes (13 total)
+    /// #2-56 UNC
+    Uts2_56    => "#2-56",
+    /// #4-40 UNC
+    Uts4_40    => "#4-40",
+    /// #6-32 UNC
+    Uts6_32    => "#6-32",
+    /// #8-32 UNC
+    Uts8_32    => "#8-32",
+    /// #10-24 UNC
+    Uts10_24   => "#10-24",
+    /// #10-32 UNF
+    Uts10_32   => "#10-32",
+    /// 1/4"-20 UNC
+    Uts1_4_20  => "1/4\"-20",
+    /// 5/16"-18 UNC
+    Uts5_16_18 => "5/16\"-18",
+    /// 3/8"-16 UNC
+    Uts3_8_16  => "3/8\"-16",
+    /// 7/16"-14 UNC
+    Uts7_16_14 => "7/16\"-14",
+    /// 1/2"-13 UNC
+    Uts1_2_13  => "1/2\"-13",
+    /// 5/8"-11 UNC
+    Uts5_8_11  => "5/8\"-11",
+    /// 3/4"-10 UNC
+    Uts3_4_10  => "3/4\"-10",
+    // BSW sizes (11 total)
+    Bsw1_8  => "1/8\" BSW",
+    Bsw3_16 => "3/16\" BSW",
+    Bsw1_4  => "1/4\" BSW",
+    Bsw5_16 => "5/16\" BSW",
+    Bsw3_8  => "3/8\" BSW",
+    Bsw7_16 => "7/16\" BSW",
+    Bsw1_2  => "1/2\" BSW",
+    Bsw5_8  => "5/8\" BSW",
+    Bsw3_4  => "3/4\" BSW",
+    Bsw7_8  => "7/8\" BSW",
+    Bsw1    => "1\" BSW",
+    // BSF sizes (10 total)
+    Bsf1_4  => "1/4\" BSF",
+    Bsf5_16 => "5/16\" BSF",
+    Bsf3_8  => "3/8\" BSF",
+    Bsf7_16 => "7/16\" BSF",
+    Bsf1_2  => "1/2\" BSF",
+    Bsf9_16 => "9/16\" BSF",
+    Bsf5_8  => "5/8\" BSF",
+    Bsf3_4  => "3/4\" BSF",
+    Bsf7_8  => "7/8\" BSF",
+    Bsf1    => "1\" BSF",
+    // BSPP sizes (10 total)
+    G1_8   => "G1/8",
+    G1_4   => "G1/4",
+    G3_8   => "G3/8",
+    G1_2   => "G1/2",
+    G5_8   => "G5/8",
+    G3_4   => "G3/4",
+    G1     => "G1",
+    G1_1_4 => "G1-1/4",
+    G1_1_2 => "G1-1/2",
+    G2     => "G2",
+}
+
 impl ThreadSize {
+    // ISO metric coarse series (ISO 261)
+    pub const M1_6: Self = Self::metric_um(1600, 350);
+    pub const M2: Self = Self::metric_um(2000, 400);
+    pub const M2_5: Self = Self::metric_um(2500, 450);
+    pub const M3: Self = Self::metric_um(3000, 500);
+    pub const M3_5: Self = Self::metric_um(3500, 600);
+    pub const M4: Self = Self::metric_um(4000, 700);
+    pub const M5: Self = Self::metric_um(5000, 800);
+    pub const M6: Self = Self::metric_um(6000, 1000);
+    pub const M8: Self = Self::metric_um(8000, 1250);
+    pub const M10: Self = Self::metric_um(10000, 1500);
+    pub const M12: Self = Self::metric_um(12000, 1750);
+    pub const M14: Self = Self::metric_um(14000, 2000);
+    pub const M16: Self = Self::metric_um(16000, 2000);
+    pub const M18: Self = Self::metric_um(18000, 2500);
+    pub const M20: Self = Self::metric_um(20000, 2500);
+    pub const M22: Self = Self::metric_um(22000, 2500);
+    pub const M24: Self = Self::metric_um(24000, 3000);
+    pub const M27: Self = Self::metric_um(27000, 3000);
+    pub const M30: Self = Self::metric_um(30000, 3500);
+    pub const M33: Self = Self::metric_um(33000, 3500);
+    pub const M36: Self = Self::metric_um(36000, 4000);
+    pub const M39: Self = Self::metric_um(39000, 4000);
+    pub const M42: Self = Self::metric_um(42000, 4500);
+    pub const M45: Self = Self::metric_um(45000, 4500);
+    pub const M48: Self = Self::metric_um(48000, 5000);
+    pub const M52: Self = Self::metric_um(52000, 5000);
+    pub const M56: Self = Self::metric_um(56000, 5500);
+    pub const M60: Self = Self::metric_um(60000, 5500);
+    pub const M64: Self = Self::metric_um(64000, 6000);
+
+    /// ISO metric size from its nominal diameter and pitch in mm.
+    pub fn metric(diameter: f64, pitch: f64) -> Self {
+        Self::metric_um((diameter * 1000.0).round() as u32, (pitch * 1000.0).round() as u32)
+    }
+
+    const fn metric_um(diameter: u32, pitch: u32) -> Self {
+        Self::Metric { diameter, pitch }
+    }
+
+    /// Every size with tabulated dimensions: the ISO metric coarse and fine
+    /// series, then the inch sizes.
+    pub fn all() -> impl Iterator<Item = Self> {
+        crate::iso_metric::sizes().chain(Self::INCH.iter().copied())
+    }
+
     /// Returns which standard this size belongs to.
     pub fn standard(self) -> ThreadStandard {
         match self {
-            Self::M1_6 | Self::M2 | Self::M2_5 | Self::M3 | Self::M4 | Self::M5
-            | Self::M6 | Self::M8 | Self::M10 | Self::M12 | Self::M14 | Self::M16
-            | Self::M20 | Self::M24 | Self::M30 => ThreadStandard::IsoMetric,
+            Self::Metric { .. } => ThreadStandard::IsoMetric,
 
             Self::Uts2_56 | Self::Uts4_40 | Self::Uts6_32 | Self::Uts8_32
             | Self::Uts10_24 | Self::Uts10_32 | Self::Uts1_4_20 | Self::Uts5_16_18
             | Self::Uts3_8_16 | Self::Uts7_16_14 | Self::Uts1_2_13 | Self::Uts5_8_11
             | Self::Uts3_4_10 => ThreadStandard::Uts,
+
+            Self::Bsw1_8 | Self::Bsw3_16 | Self::Bsw1_4 | Self::Bsw5_16 | Self::Bsw3_8
+            | Self::Bsw7_16 | Self::Bsw1_2 | Self::Bsw5_8 | Self::Bsw3_4 | Self::Bsw7_8
+            | Self::Bsw1 | Self::Bsf1_4 | Self::Bsf5_16 | Self::Bsf3_8 | Self::Bsf7_16
+            | Self::Bsf1_2 | Self::Bsf9_16 | Self::Bsf5_8 | Self::Bsf3_4 | Self::Bsf7_8
+            | Self::Bsf1 => ThreadStandard::Bsw,
+
+            Self::G1_8 | Self::G1_4 | Self::G3_8 | Self::G1_2 | Self::G5_8 | Self::G3_4
+            | Self::G1 | Self::G1_1_4 | Self::G1_1_2 | Self::G2 => ThreadStandard::Bsp,
         }
     }
 }
 
+impl ThreadSize {
+    /// Returns which series of its standard this size belongs to.
+    pub fn series(self) -> ThreadSeries {
+        match self {
+            Self::Metric { diameter, pitch } => {
+                let mm = |um: u32| f64::from(um) / 1000.0;
+                if crate::iso_metric::coarse_pitch(mm(diameter)) == Some(mm(pitch)) {
+                    ThreadSeries::MetricCoarse
+                } else {
+                    ThreadSeries::MetricFine
+                }
+            }
+
+            Self::Uts10_32 => ThreadSeries::Unf,
+
+            Self::Bsf1_4 | Self::Bsf5_16 | Self::Bsf3_8 | Self::Bsf7_16 | Self::Bsf1_2
+            | Self::Bsf9_16 | Self::Bsf5_8 | Self::Bsf3_4 | Self::Bsf7_8
+            | Self::Bsf1 => ThreadSeries::Bsf,
+
+            _ => match self.standard() {
+                ThreadStandard::Uts => ThreadSeries::Unc,
+                ThreadStandard::Bsw => ThreadSeries::Bsw,
+                _ => ThreadSeries::Bspp,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ThreadSize {
+    /// Metric sizes print as `M8` in the coarse series and `M8x1` otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Metric { diameter, pitch } => {
+                let mm = |um: u32| f64::from(um) / 1000.0;
+                write!(f, "M{}", mm(diameter))?;
+                if crate::iso_metric::coarse_pitch(mm(diameter)) != Some(mm(pitch)) {
+                    write!(f, "x{}", mm(pitch))?;
+                }
+                Ok(())
+            }
+            _ => f.write_str(self.inch_name().unwrap_or_default()),
+        }
+    }
+}
+
+impl FromStr for ThreadSize {
+    type Err = String;
+
+    /// Parses the display form: `M8` (coarse pitch), `M8x1` or an inch size
+    /// such as `1/4"-20`. Metric sizes must be in the ISO coarse or fine
+    /// series.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(size) = Self::INCH.iter().find(|size| size.inch_name() == Some(s)) {
+            return Ok(*size);
+        }
+        let unknown = || format!("unknown ThreadSize: {s}");
+        let metric = s.strip_prefix('M').ok_or_else(unknown)?;
+        let (diameter, pitch) = match metric.split_once('x') {
+            Some((d, p)) => (d, Some(p)),
+            None => (metric, None),
+        };
+        let diameter: f64 = diameter.parse().map_err(|_| unknown())?;
+        let pitch = match pitch {
+            Some(p) => p.parse().map_err(|_| unknown())?,
+            None => crate::iso_metric::coarse_pitch(diameter).ok_or_else(unknown)?,
+        };
+        let size = Self::metric(diameter, pitch);
+        if crate::iso_metric::lookup(size).is_some() {
+            return Ok(size);
+        }
+        let pitches = crate::iso_metric::pitches(diameter);
+        if pitches.is_empty() {
+            return Err(format!("{s} is not an ISO metric size; there is no M{diameter}"));
+        }
+        let pitches: Vec<String> = pitches.iter().map(f64::to_string).collect();
+        Err(format!(
+            "{s} is not an ISO metric size; M{diameter} comes in pitches {}",
+            pitches.join(", ")
+        ))
+    }
+}
+
 string_enum! {
     /// Thread type (internal/external).
     pub enum ThreadKind {
@@ -179,31 +386,84 @@ mod tests {
 
     #[test]
     fn thread_size_roundtrip() {
-        assert_display_roundtrip(ThreadSize::ALL);
+        let sizes: Vec<ThreadSize> = ThreadSize::all().collect();
+        assert_display_roundtrip(&sizes);
+    }
+
+    #[test]
+    fn thread_size_metric_display() {
+        assert_eq!(ThreadSize::M1_6.to_string(), "M1.6");
+        assert_eq!(ThreadSize::M8.to_string(), "M8");
+        assert_eq!(ThreadSize::metric(8.0, 1.0).to_string(), "M8x1");
+        assert_eq!(ThreadSize::metric(10.0, 1.25).to_string(), "M10x1.25");
+        assert_eq!("M8x1.25".parse(), Ok(ThreadSize::M8));
+        assert_eq!("M3.5".parse(), Ok(ThreadSize::M3_5));
     }
 
     #[test]
     fn thread_size_iso_standard() {
-        for size in &ThreadSize::ALL[..15] {
-            assert_eq!(size.standard(), ThreadStandard::IsoMetric, "{size}");
-        }
+        assert_eq!(ThreadSize::M5.standard(), ThreadStandard::IsoMetric);
+        assert_eq!(ThreadSize::metric(12.0, 1.5).standard(), ThreadStandard::IsoMetric);
     }
 
     #[test]
     fn thread_size_uts_standard() {
-        for size in &ThreadSize::ALL[15..] {
+        for size in &ThreadSize::INCH[..13] {
             assert_eq!(size.standard(), ThreadStandard::Uts, "{size}");
         }
     }
 
+    #[test]
+    fn thread_size_british_standards() {
+        for size in &ThreadSize::INCH[13..34] {
+            assert_eq!(size.standard(), ThreadStandard::Bsw, "{size}");
+        }
+        for size in &ThreadSize::INCH[34..] {
+            assert_eq!(size.standard(), ThreadStandard::Bsp, "{size}");
+        }
+    }
+
+    #[test]
+    fn thread_size_series() {
+        assert_eq!(ThreadSize::M8.series(), ThreadSeries::MetricCoarse);
+        assert_eq!(ThreadSize::metric(8.0, 1.0).series(), ThreadSeries::MetricFine);
+        assert_eq!(ThreadSize::Uts10_24.series(), ThreadSeries::Unc);
+        assert_eq!(ThreadSize::Uts10_32.series(), ThreadSeries::Unf);
+        for size in &ThreadSize::INCH[13..24] {
+            assert_eq!(size.series(), ThreadSeries::Bsw, "{size}");
+        }
+        for size in &ThreadSize::INCH[24..34] {
+            assert_eq!(size.series(), ThreadSeries::Bsf, "{size}");
+        }
+        assert_eq!(ThreadSize::G1_2.series(), ThreadSeries::Bspp);
+    }
+
     #[test]
     fn thread_size_all_count() {
-        assert_eq!(ThreadSize::ALL.len(), 28); // 15 ISO + 13 UTS
+        assert_eq!(ThreadSize::INCH.len(), 44); // 13 UTS + 11 BSW + 10 BSF + 10 BSP
+        assert_eq!(ThreadSize::all().count(), 108); // + 29 coarse and 35 fine metric
     }
 
     #[test]
     fn thread_size_from_str_error() {
         assert!("M99".parse::<ThreadSize>().is_err());
+        assert!("M8x0".parse::<ThreadSize>().is_err());
+        assert!("M8x".parse::<ThreadSize>().is_err());
+        assert!("8x1".parse::<ThreadSize>().is_err());
+    }
+
+    #[test]
+    fn thread_size_from_str_rejects_untabulated_metric_sizes() {
+        assert_eq!(
+            "M7x0.9".parse::<ThreadSize>(),
+            Err("M7x0.9 is not an ISO metric size; there is no M7".to_string())
+        );
+        assert_eq!(
+            "M8x0.9".parse::<ThreadSize>(),
+            Err("M8x0.9 is not an ISO metric size; M8 comes in pitches 1.25, 1, 0.75".to_string())
+        );
+        assert!("M8x0".parse::<ThreadSize>().unwrap_err().contains("1.25, 1, 0.75"));
+        assert!("M3.5x0.35".parse::<ThreadSize>().unwrap_err().contains("M3.5 comes in"));
     }
 
     #[test]
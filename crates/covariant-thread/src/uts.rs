@@ -79,7 +79,7 @@ mod tests {
 
     #[test]
     fn all_uts_sizes_present() {
-        let uts_sizes = &ThreadSize::ALL[15..];
+        let uts_sizes = &ThreadSize::INCH[..13];
         for size in uts_sizes {
             assert!(
                 lookup(*size).is_some(),
@@ -90,7 +90,7 @@ mod tests {
 
     #[test]
     fn all_dimensions_positive() {
-        let uts_sizes = &ThreadSize::ALL[15..];
+        let uts_sizes = &ThreadSize::INCH[..13];
         for size in uts_sizes {
             let d = lookup(*size).unwrap();
             assert!(d.nominal > 0.0, "{size}: nominal");
@@ -102,7 +102,7 @@ mod tests {
 
     #[test]
     fn diameter_ordering() {
-        let uts_sizes = &ThreadSize::ALL[15..];
+        let uts_sizes = &ThreadSize::INCH[..13];
         for size in uts_sizes {
             let d = lookup(*size).unwrap();
             assert!(d.minor_diameter < d.major_diameter, "{size}: minor < major");
@@ -67,10 +67,27 @@ fn uts_end_to_end_full() {
     }
 }
 
+#[test]
+fn bsp_end_to_end_full() {
+    let spec = ThreadSpec::new(ThreadSize::G1_4, ThreadKind::Internal, 12.0, 0.5);
+    assert_eq!(spec.standard, ThreadStandard::Bsp);
+    let geom = generate_thread_geometry(&spec, ThreadMode::Full).unwrap();
+    match geom {
+        ThreadGeometry::Full {
+            cylinder, helix, ..
+        } => {
+            assert_eq!(cylinder.diameter, 11.8); // G1/4 tap drill
+            assert!((helix.major_diameter - 13.157).abs() < 0.001);
+            assert!((helix.pitch - 25.4 / 19.0).abs() < 1e-9);
+        }
+        _ => panic!("expected Full"),
+    }
+}
+
 #[test]
 fn every_iso_size_produces_valid_geometry() {
-    for size in &ThreadSize::ALL[..15] {
-        let spec = ThreadSpec::new(*size, ThreadKind::Internal, 10.0, 0.5);
+    for size in iso_metric::sizes() {
+        let spec = ThreadSpec::new(size, ThreadKind::Internal, 10.0, 0.5);
         let geom = generate_thread_geometry(&spec, ThreadMode::Full).unwrap();
         match geom {
             ThreadGeometry::Full {
@@ -90,7 +107,7 @@ fn every_iso_size_produces_valid_geometry() {
 
 #[test]
 fn every_uts_size_produces_valid_geometry() {
-    for size in &ThreadSize::ALL[15..] {
+    for size in &ThreadSize::INCH[..13] {
         let spec = ThreadSpec::new(*size, ThreadKind::Internal, 10.0, 0.5);
         let geom = generate_thread_geometry(&spec, ThreadMode::Full).unwrap();
         match geom {